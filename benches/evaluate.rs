@@ -0,0 +1,50 @@
+use bevy_math::Vec2;
+use bezier::{CubicBezier, intersections};
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+fn curve() -> CubicBezier {
+    CubicBezier::new(
+        Vec2::new(0.0, 0.0),
+        Vec2::new(40.0, 120.0),
+        Vec2::new(160.0, -60.0),
+        Vec2::new(200.0, 40.0),
+    )
+}
+
+fn evaluate(c: &mut Criterion) {
+    let curve = curve();
+    let ts: Vec<f32> = (0..1000).map(|i| i as f32 / 999.0).collect();
+    c.bench_function("point_at x1000", |b| {
+        b.iter(|| {
+            black_box(&ts)
+                .iter()
+                .map(|&t| curve.point_at(t))
+                .collect::<Vec<Vec2>>()
+        })
+    });
+    c.bench_function("eval_many x1000", |b| {
+        b.iter(|| curve.eval_many(black_box(&ts)))
+    });
+}
+
+fn flatten(c: &mut Criterion) {
+    let curve = curve();
+    c.bench_function("flatten 0.1", |b| b.iter(|| black_box(curve).flatten(0.1)));
+}
+
+fn intersect(c: &mut Criterion) {
+    let first = curve();
+    let second = CubicBezier::new(
+        Vec2::new(0.0, 60.0),
+        Vec2::new(60.0, -80.0),
+        Vec2::new(140.0, 140.0),
+        Vec2::new(200.0, -20.0),
+    );
+    c.bench_function("intersections", |b| {
+        b.iter(|| intersections(black_box(&first), black_box(&second)))
+    });
+}
+
+criterion_group!(benches, evaluate, flatten, intersect);
+criterion_main!(benches);
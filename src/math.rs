@@ -0,0 +1,2373 @@
+//! Curve types and geometry. Depends only on `bevy_math` and a bare
+//! `bevy_transform`, so it is available without the `render` feature.
+use bevy_math::{Quat, Rect, Vec2};
+use bevy_transform::components::Transform;
+
+mod bevy_splines;
+pub use bevy_splines::*;
+mod bezier3;
+pub use bezier3::*;
+mod boolean;
+pub use boolean::*;
+mod plot;
+pub use plot::*;
+mod stroke;
+pub use stroke::*;
+#[cfg(feature = "ttf")]
+mod glyph;
+#[cfg(feature = "ttf")]
+pub use glyph::*;
+
+/// A cubic bezier segment by value: anchors `a` and `d`, controls `b` and
+/// `c`. Methods are thin wrappers over the free functions in this module.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CubicBezier {
+    pub a: Vec2,
+    pub b: Vec2,
+    pub c: Vec2,
+    pub d: Vec2,
+}
+
+impl CubicBezier {
+    pub fn new(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> Self {
+        Self { a, b, c, d }
+    }
+    pub fn points(&self) -> [Vec2; 4] {
+        [self.a, self.b, self.c, self.d]
+    }
+    /// The curve from `p0` to `p1` leaving with velocity `m0` and arriving
+    /// with velocity `m1`, as in a cubic Hermite segment.
+    pub fn from_hermite(p0: Vec2, m0: Vec2, p1: Vec2, m1: Vec2) -> Self {
+        Self::new(p0, p0 + m0 / 3.0, p1 - m1 / 3.0, p1)
+    }
+    /// The uniform Catmull-Rom segment between `p1` and `p2`, with `p0` and
+    /// `p3` the waypoints before and after it.
+    pub fn from_catmull_rom(p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2) -> Self {
+        Self::from_hermite(p1, (p2 - p0) * 0.5, p2, (p3 - p1) * 0.5)
+    }
+    /// Point on the curve at `t` in `[0, 1]`.
+    pub fn point_at(&self, t: f32) -> Vec2 {
+        evaluate_bezier(self.a, self.b, self.c, self.d, t)
+    }
+    /// Points at each of `ts`, see `evaluate_bezier_many`.
+    pub fn eval_many(&self, ts: &[f32]) -> Vec<Vec2> {
+        evaluate_bezier_many(self, ts)
+    }
+    /// First derivative at `t`; its length is the speed of the parameter.
+    pub fn derivative_at(&self, t: f32) -> Vec2 {
+        bezier_derivative(self.a, self.b, self.c, self.d, t)
+    }
+    /// Unit direction of travel at `t`, or zero where the curve stalls.
+    pub fn tangent_at(&self, t: f32) -> Vec2 {
+        self.derivative_at(t).normalize_or_zero()
+    }
+    /// Position, unit tangent and unit normal (the tangent turned a quarter
+    /// counter-clockwise) at `t`. Where the curve stalls, e.g. at an anchor
+    /// sitting on its control, the tangent comes from the chord towards the
+    /// rest of the curve instead.
+    pub fn frame_at(&self, t: f32) -> (Vec2, Vec2, Vec2) {
+        let position = self.point_at(t);
+        let tangent = match self.tangent_at(t) {
+            Vec2::ZERO if t < 0.5 => (self.point_at(t + 0.01) - position).normalize_or_zero(),
+            Vec2::ZERO => (position - self.point_at(t - 0.01)).normalize_or_zero(),
+            tangent => tangent,
+        };
+        (position, tangent, tangent.perp())
+    }
+    /// Transforms spread along the curve by arc length, each with its x axis
+    /// along the curve, e.g. to scatter trees along a road.
+    pub fn sample_transforms(&self, spacing: Spacing) -> Vec<Transform> {
+        let length = self.arc_length();
+        let distances: Vec<f32> = match spacing {
+            Spacing::Count(0) => Vec::new(),
+            Spacing::Count(1) => vec![0.0],
+            Spacing::Count(n) => (0..n).map(|i| length * i as f32 / (n - 1) as f32).collect(),
+            Spacing::Distance(step) if step > 0.0 => (0..)
+                .map(|i| i as f32 * step)
+                .take_while(|distance| *distance <= length)
+                .collect(),
+            Spacing::Distance(_) => vec![0.0],
+        };
+        distances
+            .into_iter()
+            .map(|distance| self.transform_at(self.t_at_distance(distance)))
+            .collect()
+    }
+    /// Transform at `t` with its x axis along the curve and its y axis
+    /// along the normal, at `z = 0`.
+    pub fn transform_at(&self, t: f32) -> Transform {
+        let (position, tangent, _normal) = self.frame_at(t);
+        let rotation = if tangent == Vec2::ZERO {
+            Quat::IDENTITY
+        } else {
+            Quat::from_rotation_z(tangent.to_angle())
+        };
+        Transform::from_translation(position.extend(0.0)).with_rotation(rotation)
+    }
+    /// Signed curvature at `t`, positive where the curve turns
+    /// counter-clockwise. The reciprocal of the radius of the osculating
+    /// circle.
+    pub fn curvature_at(&self, t: f32) -> f32 {
+        bezier_curvature(self.a, self.b, self.c, self.d, t)
+    }
+    /// Parameters where the curvature changes sign, in increasing order.
+    pub fn inflection_points(&self) -> Vec<f32> {
+        bezier_inflections(self.a, self.b, self.c, self.d)
+    }
+    /// Length of the curve.
+    pub fn arc_length(&self) -> f32 {
+        bezier_length(self.a, self.b, self.c, self.d)
+    }
+    /// The curve with one handle lengthened or shortened along its current
+    /// direction until the arc length is `length`, within `tolerance`: the
+    /// `a`–`b` handle if `at_start`, otherwise the `d`–`c` one. When the
+    /// length can't be reached, e.g. the anchors are further apart than
+    /// `length`, gets as close as it can.
+    pub fn with_arc_length(&self, length: f32, tolerance: f32, at_start: bool) -> CubicBezier {
+        let (anchor, handle) = if at_start {
+            (self.a, self.b)
+        } else {
+            (self.d, self.c)
+        };
+        // a handle sitting on its anchor has no direction; pull it towards
+        // the other end
+        let direction = (handle - anchor)
+            .try_normalize()
+            .or_else(|| {
+                (if at_start {
+                    self.d - self.a
+                } else {
+                    self.a - self.d
+                })
+                .try_normalize()
+            })
+            .unwrap_or(Vec2::X);
+        let with_handle = |reach: f32| {
+            let handle = anchor + direction * reach;
+            if at_start {
+                CubicBezier::new(self.a, handle, self.c, self.d)
+            } else {
+                CubicBezier::new(self.a, self.b, handle, self.d)
+            }
+        };
+        let (mut low, mut high) = (0.0, handle.distance(anchor).max(1.0));
+        let mut grow = 0;
+        while with_handle(high).arc_length() < length && grow < 16 {
+            low = high;
+            high *= 2.0;
+            grow += 1;
+        }
+        let mut curve = with_handle(high);
+        for _ in 0..32 {
+            let reach = (low + high) * 0.5;
+            curve = with_handle(reach);
+            let error = curve.arc_length() - length;
+            if error.abs() <= tolerance {
+                break;
+            }
+            if error < 0.0 {
+                low = reach;
+            } else {
+                high = reach;
+            }
+        }
+        curve
+    }
+    /// Parameter reached after travelling `distance` along the curve.
+    pub fn t_at_distance(&self, distance: f32) -> f32 {
+        t_at_distance(self.a, self.b, self.c, self.d, distance)
+    }
+    /// Point reached after travelling `distance` along the curve from `a`,
+    /// for moving along it at constant speed. Clamped to the curve's ends.
+    pub fn point_at_distance(&self, distance: f32) -> Vec2 {
+        self.point_at(self.t_at_distance(distance))
+    }
+    /// Splits the curve at `t` into two curves that together trace it.
+    pub fn split(&self, t: f32) -> (CubicBezier, CubicBezier) {
+        let (first, second) = split_bezier(self.a, self.b, self.c, self.d, t);
+        (
+            CubicBezier::new(first.0, first.1, first.2, first.3),
+            CubicBezier::new(second.0, second.1, second.2, second.3),
+        )
+    }
+    /// The part of the curve between `t0` and `t1`, as a curve of its own
+    /// running from `point_at(t0)` to `point_at(t1)`. Reversed when
+    /// `t1 < t0`.
+    pub fn trim(&self, t0: f32, t1: f32) -> CubicBezier {
+        let (from, to) = (t0.clamp(0.0, 1.0), t1.clamp(0.0, 1.0));
+        let (low, high) = (from.min(to), from.max(to));
+        let (head, _) = self.split(high);
+        let trimmed = if high > 0.0 {
+            head.split(low / high).1
+        } else {
+            CubicBezier::new(self.a, self.a, self.a, self.a)
+        };
+        if from <= to {
+            trimmed
+        } else {
+            CubicBezier::new(trimmed.d, trimmed.c, trimmed.b, trimmed.a)
+        }
+    }
+    /// Smallest box containing the curve, unlike `bezier_bounds` which
+    /// also contains the control points.
+    pub fn bounding_box(&self) -> Rect {
+        bezier_tight_bounds(self.a, self.b, self.c, self.d)
+    }
+    /// Parameter, position and distance of the point on the curve closest
+    /// to `p`, e.g. to snap an object onto the curve or to split it where
+    /// it was clicked.
+    pub fn closest_point(&self, p: Vec2) -> (f32, Vec2, f32) {
+        let (t, closest) = closest_point_on_bezier(self.a, self.b, self.c, self.d, p);
+        (t, closest, closest.distance(p))
+    }
+    /// Parameter of the point on the curve closest to `p`, and the distance
+    /// to it.
+    pub fn distance_to_point(&self, p: Vec2) -> (f32, f32) {
+        let (t, closest) = closest_point_on_bezier(self.a, self.b, self.c, self.d, p);
+        (t, closest.distance(p))
+    }
+    /// Cubics approximating the curve `distance` to its left (right when
+    /// negative), e.g. for road edges or outlines.
+    pub fn offset(&self, distance: f32) -> Vec<CubicBezier> {
+        offset_bezier(self.a, self.b, self.c, self.d, distance)
+            .into_iter()
+            .map(|(a, b, c, d)| CubicBezier::new(a, b, c, d))
+            .collect()
+    }
+    /// First hit of a ray from `origin` along `dir`, see `raycast`.
+    pub fn raycast(&self, origin: Vec2, dir: Vec2) -> Option<(f32, f32, Vec2)> {
+        raycast(self, origin, dir)
+    }
+    /// The curve bent so its point at `t` lands on `target`, see
+    /// `bend_bezier`.
+    pub fn bend(&self, t: f32, target: Vec2) -> CubicBezier {
+        bend_bezier(self, t, target)
+    }
+    /// Whether the curve is the graph of a function y = f(x), see
+    /// `is_x_monotonic`.
+    pub fn is_x_monotonic(&self) -> bool {
+        is_x_monotonic(self)
+    }
+    /// The parameter where the curve's x is `x`, see `solve_t_for_x`.
+    pub fn solve_t_for_x(&self, x: f32) -> Option<f32> {
+        solve_t_for_x(self, x)
+    }
+    /// y of the curve read as y = f(x), at `x`.
+    pub fn y_for_x(&self, x: f32) -> Option<f32> {
+        solve_t_for_x(self, x).map(|t| self.point_at(t).y)
+    }
+    /// The nearest curve that is a function of x, see `to_x_monotonic`.
+    pub fn to_x_monotonic(&self) -> CubicBezier {
+        to_x_monotonic(self)
+    }
+    /// Where the curve crosses itself, see `self_intersections`.
+    pub fn self_intersections(&self) -> Vec<(f32, f32, Vec2)> {
+        self_intersections(self)
+    }
+    /// Polyline through the curve staying within `tolerance` of it, found by
+    /// adaptive de Casteljau subdivision. Starts at `a` and ends at `d`.
+    pub fn flatten(&self, tolerance: f32) -> Vec<Vec2> {
+        flatten_bezier(self.a, self.b, self.c, self.d, tolerance)
+    }
+    /// `n` points at evenly spaced parameters, from `a` to `d` inclusive.
+    pub fn sample(&self, n: usize) -> Vec<Vec2> {
+        match n {
+            0 => Vec::new(),
+            1 => vec![self.a],
+            _ => {
+                let ts: Vec<f32> = (0..n).map(|i| i as f32 / (n - 1) as f32).collect();
+                self.eval_many(&ts)
+            }
+        }
+    }
+}
+
+/// How `CubicBezier::sample_transforms` spreads its samples.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Spacing {
+    /// This many samples, evenly spaced from end to end.
+    Count(usize),
+    /// One sample every this many world units from the start.
+    Distance(f32),
+}
+
+impl From<[Vec2; 4]> for CubicBezier {
+    fn from([a, b, c, d]: [Vec2; 4]) -> Self {
+        Self { a, b, c, d }
+    }
+}
+
+/// Hashes the exact coordinates, agreeing with `PartialEq`: `-0.0` and
+/// `0.0` hash alike. Curves with NaN coordinates never compare equal, so
+/// don't key maps with them. Use `quantized` to treat nearly equal curves
+/// as the same.
+impl std::hash::Hash for CubicBezier {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for point in self.points() {
+            for value in [point.x, point.y] {
+                // `+ 0.0` turns -0.0 into 0.0
+                (value + 0.0).to_bits().hash(state);
+            }
+        }
+    }
+}
+
+/// A `CubicBezier` with its coordinates rounded to a grid, see
+/// `CubicBezier::quantized`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct QuantizedBezier(pub [[i64; 2]; 4]);
+
+impl CubicBezier {
+    /// This curve's coordinates rounded to multiples of `epsilon`, as a key
+    /// that is equal and hashes alike for curves differing by float noise.
+    /// Curves straddling a grid line can still land on different keys.
+    pub fn quantized(&self, epsilon: f32) -> QuantizedBezier {
+        let epsilon = epsilon.max(f32::EPSILON);
+        QuantizedBezier(
+            self.points()
+                .map(|point| [point.x, point.y].map(|value| (value / epsilon).round() as i64)),
+        )
+    }
+}
+
+/// A quadratic bezier segment: anchors `a` and `d` with a single control `q`,
+/// as used by TrueType fonts and SVG `Q` commands.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct QuadraticBezier {
+    pub a: Vec2,
+    pub q: Vec2,
+    pub d: Vec2,
+}
+
+impl QuadraticBezier {
+    pub fn new(a: Vec2, q: Vec2, d: Vec2) -> Self {
+        Self { a, q, d }
+    }
+    pub fn points(&self) -> [Vec2; 3] {
+        [self.a, self.q, self.d]
+    }
+    /// Point on the curve at `t` in `[0, 1]`.
+    pub fn point_at(&self, t: f32) -> Vec2 {
+        let mt = 1.0 - t;
+        mt * mt * self.a + 2.0 * mt * t * self.q + t * t * self.d
+    }
+    /// Polyline through the curve staying within `tolerance` of it.
+    pub fn flatten(&self, tolerance: f32) -> Vec<Vec2> {
+        self.elevate().flatten(tolerance)
+    }
+    /// The same curve as a cubic (degree elevation), which is exact.
+    pub fn elevate(&self) -> CubicBezier {
+        let (b, c) = elevate_quadratic(self.a, self.q, self.d);
+        CubicBezier::new(self.a, b, c, self.d)
+    }
+}
+
+impl From<[Vec2; 3]> for QuadraticBezier {
+    fn from([a, q, d]: [Vec2; 3]) -> Self {
+        Self { a, q, d }
+    }
+}
+
+impl From<QuadraticBezier> for CubicBezier {
+    fn from(quadratic: QuadraticBezier) -> Self {
+        quadratic.elevate()
+    }
+}
+
+impl CubicBezier {
+    /// Best single-control quadratic for this cubic. Exact when the cubic
+    /// was elevated from a quadratic, an approximation otherwise.
+    pub fn to_quadratic(&self) -> QuadraticBezier {
+        QuadraticBezier::new(
+            self.a,
+            reduce_to_quadratic(self.a, self.b, self.c, self.d),
+            self.d,
+        )
+    }
+    /// The same curve as a quartic's five control points (degree elevation),
+    /// for tools that want a higher degree.
+    pub fn elevate(&self) -> [Vec2; 5] {
+        elevate_cubic(self.a, self.b, self.c, self.d)
+    }
+    /// The quadratic from `to_quadratic` if it stays within `tolerance` of
+    /// this curve everywhere, e.g. to store imported cubics that were really
+    /// quadratics in the smaller form.
+    pub fn try_reduce(&self, tolerance: f32) -> Option<QuadraticBezier> {
+        (quadratic_reduction_error(self.a, self.b, self.c, self.d) <= tolerance)
+            .then(|| self.to_quadratic())
+    }
+}
+
+/// Quartic control points tracing the same curve as cubic `a, b, c, d`.
+pub fn elevate_cubic(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> [Vec2; 5] {
+    [a, a.lerp(b, 0.75), b.lerp(c, 0.5), c.lerp(d, 0.25), d]
+}
+
+/// Upper bound on how far `reduce_to_quadratic`'s curve strays from cubic
+/// `a, b, c, d`. Zero exactly when the cubic is an elevated quadratic.
+pub fn quadratic_reduction_error(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> f32 {
+    (d - 3.0 * c + 3.0 * b - a).length() * 3.0_f32.sqrt() / 36.0
+}
+
+/// Cubic controls `(b, c)` tracing the same curve as quadratic `a, q, d`.
+pub fn elevate_quadratic(a: Vec2, q: Vec2, d: Vec2) -> (Vec2, Vec2) {
+    (a + (q - a) * (2.0 / 3.0), d + (q - d) * (2.0 / 3.0))
+}
+
+/// Quadratic control closest to cubic `a, b, c, d`: the average of the
+/// controls each cubic handle implies.
+pub fn reduce_to_quadratic(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> Vec2 {
+    (3.0 * (b + c) - a - d) / 4.0
+}
+
+/// Conservative bounds of a cubic bezier: the box around its four control
+/// points, which always contains the curve.
+pub fn bezier_bounds(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> Rect {
+    Rect::from_corners(a.min(b).min(c).min(d), a.max(b).max(c).max(d))
+}
+
+/// Tight bounds of a cubic bezier: the box around its end points and the
+/// extremes where the derivative's x or y component is zero.
+pub fn bezier_tight_bounds(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> Rect {
+    // B'(t) / 3 = (d - 3c + 3b - a) t² + 2 (a - 2b + c) t + (b - a)
+    let p = d - 3.0 * c + 3.0 * b - a;
+    let q = 2.0 * (a - 2.0 * b + c);
+    let r = b - a;
+    let mut bounds = Rect::from_corners(a, d);
+    for (p, q, r) in [(p.x, q.x, r.x), (p.y, q.y, r.y)] {
+        for t in solve_quadratic(p, q, r) {
+            if (0.0..=1.0).contains(&t) {
+                bounds = bounds.union_point(evaluate_bezier(a, b, c, d, t));
+            }
+        }
+    }
+    bounds
+}
+
+/// Splits a cubic bezier at `t` with de Casteljau's algorithm, returning the
+/// control points of the two halves.
+#[allow(clippy::type_complexity)]
+pub fn split_bezier(
+    a: Vec2,
+    b: Vec2,
+    c: Vec2,
+    d: Vec2,
+    t: f32,
+) -> ((Vec2, Vec2, Vec2, Vec2), (Vec2, Vec2, Vec2, Vec2)) {
+    let ab = a.lerp(b, t);
+    let bc = b.lerp(c, t);
+    let cd = c.lerp(d, t);
+    let abc = ab.lerp(bc, t);
+    let bcd = bc.lerp(cd, t);
+    let mid = abc.lerp(bcd, t);
+    ((a, ab, abc, mid), (mid, bcd, cd, d))
+}
+
+const MAX_FLATTEN_DEPTH: u32 = 16;
+
+/// Flattens a cubic bezier into a polyline whose points stay within
+/// `tolerance` of the curve. Flat stretches get few points, tight bends many.
+/// The result starts at `a` and ends at `d`.
+pub fn flatten_bezier(a: Vec2, b: Vec2, c: Vec2, d: Vec2, tolerance: f32) -> Vec<Vec2> {
+    let mut points = vec![a];
+    flatten_into(a, b, c, d, tolerance.max(f32::EPSILON), 0, &mut points);
+    points
+}
+
+fn flatten_into(
+    a: Vec2,
+    b: Vec2,
+    c: Vec2,
+    d: Vec2,
+    tolerance: f32,
+    depth: u32,
+    points: &mut Vec<Vec2>,
+) {
+    let u = 3.0 * b - 2.0 * a - d;
+    let v = 3.0 * c - a - 2.0 * d;
+    let flatness = (u * u).max(v * v);
+    if depth >= MAX_FLATTEN_DEPTH || flatness.x + flatness.y <= 16.0 * tolerance * tolerance {
+        points.push(d);
+        return;
+    }
+    let (first, second) = split_bezier(a, b, c, d, 0.5);
+    flatten_into(
+        first.0,
+        first.1,
+        first.2,
+        first.3,
+        tolerance,
+        depth + 1,
+        points,
+    );
+    flatten_into(
+        second.0,
+        second.1,
+        second.2,
+        second.3,
+        tolerance,
+        depth + 1,
+        points,
+    );
+}
+
+const CONTAINS_TOLERANCE: f32 = 0.05;
+
+/// Non-zero winding test for a path made of cubic segments. The path is
+/// treated as closed: the end of the last segment joins the start of the
+/// first. Points lying on the boundary (within a small tolerance of the
+/// flattened outline) count as inside.
+pub fn bezier_contains_point(segments: &[(Vec2, Vec2, Vec2, Vec2)], point: Vec2) -> bool {
+    let mut outline: Vec<Vec2> = Vec::new();
+    for &(a, b, c, d) in segments {
+        let flat = flatten_bezier(a, b, c, d, CONTAINS_TOLERANCE);
+        let skip = usize::from(outline.last() == flat.first());
+        outline.extend(flat.into_iter().skip(skip));
+    }
+    if outline.len() < 3 {
+        return false;
+    }
+
+    if outline.iter().enumerate().any(|(i, &start)| {
+        let end = outline[(i + 1) % outline.len()];
+        distance_to_segment(point, start, end) <= CONTAINS_TOLERANCE
+    }) {
+        return true;
+    }
+    winding_number(&outline, point) != 0
+}
+
+/// Winding number of the closed polygon `outline` around `point`: how many
+/// times it wraps counter-clockwise around it, less the clockwise wraps.
+pub fn winding_number(outline: &[Vec2], point: Vec2) -> i32 {
+    let mut winding = 0;
+    for (i, &start) in outline.iter().enumerate() {
+        let end = outline[(i + 1) % outline.len()];
+        // Half-open crossing rule: an edge counts when the ray's y lies in
+        // [min, max), so a ray through a shared vertex is counted once.
+        let side = (end - start).perp_dot(point - start);
+        if start.y <= point.y {
+            if end.y > point.y && side > 0.0 {
+                winding += 1;
+            }
+        } else if end.y <= point.y && side < 0.0 {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+fn distance_to_segment(p: Vec2, start: Vec2, end: Vec2) -> f32 {
+    let edge = end - start;
+    let length_squared = edge.length_squared();
+    if length_squared <= f32::EPSILON {
+        return p.distance(start);
+    }
+    let t = ((p - start).dot(edge) / length_squared).clamp(0.0, 1.0);
+    p.distance(start + edge * t)
+}
+
+/// First derivative (velocity) of a cubic bezier at `t`.
+pub fn bezier_derivative(a: Vec2, b: Vec2, c: Vec2, d: Vec2, t: f32) -> Vec2 {
+    let mt = 1.0 - t;
+    3.0 * mt * mt * (b - a) + 6.0 * mt * t * (c - b) + 3.0 * t * t * (d - c)
+}
+
+/// Second derivative (acceleration) of a cubic bezier at `t`.
+pub fn bezier_second_derivative(a: Vec2, b: Vec2, c: Vec2, d: Vec2, t: f32) -> Vec2 {
+    6.0 * (1.0 - t) * (c - 2.0 * b + a) + 6.0 * t * (d - 2.0 * c + b)
+}
+
+/// Signed curvature at `t`, positive when the curve turns counter-clockwise.
+/// Where the velocity vanishes (e.g. an anchor coinciding with its control
+/// point) curvature is undefined and `0.0` is returned.
+pub fn bezier_curvature(a: Vec2, b: Vec2, c: Vec2, d: Vec2, t: f32) -> f32 {
+    let velocity = bezier_derivative(a, b, c, d, t);
+    let speed_squared = velocity.length_squared();
+    if speed_squared <= f32::EPSILON {
+        return 0.0;
+    }
+    let acceleration = bezier_second_derivative(a, b, c, d, t);
+    velocity.perp_dot(acceleration) / speed_squared.powf(1.5)
+}
+
+/// Reflects a cubic bezier across the line through `axis_point` along
+/// `axis_dir`. A zero `axis_dir` has no axis to reflect across and leaves the
+/// points unchanged.
+pub fn mirror_bezier(
+    a: Vec2,
+    b: Vec2,
+    c: Vec2,
+    d: Vec2,
+    axis_point: Vec2,
+    axis_dir: Vec2,
+) -> (Vec2, Vec2, Vec2, Vec2) {
+    let Some(dir) = axis_dir.try_normalize() else {
+        return (a, b, c, d);
+    };
+    let reflect = |p: Vec2| {
+        let offset = p - axis_point;
+        axis_point + 2.0 * offset.dot(dir) * dir - offset
+    };
+    (reflect(a), reflect(b), reflect(c), reflect(d))
+}
+
+/// Applies `transform` to all four control points. Beziers are affine
+/// invariant, so this transforms the curve itself exactly.
+pub fn transform_bezier(
+    points: (Vec2, Vec2, Vec2, Vec2),
+    transform: &Transform,
+) -> (Vec2, Vec2, Vec2, Vec2) {
+    let apply = |p: Vec2| transform.transform_point(p.extend(0.0)).truncate();
+    (
+        apply(points.0),
+        apply(points.1),
+        apply(points.2),
+        apply(points.3),
+    )
+}
+
+/// Point on a cubic bezier at parameter `t` in `[0, 1]`.
+pub fn evaluate_bezier(a: Vec2, b: Vec2, c: Vec2, d: Vec2, t: f32) -> Vec2 {
+    let mt = 1.0 - t;
+    mt * mt * mt * a + 3.0 * mt * mt * t * b + 3.0 * mt * t * t * c + t * t * t * d
+}
+
+/// Parameters `evaluate_bezier_many` evaluates at once.
+const EVALUATE_LANES: usize = 8;
+
+/// Points on `curve` at each of `ts`, as `evaluate_bezier` would give them
+/// one by one but cheaper in bulk, e.g. for many agents following one path.
+/// The curve is turned into polynomial coefficients once and the parameters
+/// are run in fixed-size chunks with x and y in separate arrays, a loop the
+/// compiler vectorizes.
+pub fn evaluate_bezier_many(curve: &CubicBezier, ts: &[f32]) -> Vec<Vec2> {
+    // B(t) = a + 3 (b - a) t + 3 (a - 2b + c) t² + (d - a + 3 (b - c)) t³
+    let CubicBezier { a, b, c, d } = *curve;
+    let (c1, c2, c3) = (
+        3.0 * (b - a),
+        3.0 * (a - 2.0 * b + c),
+        d - a + 3.0 * (b - c),
+    );
+    let mut points = Vec::with_capacity(ts.len());
+    let mut chunks = ts.chunks_exact(EVALUATE_LANES);
+    for chunk in &mut chunks {
+        let (mut xs, mut ys) = ([0.0; EVALUATE_LANES], [0.0; EVALUATE_LANES]);
+        for ((&t, x), y) in chunk.iter().zip(&mut xs).zip(&mut ys) {
+            *x = a.x + t * (c1.x + t * (c2.x + t * c3.x));
+            *y = a.y + t * (c1.y + t * (c2.y + t * c3.y));
+        }
+        points.extend(xs.into_iter().zip(ys).map(|(x, y)| Vec2::new(x, y)));
+    }
+    points.extend(
+        chunks
+            .remainder()
+            .iter()
+            .map(|&t| a + t * (c1 + t * (c2 + t * c3))),
+    );
+    points
+}
+
+/// Real roots of `a t² + b t + c = 0`, falling back to the linear equation
+/// when `a` is (nearly) zero. A fully degenerate equation has no roots.
+pub(crate) fn solve_quadratic(a: f32, b: f32, c: f32) -> Vec<f32> {
+    const EPSILON: f32 = 1e-6;
+    if a.abs() <= EPSILON {
+        if b.abs() <= EPSILON {
+            return Vec::new();
+        }
+        return vec![-c / b];
+    }
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return Vec::new();
+    }
+    let root = discriminant.sqrt();
+    if root == 0.0 {
+        return vec![-b / (2.0 * a)];
+    }
+    vec![(-b - root) / (2.0 * a), (-b + root) / (2.0 * a)]
+}
+
+/// Parameters in `[0, 1]` where the curvature changes sign, in increasing
+/// order. Curves without an inflection (including straight lines and other
+/// degenerate cubics) return an empty vec.
+pub fn bezier_inflections(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> Vec<f32> {
+    // With B(t) = a + 3 p t + 3 q t² + r t³ the cross product of the first
+    // and second derivatives is proportional to (q×r) t² + (p×r) t + p×q.
+    let p = b - a;
+    let q = c - 2.0 * b + a;
+    let r = d - 3.0 * c + 3.0 * b - a;
+    let mut roots: Vec<f32> = solve_quadratic(q.perp_dot(r), p.perp_dot(r), p.perp_dot(q))
+        .into_iter()
+        .filter(|t| (0.0..=1.0).contains(t))
+        .collect();
+    roots.sort_by(f32::total_cmp);
+    roots.dedup();
+    roots
+}
+
+const CLOSEST_POINT_SAMPLES: usize = 16;
+const CLOSEST_POINT_ITERATIONS: usize = 8;
+
+/// Parameter and position of the point on the curve closest to `p`: a coarse
+/// scan finds every sample closer than its neighbours, Newton steps on
+/// `(B(t) - p)·B'(t) = 0` refine each of them, and the closest result wins.
+/// Refining all local minima rather than just the best sample keeps a
+/// curve that doubles back near `p` from snapping to the wrong branch.
+pub fn closest_point_on_bezier(a: Vec2, b: Vec2, c: Vec2, d: Vec2, p: Vec2) -> (f32, Vec2) {
+    let distances: Vec<f32> = (0..=CLOSEST_POINT_SAMPLES)
+        .map(|i| {
+            let t = i as f32 / CLOSEST_POINT_SAMPLES as f32;
+            evaluate_bezier(a, b, c, d, t).distance_squared(p)
+        })
+        .collect();
+    let mut best = (0.0, a, f32::INFINITY);
+    for (i, &distance) in distances.iter().enumerate() {
+        let before = i.checked_sub(1).map_or(f32::INFINITY, |j| distances[j]);
+        let after = distances.get(i + 1).copied().unwrap_or(f32::INFINITY);
+        if distance > before || distance > after {
+            continue;
+        }
+        let sample_t = i as f32 / CLOSEST_POINT_SAMPLES as f32;
+        let t = refine_closest_t(a, b, c, d, p, sample_t);
+        let point = evaluate_bezier(a, b, c, d, t);
+        // Newton can wander off on cusps; never take worse than the sample
+        let (t, point, distance) = match point.distance_squared(p) {
+            refined if refined <= distance => (t, point, refined),
+            _ => (sample_t, evaluate_bezier(a, b, c, d, sample_t), distance),
+        };
+        if distance < best.2 {
+            best = (t, point, distance);
+        }
+    }
+    (best.0, best.1)
+}
+
+/// Newton steps from `t` towards a parameter where `B(t) - p` is
+/// perpendicular to the curve.
+fn refine_closest_t(a: Vec2, b: Vec2, c: Vec2, d: Vec2, p: Vec2, mut t: f32) -> f32 {
+    for _ in 0..CLOSEST_POINT_ITERATIONS {
+        let offset = evaluate_bezier(a, b, c, d, t) - p;
+        let first = bezier_derivative(a, b, c, d, t);
+        let second = bezier_second_derivative(a, b, c, d, t);
+        let numerator = offset.dot(first);
+        let denominator = first.dot(first) + offset.dot(second);
+        if denominator.abs() <= f32::EPSILON {
+            break;
+        }
+        let next = (t - numerator / denominator).clamp(0.0, 1.0);
+        if (next - t).abs() <= 1e-6 {
+            return next;
+        }
+        t = next;
+    }
+    t
+}
+
+/// Shortest distance from `p` to the curve.
+pub fn distance_to_bezier(a: Vec2, b: Vec2, c: Vec2, d: Vec2, p: Vec2) -> f32 {
+    closest_point_on_bezier(a, b, c, d, p).1.distance(p)
+}
+
+/// What a cubic bezier really is, as far as cleanup tools are concerned.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BezierClass {
+    Line,
+    Arc { center: Vec2, radius: f32 },
+    General,
+}
+
+const CLASSIFY_SAMPLES: usize = 16;
+
+/// Recognises curves that are straight lines or circular arcs to within
+/// `tolerance`. Conservative: anything with an inflection, or whose control
+/// points overshoot the chord, stays `General`.
+pub fn classify_bezier(a: Vec2, b: Vec2, c: Vec2, d: Vec2, tolerance: f32) -> BezierClass {
+    let chord = d - a;
+    let length_squared = chord.length_squared();
+    if length_squared <= f32::EPSILON {
+        return BezierClass::General;
+    }
+    let on_chord = |p: Vec2| {
+        let t = (p - a).dot(chord) / length_squared;
+        (0.0..=1.0).contains(&t) && chord.perp_dot(p - a).abs() / length_squared.sqrt() <= tolerance
+    };
+    if on_chord(b) && on_chord(c) {
+        return BezierClass::Line;
+    }
+
+    if !bezier_inflections(a, b, c, d).is_empty() {
+        return BezierClass::General;
+    }
+    let Some(center) = circumcenter(a, evaluate_bezier(a, b, c, d, 0.5), d) else {
+        return BezierClass::General;
+    };
+    let radius = center.distance(a);
+    let fits = (0..=CLASSIFY_SAMPLES).all(|i| {
+        let t = i as f32 / CLASSIFY_SAMPLES as f32;
+        (evaluate_bezier(a, b, c, d, t).distance(center) - radius).abs() <= tolerance
+    });
+    if fits {
+        BezierClass::Arc { center, radius }
+    } else {
+        BezierClass::General
+    }
+}
+
+fn circumcenter(p: Vec2, q: Vec2, r: Vec2) -> Option<Vec2> {
+    let denominator = 2.0 * (q - p).perp_dot(r - p);
+    if denominator.abs() <= f32::EPSILON {
+        return None;
+    }
+    let qp = q - p;
+    let rp = r - p;
+    let offset = Vec2::new(
+        rp.y * qp.length_squared() - qp.y * rp.length_squared(),
+        qp.x * rp.length_squared() - rp.x * qp.length_squared(),
+    ) / denominator;
+    Some(p + offset)
+}
+
+const ARC_LENGTH_SAMPLES: usize = 64;
+
+/// Cumulative chord length at `ARC_LENGTH_SAMPLES + 1` evenly spaced `t`s.
+fn arc_length_table(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> Vec<f32> {
+    let mut table = Vec::with_capacity(ARC_LENGTH_SAMPLES + 1);
+    let mut previous = a;
+    let mut length = 0.0;
+    table.push(0.0);
+    for i in 1..=ARC_LENGTH_SAMPLES {
+        let point = evaluate_bezier(a, b, c, d, i as f32 / ARC_LENGTH_SAMPLES as f32);
+        length += previous.distance(point);
+        table.push(length);
+        previous = point;
+    }
+    table
+}
+
+fn t_from_table(table: &[f32], distance: f32) -> f32 {
+    let total = table[table.len() - 1];
+    if total <= f32::EPSILON {
+        return 0.0;
+    }
+    let distance = distance.clamp(0.0, total);
+    let upper = table.partition_point(|&length| length < distance).max(1);
+    let (start, end) = (table[upper - 1], table[upper]);
+    let fraction = if end > start {
+        (distance - start) / (end - start)
+    } else {
+        0.0
+    };
+    (upper - 1) as f32 / ARC_LENGTH_SAMPLES as f32 + fraction / ARC_LENGTH_SAMPLES as f32
+}
+
+const ARC_LENGTH_TOLERANCE: f32 = 1e-3;
+
+/// Approximate length of the curve.
+pub fn bezier_length(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> f32 {
+    bezier_arc_length(a, b, c, d, ARC_LENGTH_TOLERANCE)
+}
+
+/// Length of the curve by adaptive subdivision (Gravesen): a piece is done
+/// once its chord and control polygon lengths agree to within `tolerance`,
+/// and its length is then estimated from both.
+pub fn bezier_arc_length(a: Vec2, b: Vec2, c: Vec2, d: Vec2, tolerance: f32) -> f32 {
+    arc_length_into(a, b, c, d, tolerance.max(f32::EPSILON), 0)
+}
+
+fn arc_length_into(a: Vec2, b: Vec2, c: Vec2, d: Vec2, tolerance: f32, depth: u32) -> f32 {
+    let chord = a.distance(d);
+    let polygon = a.distance(b) + b.distance(c) + c.distance(d);
+    if depth >= MAX_FLATTEN_DEPTH || polygon - chord <= tolerance {
+        return (2.0 * chord + polygon) / 3.0;
+    }
+    let (first, second) = split_bezier(a, b, c, d, 0.5);
+    arc_length_into(first.0, first.1, first.2, first.3, tolerance, depth + 1)
+        + arc_length_into(second.0, second.1, second.2, second.3, tolerance, depth + 1)
+}
+
+/// Parameter `t` reached after travelling `distance` along the curve from
+/// `a`. Distances outside the curve are clamped to its ends.
+pub fn t_at_distance(a: Vec2, b: Vec2, c: Vec2, d: Vec2, distance: f32) -> f32 {
+    t_from_table(&arc_length_table(a, b, c, d), distance)
+}
+
+/// `n + 1` points spaced at equal arc length along the curve, starting at
+/// `a` and ending at `d`. Unlike `flatten_bezier` the spacing ignores
+/// curvature. `n` of zero is treated as one.
+pub fn subdivide_equal_length(a: Vec2, b: Vec2, c: Vec2, d: Vec2, n: usize) -> Vec<Vec2> {
+    let n = n.max(1);
+    let table = arc_length_table(a, b, c, d);
+    let total = table[ARC_LENGTH_SAMPLES];
+    let mut points = Vec::with_capacity(n + 1);
+    points.push(a);
+    for i in 1..n {
+        let point = if total <= f32::EPSILON {
+            a.lerp(d, i as f32 / n as f32)
+        } else {
+            let t = t_from_table(&table, total * i as f32 / n as f32);
+            evaluate_bezier(a, b, c, d, t)
+        };
+        points.push(point);
+    }
+    points.push(d);
+    points
+}
+
+/// Cubic approximation of a circular arc, one segment per quarter turn or
+/// less, going counter-clockwise when `end_angle > start_angle`.
+pub fn arc_to_beziers(
+    center: Vec2,
+    radius: f32,
+    start_angle: f32,
+    end_angle: f32,
+) -> Vec<(Vec2, Vec2, Vec2, Vec2)> {
+    let sweep = end_angle - start_angle;
+    let count = (sweep.abs() / std::f32::consts::FRAC_PI_2).ceil().max(1.0) as usize;
+    let step = sweep / count as f32;
+    let handle = 4.0 / 3.0 * (step / 4.0).tan() * radius;
+    (0..count)
+        .map(|i| {
+            let from = start_angle + step * i as f32;
+            let to = from + step;
+            let (from_dir, to_dir) = (Vec2::from_angle(from), Vec2::from_angle(to));
+            let start = center + from_dir * radius;
+            let end = center + to_dir * radius;
+            (
+                start,
+                start + from_dir.perp() * handle,
+                end - to_dir.perp() * handle,
+                end,
+            )
+        })
+        .collect()
+}
+
+/// `arc_to_beziers` as `CubicBezier`s: a circular arc from `start_angle`
+/// to `end_angle`, one segment per quarter turn or less.
+pub fn arc_to_cubics(
+    center: Vec2,
+    radius: f32,
+    start_angle: f32,
+    end_angle: f32,
+) -> Vec<CubicBezier> {
+    arc_to_beziers(center, radius, start_angle, end_angle)
+        .into_iter()
+        .map(|(a, b, c, d)| CubicBezier::new(a, b, c, d))
+        .collect()
+}
+
+/// The standard four-segment approximation of a circle, counter-clockwise
+/// from its rightmost point, closed exactly so it can be spawned as a
+/// closed spline.
+pub fn circle_to_cubics(center: Vec2, radius: f32) -> Vec<CubicBezier> {
+    let mut segments = arc_to_cubics(center, radius, 0.0, std::f32::consts::TAU);
+    if let Some(first) = segments.first().map(|segment| segment.a) {
+        if let Some(last) = segments.last_mut() {
+            last.d = first;
+        }
+    }
+    segments
+}
+
+/// An arc of the ellipse around `center` with semi-axes `radii`, turned
+/// `rotation` radians, from `start_angle` to `end_angle` measured on the
+/// unrotated, unscaled circle. Made by mapping a circular arc, which stays
+/// exact as cubics are affine invariant.
+pub fn elliptical_arc_to_cubics(
+    center: Vec2,
+    radii: Vec2,
+    rotation: f32,
+    start_angle: f32,
+    end_angle: f32,
+) -> Vec<CubicBezier> {
+    let axes = Vec2::from_angle(rotation);
+    let map = |point: Vec2| center + axes.rotate(point * radii);
+    arc_to_beziers(Vec2::ZERO, 1.0, start_angle, end_angle)
+        .into_iter()
+        .map(|(a, b, c, d)| CubicBezier::new(map(a), map(b), map(c), map(d)))
+        .collect()
+}
+
+/// An SVG `A` command from `from` to `to` as cubics, following the SVG
+/// spec's endpoint to center conversion: `radii` too small to span the
+/// points are scaled up, zero radii give a straight line, and `large_arc`
+/// and `sweep` pick one of the four candidate arcs. `rotation` is in
+/// radians.
+pub fn svg_arc_to_cubics(
+    from: Vec2,
+    radii: Vec2,
+    rotation: f32,
+    large_arc: bool,
+    sweep: bool,
+    to: Vec2,
+) -> Vec<CubicBezier> {
+    if from == to {
+        return Vec::new();
+    }
+    let mut radii = radii.abs();
+    if radii.x <= f32::EPSILON || radii.y <= f32::EPSILON {
+        return vec![CubicBezier::new(
+            from,
+            from.lerp(to, 1.0 / 3.0),
+            from.lerp(to, 2.0 / 3.0),
+            to,
+        )];
+    }
+    let axes = Vec2::from_angle(rotation);
+    // the chord's half in the ellipse's own frame
+    let half = Vec2::from_angle(-rotation).rotate((from - to) * 0.5);
+    let reach = (half / radii).length_squared();
+    if reach > 1.0 {
+        radii *= reach.sqrt();
+    }
+    let (rx2, ry2) = (radii.x * radii.x, radii.y * radii.y);
+    let (hx2, hy2) = (half.x * half.x, half.y * half.y);
+    let spread = ((rx2 * ry2 - rx2 * hy2 - ry2 * hx2) / (rx2 * hy2 + ry2 * hx2)).max(0.0);
+    let sign = if large_arc == sweep { -1.0 } else { 1.0 };
+    let center_local =
+        sign * spread.sqrt() * Vec2::new(radii.x * half.y / radii.y, -radii.y * half.x / radii.x);
+    let center = axes.rotate(center_local) + (from + to) * 0.5;
+    let start = (half - center_local) / radii;
+    let end = (-half - center_local) / radii;
+    let start_angle = start.to_angle();
+    let mut delta = start.angle_to(end);
+    if !sweep && delta > 0.0 {
+        delta -= std::f32::consts::TAU;
+    } else if sweep && delta < 0.0 {
+        delta += std::f32::consts::TAU;
+    }
+    let mut segments =
+        elliptical_arc_to_cubics(center, radii, rotation, start_angle, start_angle + delta);
+    // snap onto the endpoints so the path stays connected
+    if let Some(first) = segments.first_mut() {
+        first.a = from;
+    }
+    if let Some(last) = segments.last_mut() {
+        last.d = to;
+    }
+    segments
+}
+
+/// Result of rounding the join between two connected segments.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fillet {
+    /// First segment, trimmed back to where the fillet starts.
+    pub seg_a: (Vec2, Vec2, Vec2, Vec2),
+    /// The rounding arc inserted between the two segments, one cubic per
+    /// quarter turn or less: corners sharper than 90° sweep further.
+    pub arc: Vec<(Vec2, Vec2, Vec2, Vec2)>,
+    /// Second segment, trimmed to start where the fillet ends.
+    pub seg_b: (Vec2, Vec2, Vec2, Vec2),
+}
+
+/// Rounds the corner where `seg_a` ends and `seg_b` starts with an arc of
+/// `radius` tangent to both. Returns `None` when the segments are nearly
+/// collinear (there is no corner) or the radius doesn't fit on them.
+pub fn fillet_corner(
+    seg_a: (Vec2, Vec2, Vec2, Vec2),
+    seg_b: (Vec2, Vec2, Vec2, Vec2),
+    radius: f32,
+) -> Option<Fillet> {
+    let (a0, a1, a2, a3) = seg_a;
+    let (b0, b1, b2, b3) = seg_b;
+    let corner = a3;
+    // directions pointing away from the corner along each segment
+    let back = (-bezier_derivative(a0, a1, a2, a3, 1.0)).try_normalize()?;
+    let forward = bezier_derivative(b0, b1, b2, b3, 0.0).try_normalize()?;
+    let angle = back.angle_to(forward).abs();
+    if radius <= 0.0 || angle <= 1e-3 || std::f32::consts::PI - angle <= 1e-3 {
+        return None;
+    }
+
+    let setback = radius / (angle / 2.0).tan();
+    let length_a = bezier_length(a0, a1, a2, a3);
+    let length_b = bezier_length(b0, b1, b2, b3);
+    if setback >= length_a || setback >= length_b {
+        return None;
+    }
+    let t_a = t_at_distance(a0, a1, a2, a3, length_a - setback);
+    let t_b = t_at_distance(b0, b1, b2, b3, setback);
+    let (trimmed_a, _) = split_bezier(a0, a1, a2, a3, t_a);
+    let (_, trimmed_b) = split_bezier(b0, b1, b2, b3, t_b);
+
+    let bisector = (back + forward).try_normalize()?;
+    let center = corner + bisector * radius / (angle / 2.0).sin();
+    let start = trimmed_a.3;
+    let end = trimmed_b.0;
+    let start_angle = (start - center).to_angle();
+    let sweep = (start - center).angle_to(end - center);
+    let mut arc = arc_to_beziers(center, radius, start_angle, start_angle + sweep);
+    // snap the arc onto the trimmed ends so the path stays connected
+    arc.first_mut()?.0 = start;
+    arc.last_mut()?.3 = end;
+    Some(Fillet {
+        seg_a: trimmed_a,
+        arc,
+        seg_b: trimmed_b,
+    })
+}
+
+const OFFSET_TOLERANCE: f32 = 0.05;
+const MAX_OFFSET_DEPTH: u32 = 8;
+
+/// Approximates the curve `distance` to the left of a cubic (right for a
+/// negative distance) with cubics, splitting until each piece is within
+/// `OFFSET_TOLERANCE` of the true parallel curve. Consecutive pieces join.
+pub fn offset_bezier(
+    a: Vec2,
+    b: Vec2,
+    c: Vec2,
+    d: Vec2,
+    distance: f32,
+) -> Vec<(Vec2, Vec2, Vec2, Vec2)> {
+    let mut pieces = Vec::new();
+    offset_into(a, b, c, d, distance, 0, &mut pieces);
+    pieces
+}
+
+fn offset_into(
+    a: Vec2,
+    b: Vec2,
+    c: Vec2,
+    d: Vec2,
+    distance: f32,
+    depth: u32,
+    pieces: &mut Vec<(Vec2, Vec2, Vec2, Vec2)>,
+) {
+    let offset_point =
+        |t: f32| evaluate_bezier(a, b, c, d, t) + offset_normal(a, b, c, d, t) * distance;
+    // the parallel curve moves at (1 - curvature * distance) times the speed
+    // of the original, so the handles scale by the same factor
+    let start = offset_point(0.0);
+    let end = offset_point(1.0);
+    let start_scale = 1.0 - bezier_curvature(a, b, c, d, 0.0) * distance;
+    let end_scale = 1.0 - bezier_curvature(a, b, c, d, 1.0) * distance;
+    let piece = (
+        start,
+        start + (b - a) * start_scale,
+        end + (c - d) * end_scale,
+        end,
+    );
+    let close_enough = [0.25, 0.5, 0.75].iter().all(|&t| {
+        evaluate_bezier(piece.0, piece.1, piece.2, piece.3, t).distance(offset_point(t))
+            <= OFFSET_TOLERANCE
+    });
+    if close_enough || depth >= MAX_OFFSET_DEPTH {
+        pieces.push(piece);
+        return;
+    }
+    let (first, second) = split_bezier(a, b, c, d, 0.5);
+    offset_into(
+        first.0,
+        first.1,
+        first.2,
+        first.3,
+        distance,
+        depth + 1,
+        pieces,
+    );
+    offset_into(
+        second.0,
+        second.1,
+        second.2,
+        second.3,
+        distance,
+        depth + 1,
+        pieces,
+    );
+}
+
+/// Unit left normal at `t`, falling back to the chord where the curve
+/// stalls (e.g. a control point on its anchor).
+fn offset_normal(a: Vec2, b: Vec2, c: Vec2, d: Vec2, t: f32) -> Vec2 {
+    let tangent = bezier_derivative(a, b, c, d, t).normalize_or_zero();
+    let tangent = if tangent == Vec2::ZERO {
+        (d - a).normalize_or_zero()
+    } else {
+        tangent
+    };
+    tangent.perp()
+}
+
+const INTERSECTION_TOLERANCE: f32 = 1e-3;
+const MAX_INTERSECTION_DEPTH: u32 = 32;
+
+/// Points where two cubics cross, as `(t on curve_a, t on curve_b, point)`
+/// sorted by `t` on `curve_a`. Found by subdividing both curves while their
+/// control hulls overlap. Curves that overlap along a stretch report a run of
+/// points on it rather than the stretch itself.
+pub fn intersections(curve_a: &CubicBezier, curve_b: &CubicBezier) -> Vec<(f32, f32, Vec2)> {
+    let mut found = Vec::new();
+    intersections_into(curve_a, (0.0, 1.0), curve_b, (0.0, 1.0), 0, &mut found);
+    found.sort_by(|x, y| x.0.total_cmp(&y.0));
+    // neighbouring leaf boxes around one crossing report it more than once
+    found.dedup_by(|x, y| x.2.distance(y.2) <= INTERSECTION_TOLERANCE * 10.0);
+    found
+}
+
+/// Gap in `t` below which `self_intersections` takes a crossing between
+/// two pieces of a curve for the point they share.
+const SELF_INTERSECTION_MIN_GAP: f32 = 1e-2;
+
+/// Where a cubic crosses itself, as `(t, later t, point)`. A cubic has at
+/// most one such loop. The curve is cut where its direction turns through
+/// horizontal or vertical, leaving pieces that can't cross themselves,
+/// and the pieces are intersected pairwise.
+pub fn self_intersections(curve: &CubicBezier) -> Vec<(f32, f32, Vec2)> {
+    let CubicBezier { a, b, c, d } = *curve;
+    // B'(t) / 3 = p + 2 q t + r t², per axis
+    let p = b - a;
+    let q = c - 2.0 * b + a;
+    let r = d - 3.0 * c + 3.0 * b - a;
+    let mut cuts: Vec<f32> = solve_quadratic(r.x, 2.0 * q.x, p.x)
+        .into_iter()
+        .chain(solve_quadratic(r.y, 2.0 * q.y, p.y))
+        .filter(|t| *t > 0.0 && *t < 1.0)
+        .collect();
+    cuts.sort_by(f32::total_cmp);
+    cuts.dedup();
+    let bounds: Vec<f32> = std::iter::once(0.0)
+        .chain(cuts)
+        .chain(std::iter::once(1.0))
+        .collect();
+    let pieces: Vec<(f32, f32)> = bounds.windows(2).map(|pair| (pair[0], pair[1])).collect();
+    let mut found = Vec::new();
+    for (i, &(start_i, end_i)) in pieces.iter().enumerate() {
+        for &(start_j, end_j) in &pieces[i + 1..] {
+            let (piece_i, piece_j) = (curve.trim(start_i, end_i), curve.trim(start_j, end_j));
+            for (t_i, t_j, point) in intersections(&piece_i, &piece_j) {
+                let t_i = start_i + (end_i - start_i) * t_i;
+                let t_j = start_j + (end_j - start_j) * t_j;
+                if t_j - t_i > SELF_INTERSECTION_MIN_GAP {
+                    found.push((t_i, t_j, point));
+                }
+            }
+        }
+    }
+    found
+}
+
+/// Where a chain of cubics crosses itself, each as `(s, later s, point)`
+/// with `s` the segment index plus `t` on it. Covers loops within a segment
+/// and crossings between segments; the anchors neighbours share, including
+/// the one closing a `closed` chain, don't count.
+pub fn spline_self_intersections(segments: &[CubicBezier], closed: bool) -> Vec<(f32, f32, Vec2)> {
+    let mut found = Vec::new();
+    let last = segments.len().saturating_sub(1);
+    for (i, segment) in segments.iter().enumerate() {
+        found.extend(
+            self_intersections(segment)
+                .into_iter()
+                .map(|(t, u, point)| (i as f32 + t, i as f32 + u, point)),
+        );
+        for (j, other) in segments.iter().enumerate().skip(i + 1) {
+            let shares_end = j == i + 1;
+            let shares_start = closed && i == 0 && j == last;
+            for (t, u, point) in intersections(segment, other) {
+                let at_joint = (shares_end && 1.0 - t + u < SELF_INTERSECTION_MIN_GAP)
+                    || (shares_start && t + 1.0 - u < SELF_INTERSECTION_MIN_GAP);
+                if !at_joint {
+                    found.push((i as f32 + t, j as f32 + u, point));
+                }
+            }
+        }
+    }
+    found.sort_by(|x, y| x.0.total_cmp(&y.0));
+    found
+}
+
+fn intersections_into(
+    curve_a: &CubicBezier,
+    range_a: (f32, f32),
+    curve_b: &CubicBezier,
+    range_b: (f32, f32),
+    depth: u32,
+    found: &mut Vec<(f32, f32, Vec2)>,
+) {
+    let bounds_a = bezier_bounds(curve_a.a, curve_a.b, curve_a.c, curve_a.d);
+    let bounds_b = bezier_bounds(curve_b.a, curve_b.b, curve_b.c, curve_b.d);
+    // not `Rect::intersect`, which calls the touching boxes of straight
+    // segments empty
+    if !(bounds_a.min.cmple(bounds_b.max).all() && bounds_b.min.cmple(bounds_a.max).all()) {
+        return;
+    }
+    let small = |bounds: Rect| bounds.width().max(bounds.height()) <= INTERSECTION_TOLERANCE;
+    if depth >= MAX_INTERSECTION_DEPTH || (small(bounds_a) && small(bounds_b)) {
+        let t_a = (range_a.0 + range_a.1) * 0.5;
+        let t_b = (range_b.0 + range_b.1) * 0.5;
+        found.push((t_a, t_b, curve_a.point_at(0.5)));
+        return;
+    }
+    let (a_first, a_second) = curve_a.split(0.5);
+    let (b_first, b_second) = curve_b.split(0.5);
+    let mid_a = (range_a.0 + range_a.1) * 0.5;
+    let mid_b = (range_b.0 + range_b.1) * 0.5;
+    for (half_a, sub_a) in [
+        (a_first, (range_a.0, mid_a)),
+        (a_second, (mid_a, range_a.1)),
+    ] {
+        for (half_b, sub_b) in [
+            (b_first, (range_b.0, mid_b)),
+            (b_second, (mid_b, range_b.1)),
+        ] {
+            intersections_into(&half_a, sub_a, &half_b, sub_b, depth + 1, found);
+        }
+    }
+}
+
+/// Points where a cubic crosses the line segment `p0`–`p1`, as
+/// `(t on curve, point)` sorted by `t`.
+pub fn line_intersections(curve: &CubicBezier, p0: Vec2, p1: Vec2) -> Vec<(f32, Vec2)> {
+    let line = CubicBezier::new(p0, p0.lerp(p1, 1.0 / 3.0), p0.lerp(p1, 2.0 / 3.0), p1);
+    intersections(curve, &line)
+        .into_iter()
+        .map(|(t, _, point)| (t, point))
+        .collect()
+}
+
+/// First place a ray from `origin` heading along `dir` hits the curve, as
+/// `(t on curve, distance along the ray, point)`. `None` when it misses or
+/// `dir` is zero. A ray starting on the curve hits it at distance zero.
+pub fn raycast(curve: &CubicBezier, origin: Vec2, dir: Vec2) -> Option<(f32, f32, Vec2)> {
+    let dir = dir.try_normalize()?;
+    // the curve lies inside its control points' box, so a segment reaching
+    // past the box's far corner covers every hit
+    let bounds = bezier_bounds(curve.a, curve.b, curve.c, curve.d);
+    let reach = [
+        bounds.min,
+        bounds.max,
+        Vec2::new(bounds.min.x, bounds.max.y),
+        Vec2::new(bounds.max.x, bounds.min.y),
+    ]
+    .iter()
+    .map(|corner| corner.distance(origin))
+    .fold(0.0, f32::max)
+        + 1.0;
+    line_intersections(curve, origin, origin + dir * reach)
+        .into_iter()
+        .map(|(t, point)| (t, (point - origin).dot(dir).max(0.0), point))
+        .min_by(|p, q| p.1.total_cmp(&q.1))
+}
+
+/// `curve` with its controls moved so its point at `t` lands on `target`
+/// while the anchors stay put, as when pulling on the curve body. Each
+/// control moves in proportion to its Bernstein weight at `t`, the smallest
+/// change that does it. Near the anchors the controls barely move the
+/// point, so there the curve comes back unchanged.
+pub fn bend_bezier(curve: &CubicBezier, t: f32, target: Vec2) -> CubicBezier {
+    let delta = target - curve.point_at(t);
+    let s = 1.0 - t;
+    let (weight_b, weight_c) = (3.0 * s * s * t, 3.0 * s * t * t);
+    let norm = weight_b * weight_b + weight_c * weight_c;
+    if norm <= 1e-2 {
+        return *curve;
+    }
+    CubicBezier::new(
+        curve.a,
+        curve.b + delta * weight_b / norm,
+        curve.c + delta * weight_c / norm,
+        curve.d,
+    )
+}
+
+/// `p` moved to the nearest intersection of a grid with cells of `size`
+/// anchored at the origin. A non-positive `size` leaves `p` alone.
+pub fn snap_to_grid(p: Vec2, size: f32) -> Vec2 {
+    if size <= 0.0 {
+        return p;
+    }
+    (p / size).round() * size
+}
+
+/// `p` rotated about `origin` onto the nearest multiple of `increment`
+/// radians, keeping its distance from `origin`.
+pub fn snap_to_angle(origin: Vec2, p: Vec2, increment: f32) -> Vec2 {
+    let offset = p - origin;
+    if increment <= 0.0 || offset == Vec2::ZERO {
+        return p;
+    }
+    let angle = (offset.to_angle() / increment).round() * increment;
+    origin + Vec2::from_angle(angle) * offset.length()
+}
+
+/// A timing function like CSS `cubic-bezier(x1, y1, x2, y2)`: a cubic from
+/// `(0, 0)` to `(1, 1)` with controls `p1` and `p2`, read as y over x.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EasingCurve {
+    pub p1: Vec2,
+    pub p2: Vec2,
+}
+
+impl Default for EasingCurve {
+    /// CSS `ease`.
+    fn default() -> Self {
+        Self::new(0.25, 0.1, 0.25, 1.0)
+    }
+}
+
+impl EasingCurve {
+    /// Control x values are clamped to `[0, 1]` so x never runs backwards.
+    pub fn new(x1: f32, y1: f32, x2: f32, y2: f32) -> Self {
+        Self {
+            p1: Vec2::new(x1.clamp(0.0, 1.0), y1),
+            p2: Vec2::new(x2.clamp(0.0, 1.0), y2),
+        }
+    }
+    pub fn to_cubic(&self) -> CubicBezier {
+        CubicBezier::new(Vec2::ZERO, self.p1, self.p2, Vec2::ONE)
+    }
+    /// Eased progress at `t` in `[0, 1]`.
+    pub fn ease(&self, t: f32) -> f32 {
+        ease_bezier(self.p1, self.p2, t)
+    }
+}
+
+const EASE_NEWTON_ITERATIONS: usize = 8;
+const EASE_BISECTION_ITERATIONS: usize = 32;
+const EASE_EPSILON: f32 = 1e-6;
+
+/// y of the timing function with controls `p1`, `p2` where its x equals
+/// `t`: Newton steps on x(s) = t, with bisection when they stall.
+pub fn ease_bezier(p1: Vec2, p2: Vec2, t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    let (a, d) = (Vec2::ZERO, Vec2::ONE);
+    let x_at = |s: f32| evaluate_bezier(a, p1, p2, d, s).x;
+
+    let mut s = t;
+    for _ in 0..EASE_NEWTON_ITERATIONS {
+        let error = x_at(s) - t;
+        if error.abs() <= EASE_EPSILON {
+            return evaluate_bezier(a, p1, p2, d, s).y;
+        }
+        let slope = bezier_derivative(a, p1, p2, d, s).x;
+        if slope.abs() <= EASE_EPSILON {
+            break;
+        }
+        s = (s - error / slope).clamp(0.0, 1.0);
+    }
+
+    let (mut low, mut high) = (0.0, 1.0);
+    s = t;
+    for _ in 0..EASE_BISECTION_ITERATIONS {
+        let x = x_at(s);
+        if (x - t).abs() <= EASE_EPSILON {
+            break;
+        }
+        if x < t {
+            low = s;
+        } else {
+            high = s;
+        }
+        s = (low + high) * 0.5;
+    }
+    evaluate_bezier(a, p1, p2, d, s).y
+}
+
+const SOLVE_X_ITERATIONS: usize = 32;
+
+/// Whether x never runs backwards along `curve`, so read as y = f(x), e.g.
+/// an easing or a volume envelope, it gives one y for every x between its
+/// anchors. Checks the minimum of dx/dt, a quadratic, over `[0, 1]`.
+pub fn is_x_monotonic(curve: &CubicBezier) -> bool {
+    let (p0, p1, p2) = (
+        curve.b.x - curve.a.x,
+        curve.c.x - curve.b.x,
+        curve.d.x - curve.c.x,
+    );
+    // dx/dt / 3 = k2 t² + k1 t + p0
+    let (k2, k1) = (p0 - 2.0 * p1 + p2, 2.0 * (p1 - p0));
+    let slope = |t: f32| (k2 * t + k1) * t + p0;
+    let tolerance = -EASE_EPSILON * (curve.d.x - curve.a.x).abs().max(1.0);
+    let vertex = (k2.abs() > EASE_EPSILON)
+        .then(|| -k1 / (2.0 * k2))
+        .filter(|t| (0.0..=1.0).contains(t));
+    [Some(0.0), Some(1.0), vertex]
+        .into_iter()
+        .flatten()
+        .all(|t| slope(t) >= tolerance)
+}
+
+/// The parameter in `[0, 1]` where `curve`'s x equals `x`, by Newton steps
+/// kept inside a shrinking bracket. `None` when the curve isn't
+/// `is_x_monotonic` or `x` lies outside its anchors.
+pub fn solve_t_for_x(curve: &CubicBezier, x: f32) -> Option<f32> {
+    let span = curve.d.x - curve.a.x;
+    if !is_x_monotonic(curve) || x < curve.a.x || x > curve.d.x {
+        return None;
+    }
+    if span <= EASE_EPSILON {
+        return Some(0.0);
+    }
+    let tolerance = EASE_EPSILON * span.max(1.0);
+    let (mut low, mut high) = (0.0, 1.0);
+    let mut t = (x - curve.a.x) / span;
+    for _ in 0..SOLVE_X_ITERATIONS {
+        let error = curve.point_at(t).x - x;
+        if error.abs() <= tolerance {
+            break;
+        }
+        if error < 0.0 {
+            low = t;
+        } else {
+            high = t;
+        }
+        let slope = curve.derivative_at(t).x;
+        let step = t - error / slope;
+        t = if slope.abs() > EASE_EPSILON && step > low && step < high {
+            step
+        } else {
+            (low + high) * 0.5
+        };
+    }
+    Some(t)
+}
+
+/// `curve` with its anchors in x order and its controls' x clamped between
+/// them, which is enough to make it `is_x_monotonic`. The `End` anchor is
+/// moved if it lies left of `Start`.
+pub fn to_x_monotonic(curve: &CubicBezier) -> CubicBezier {
+    let (a, mut d) = (curve.a, curve.d);
+    d.x = d.x.max(a.x);
+    let clamp = |point: Vec2| Vec2::new(point.x.clamp(a.x, d.x), point.y);
+    CubicBezier::new(a, clamp(curve.b), clamp(curve.c), d)
+}
+
+/// Splits a polyline into the "on" runs of a dash `pattern` of alternating
+/// on and off lengths, which repeats along the whole line. An empty pattern,
+/// or one with no positive length, leaves the line solid.
+pub fn dash_polyline(points: &[Vec2], pattern: &[f32]) -> Vec<Vec<Vec2>> {
+    if points.len() < 2 || !pattern.iter().any(|&length| length > 0.0) {
+        return vec![points.to_vec()];
+    }
+    let mut dashes = Vec::new();
+    let mut current = vec![points[0]];
+    let mut index = 0;
+    let mut remaining = pattern[0].max(0.0);
+    let mut on = true;
+    for segment in points.windows(2) {
+        let (mut start, end) = (segment[0], segment[1]);
+        let mut length = start.distance(end);
+        while length > remaining {
+            let split = start.lerp(end, remaining / length);
+            if on {
+                current.push(split);
+                dashes.push(std::mem::take(&mut current));
+            } else {
+                current = vec![split];
+            }
+            length -= remaining;
+            start = split;
+            on = !on;
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index].max(0.0);
+        }
+        remaining -= length;
+        if on {
+            current.push(end);
+        }
+    }
+    if on && current.len() > 1 {
+        dashes.push(current);
+    }
+    dashes
+}
+
+/// A smooth chain of cubics passing through every one of `points`, one
+/// Catmull-Rom segment per pair of neighbours. The ends are treated as if
+/// the first and last points were repeated, so the chain starts and ends
+/// heading at its neighbour. Fewer than two points give no curves.
+pub fn catmull_rom_spline(points: &[Vec2]) -> Vec<CubicBezier> {
+    if points.len() < 2 {
+        return Vec::new();
+    }
+    let at = |i: isize| points[i.clamp(0, points.len() as isize - 1) as usize];
+    (0..points.len() as isize - 1)
+        .map(|i| CubicBezier::from_catmull_rom(at(i - 1), at(i), at(i + 1), at(i + 2)))
+        .collect()
+}
+
+/// A chain of cubics through every one of `points`, with Catmull-Rom
+/// tangents scaled by `smoothing`: `1.0` gives `catmull_rom_spline`, `0.0`
+/// straight segments between the points, values in between rounder or
+/// tighter corners. Repeated points are dropped first, as recorded tracks
+/// often hold still.
+pub fn spline_through_points(points: &[Vec2], smoothing: f32) -> Vec<CubicBezier> {
+    let mut points = points.to_vec();
+    points.dedup();
+    if points.len() < 2 {
+        return Vec::new();
+    }
+    let at = |i: isize| points[i.clamp(0, points.len() as isize - 1) as usize];
+    (0..points.len() as isize - 1)
+        .map(|i| {
+            let (p0, p1, p2, p3) = (at(i - 1), at(i), at(i + 1), at(i + 2));
+            CubicBezier::from_hermite(
+                p1,
+                (p2 - p0) * 0.5 * smoothing,
+                p2,
+                (p3 - p1) * 0.5 * smoothing,
+            )
+        })
+        .collect()
+}
+
+/// `segments`, a chain whose consecutive curves share anchors, with every
+/// handle moved `strength` of the way (clamped to `[0, 1]`) towards where
+/// a Catmull-Rom spline through the anchors puts it. The anchors stay put;
+/// a `closed` chain wraps its tangents around the first anchor, an open
+/// one heads at its neighbour at either end, as `catmull_rom_spline` does.
+pub fn smooth_spline(segments: &[CubicBezier], closed: bool, strength: f32) -> Vec<CubicBezier> {
+    let Some(last) = segments.last() else {
+        return Vec::new();
+    };
+    let strength = strength.clamp(0.0, 1.0);
+    let mut anchors: Vec<Vec2> = segments.iter().map(|segment| segment.a).collect();
+    if !closed {
+        anchors.push(last.d);
+    }
+    let n = anchors.len() as isize;
+    let at = |i: isize| {
+        if closed {
+            anchors[i.rem_euclid(n) as usize]
+        } else {
+            anchors[i.clamp(0, n - 1) as usize]
+        }
+    };
+    segments
+        .iter()
+        .enumerate()
+        .map(|(i, segment)| {
+            let i = i as isize;
+            let smooth = CubicBezier::from_catmull_rom(at(i - 1), at(i), at(i + 1), at(i + 2));
+            CubicBezier::new(
+                segment.a,
+                segment.b.lerp(smooth.b, strength),
+                segment.c.lerp(smooth.c, strength),
+                segment.d,
+            )
+        })
+        .collect()
+}
+
+const FIT_REPARAMETERIZE_ITERATIONS: usize = 4;
+
+/// Fits a chain of cubics through sampled `points` (e.g. a freehand stroke)
+/// with Schneider's algorithm, splitting wherever a single curve would
+/// stray more than `max_error` from the samples. Consecutive curves share
+/// their anchors and meet with matching tangents.
+pub fn fit_points(points: &[Vec2], max_error: f32) -> Vec<CubicBezier> {
+    let mut points = points.to_vec();
+    points.dedup();
+    let mut curves = Vec::new();
+    if points.len() < 2 {
+        return curves;
+    }
+    let last = points.len() - 1;
+    let start_tangent = (points[1] - points[0]).normalize_or_zero();
+    let end_tangent = (points[last - 1] - points[last]).normalize_or_zero();
+    fit_cubic(&points, start_tangent, end_tangent, max_error, &mut curves);
+    curves
+}
+
+/// Turn, in radians, between the tangents of two joined curves above which
+/// `simplify` keeps the join as a corner.
+const SIMPLIFY_CORNER_ANGLE: f32 = 0.2;
+
+/// Fewer curves tracing `curves` to within `tolerance`, e.g. for SVG
+/// imports made of hundreds of tiny segments. Runs of joined curves are
+/// flattened and re-fitted with `fit_points`, keeping sharp corners and
+/// gaps between curves where they were. A run that doesn't get any
+/// shorter is kept as it was.
+pub fn simplify(curves: &[CubicBezier], tolerance: f32) -> Vec<CubicBezier> {
+    let mut runs: Vec<Vec<CubicBezier>> = Vec::new();
+    for curve in curves {
+        let joined = runs.last().and_then(|run| run.last()).is_some_and(|last| {
+            last.d.distance(curve.a) <= f32::EPSILON
+                && last.tangent_at(1.0).angle_to(curve.tangent_at(0.0)).abs()
+                    <= SIMPLIFY_CORNER_ANGLE
+        });
+        match runs.last_mut() {
+            Some(run) if joined => run.push(*curve),
+            _ => runs.push(vec![*curve]),
+        }
+    }
+    let mut simplified = Vec::new();
+    for run in runs {
+        if run.len() < 2 {
+            simplified.extend(run);
+            continue;
+        }
+        let mut points: Vec<Vec2> = Vec::new();
+        for curve in &run {
+            let flat = curve.flatten(tolerance * 0.25);
+            let skip = usize::from(points.last() == flat.first());
+            points.extend(flat.into_iter().skip(skip));
+        }
+        let fitted = fit_points(&points, tolerance);
+        if !fitted.is_empty() && fitted.len() < run.len() {
+            simplified.extend(fitted);
+        } else {
+            simplified.extend(run);
+        }
+    }
+    simplified
+}
+
+fn fit_cubic(
+    points: &[Vec2],
+    start_tangent: Vec2,
+    end_tangent: Vec2,
+    max_error: f32,
+    curves: &mut Vec<CubicBezier>,
+) {
+    let (first, last) = (points[0], points[points.len() - 1]);
+    if points.len() == 2 {
+        let handle = first.distance(last) / 3.0;
+        curves.push(CubicBezier::new(
+            first,
+            first + start_tangent * handle,
+            last + end_tangent * handle,
+            last,
+        ));
+        return;
+    }
+    let mut params = chord_length_parameterize(points);
+    let mut curve = fit_bezier(points, &params, start_tangent, end_tangent);
+    let (mut error, mut split) = max_fit_error(points, &curve, &params);
+    if error < max_error {
+        curves.push(curve);
+        return;
+    }
+    // close enough that nudging the parameters may be all it takes
+    if error < max_error * 4.0 {
+        for _ in 0..FIT_REPARAMETERIZE_ITERATIONS {
+            params = reparameterize(points, &params, &curve);
+            curve = fit_bezier(points, &params, start_tangent, end_tangent);
+            (error, split) = max_fit_error(points, &curve, &params);
+            if error < max_error {
+                curves.push(curve);
+                return;
+            }
+        }
+    }
+    let center_tangent = (points[split - 1] - points[split + 1])
+        .try_normalize()
+        .unwrap_or_else(|| (points[split - 1] - points[split]).normalize_or_zero());
+    fit_cubic(
+        &points[..=split],
+        start_tangent,
+        center_tangent,
+        max_error,
+        curves,
+    );
+    fit_cubic(
+        &points[split..],
+        -center_tangent,
+        end_tangent,
+        max_error,
+        curves,
+    );
+}
+
+const MERGE_SAMPLES: usize = 16;
+
+/// How far, relative to the pair's size, `merge_beziers` lets the halves of
+/// a rebuilt cubic be from the pair and still count it as a split.
+const MERGE_SPLIT_TOLERANCE: f32 = 1e-4;
+
+/// Single cubic approximating `first` followed by `second`, fitted by least
+/// squares with its handles along the outer tangents of the pair. When the
+/// two came from splitting one cubic, that cubic is rebuilt exactly instead.
+pub fn merge_beziers(first: &CubicBezier, second: &CubicBezier) -> CubicBezier {
+    if let Some(original) = unsplit_beziers(first, second) {
+        return original;
+    }
+    let mut points = first.sample(MERGE_SAMPLES);
+    points.extend(second.sample(MERGE_SAMPLES).into_iter().skip(1));
+    points.dedup();
+    if points.len() < 3 {
+        return CubicBezier::new(first.a, first.b, second.c, second.d);
+    }
+    let outward = |anchor: Vec2, handles: [Vec2; 3]| {
+        handles
+            .iter()
+            .find_map(|&handle| (handle - anchor).try_normalize())
+            .unwrap_or(Vec2::ZERO)
+    };
+    let start_tangent = outward(first.a, [first.b, first.c, first.d]);
+    let end_tangent = outward(second.d, [second.c, second.b, second.a]);
+    let params = chord_length_parameterize(&points);
+    fit_bezier(&points, &params, start_tangent, end_tangent)
+}
+
+/// The cubic that splitting at some `t` gives `first` and `second`, if
+/// any. De Casteljau puts the joint `t` of the way from `first.c` to
+/// `second.b`, and the outer handles `t` and `1 - t` of the way along the
+/// original ones.
+fn unsplit_beziers(first: &CubicBezier, second: &CubicBezier) -> Option<CubicBezier> {
+    let (joint, before, after) = (first.d, first.c, second.b);
+    let span = after - before;
+    let t = (joint - before).dot(span) / span.length_squared();
+    if !t.is_finite() || t <= 1e-3 || t >= 1.0 - 1e-3 {
+        return None;
+    }
+    let original = CubicBezier::new(
+        first.a,
+        first.a + (first.b - first.a) / t,
+        second.d + (second.c - second.d) / (1.0 - t),
+        second.d,
+    );
+    let (left, right) = split_bezier(original.a, original.b, original.c, original.d, t);
+    let size = [
+        first.a, first.b, first.c, first.d, second.b, second.c, second.d,
+    ]
+    .iter()
+    .map(|point| point.distance(first.a))
+    .fold(1.0, f32::max);
+    let near = |(a, b, c, d): (Vec2, Vec2, Vec2, Vec2), curve: &CubicBezier| {
+        [a, b, c, d]
+            .iter()
+            .zip(curve.points())
+            .all(|(point, expected)| point.distance(expected) <= MERGE_SPLIT_TOLERANCE * size)
+    };
+    (second.a == joint && near(left, first) && near(right, second)).then_some(original)
+}
+
+/// Parameter of each point by its share of the polyline's length.
+fn chord_length_parameterize(points: &[Vec2]) -> Vec<f32> {
+    let mut params = Vec::with_capacity(points.len());
+    let mut length = 0.0;
+    params.push(0.0);
+    for pair in points.windows(2) {
+        length += pair[0].distance(pair[1]);
+        params.push(length);
+    }
+    if length > 0.0 {
+        for param in params.iter_mut() {
+            *param /= length;
+        }
+    }
+    params
+}
+
+/// Least-squares cubic through `points` at `params`, with its handles along
+/// the given tangents.
+fn fit_bezier(
+    points: &[Vec2],
+    params: &[f32],
+    start_tangent: Vec2,
+    end_tangent: Vec2,
+) -> CubicBezier {
+    let (first, last) = (points[0], points[points.len() - 1]);
+    let mut c = [[0.0f32; 2]; 2];
+    let mut x = [0.0f32; 2];
+    for (point, &t) in points.iter().zip(params) {
+        let mt = 1.0 - t;
+        let (b0, b1, b2, b3) = (mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t * t, t * t * t);
+        let (a0, a1) = (start_tangent * b1, end_tangent * b2);
+        c[0][0] += a0.dot(a0);
+        c[0][1] += a0.dot(a1);
+        c[1][1] += a1.dot(a1);
+        let rest = *point - (first * (b0 + b1) + last * (b2 + b3));
+        x[0] += a0.dot(rest);
+        x[1] += a1.dot(rest);
+    }
+    c[1][0] = c[0][1];
+    let det = c[0][0] * c[1][1] - c[1][0] * c[0][1];
+    let (mut alpha_start, mut alpha_end) = if det.abs() > f32::EPSILON {
+        (
+            (x[0] * c[1][1] - x[1] * c[0][1]) / det,
+            (c[0][0] * x[1] - c[1][0] * x[0]) / det,
+        )
+    } else {
+        (0.0, 0.0)
+    };
+    // a degenerate or backwards solution falls back to the usual heuristic
+    let length = first.distance(last);
+    let epsilon = 1.0e-6 * length;
+    if alpha_start < epsilon || alpha_end < epsilon {
+        alpha_start = length / 3.0;
+        alpha_end = length / 3.0;
+    }
+    CubicBezier::new(
+        first,
+        first + start_tangent * alpha_start,
+        last + end_tangent * alpha_end,
+        last,
+    )
+}
+
+/// Largest distance from a point to its place on `curve`, and the
+/// index of that point, never an end so it can split the points.
+fn max_fit_error(points: &[Vec2], curve: &CubicBezier, params: &[f32]) -> (f32, usize) {
+    let mut split = points.len() / 2;
+    let mut max = 0.0;
+    for (i, (point, &t)) in points.iter().zip(params).enumerate() {
+        let distance = curve.point_at(t).distance(*point);
+        if distance > max {
+            max = distance;
+            split = i;
+        }
+    }
+    (max, split.clamp(1, points.len() - 2))
+}
+
+/// One Newton-Raphson step per point towards the parameter of its closest
+/// point on `curve`.
+fn reparameterize(points: &[Vec2], params: &[f32], curve: &CubicBezier) -> Vec<f32> {
+    points
+        .iter()
+        .zip(params)
+        .map(|(point, &t)| {
+            let offset = curve.point_at(t) - *point;
+            let first = curve.derivative_at(t);
+            let second = bezier_second_derivative(curve.a, curve.b, curve.c, curve.d, t);
+            let denominator = first.dot(first) + offset.dot(second);
+            if denominator.abs() <= f32::EPSILON {
+                t
+            } else {
+                (t - offset.dot(first) / denominator).clamp(0.0, 1.0)
+            }
+        })
+        .collect()
+}
+
+/// A rope hanging from `start` to `end` whose middle drops `sag` below the
+/// chord's midpoint, along -y. Both handles hang straight down from the
+/// thirds of the chord, which keeps the curve symmetric like a catenary.
+pub fn sag_bezier(start: Vec2, end: Vec2, sag: f32) -> CubicBezier {
+    // the midpoint of a cubic is 3/4 of the way to its handles' midpoint
+    let drop = Vec2::NEG_Y * sag / 0.75;
+    CubicBezier::new(
+        start,
+        start.lerp(end, 1.0 / 3.0) + drop,
+        start.lerp(end, 2.0 / 3.0) + drop,
+        end,
+    )
+}
+
+/// A rope from `start` to `end` that is `slack` longer than the straight
+/// line between them, sagging along -y as `sag_bezier` does.
+pub fn rope_bezier(start: Vec2, end: Vec2, slack: f32) -> CubicBezier {
+    let length = start.distance(end) + slack.max(0.0);
+    let (mut low, mut high) = (0.0, length.max(1.0));
+    for _ in 0..32 {
+        let sag = (low + high) * 0.5;
+        if sag_bezier(start, end, sag).arc_length() < length {
+            low = sag;
+        } else {
+            high = sag;
+        }
+    }
+    sag_bezier(start, end, (low + high) * 0.5)
+}
+
+/// The curve `t` of the way from `from` to `to`, each point moving in a
+/// straight line.
+pub fn lerp_curves(from: &CubicBezier, to: &CubicBezier, t: f32) -> CubicBezier {
+    CubicBezier::new(
+        from.a.lerp(to.a, t),
+        from.b.lerp(to.b, t),
+        from.c.lerp(to.c, t),
+        from.d.lerp(to.d, t),
+    )
+}
+
+/// `spline` split until it has `count` segments, always halving the longest,
+/// so the shape is unchanged. Splines with `count` or more segments, or
+/// none, are returned as they are.
+pub fn resample_spline(spline: &[CubicBezier], count: usize) -> Vec<CubicBezier> {
+    let mut segments = spline.to_vec();
+    while !segments.is_empty() && segments.len() < count {
+        let (longest, _) = segments
+            .iter()
+            .enumerate()
+            .map(|(i, segment)| (i, segment.arc_length()))
+            .max_by(|(_, x), (_, y)| x.total_cmp(y))
+            .unwrap();
+        let (first, second) = segments[longest].split(0.5);
+        segments[longest] = first;
+        segments.insert(longest + 1, second);
+    }
+    segments
+}
+
+/// The spline `t` of the way from `from` to `to`. When their segment
+/// counts differ, the one with fewer is first resampled with
+/// `resample_spline`, so the two can be blended segment by segment.
+pub fn morph_splines(from: &[CubicBezier], to: &[CubicBezier], t: f32) -> Vec<CubicBezier> {
+    let count = from.len().max(to.len());
+    let (from, to) = (resample_spline(from, count), resample_spline(to, count));
+    from.iter()
+        .zip(&to)
+        .map(|(from, to)| lerp_curves(from, to, t))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(from: Vec2, to: Vec2) -> (Vec2, Vec2, Vec2, Vec2) {
+        (from, from.lerp(to, 1.0 / 3.0), from.lerp(to, 2.0 / 3.0), to)
+    }
+
+    #[test]
+    fn fillet_of_a_sharp_corner_stays_on_the_circle() {
+        // 60° between the segments, so the arc sweeps 120°
+        let forward = Vec2::from_angle(120f32.to_radians());
+        let seg_a = line(Vec2::new(-100.0, 0.0), Vec2::ZERO);
+        let seg_b = line(Vec2::ZERO, forward * 100.0);
+        let radius = 10.0;
+        let fillet = fillet_corner(seg_a, seg_b, radius).unwrap();
+        assert_eq!(fillet.arc.len(), 2);
+
+        let bisector = (Vec2::NEG_X + forward).normalize();
+        let center = bisector * radius / 30f32.to_radians().sin();
+        for &(a, b, c, d) in &fillet.arc {
+            for i in 0..=10 {
+                let point = evaluate_bezier(a, b, c, d, i as f32 / 10.0);
+                assert!((point.distance(center) - radius).abs() < 0.01);
+            }
+        }
+        let first = fillet.arc[0];
+        let last = fillet.arc[1];
+        assert!(first.0.distance(fillet.seg_a.3) < 1e-4);
+        assert!(last.3.distance(fillet.seg_b.0) < 1e-4);
+        // tangent to both segments where it meets them
+        let arc_in = (first.1 - first.0).normalize();
+        let arc_out = (last.3 - last.2).normalize();
+        assert!(arc_in.dot(Vec2::X) > 0.9999);
+        assert!(arc_out.dot(forward) > 0.9999);
+    }
+
+    const CURVE: (Vec2, Vec2, Vec2, Vec2) = (
+        Vec2::new(-40.0, 10.0),
+        Vec2::new(-10.0, 60.0),
+        Vec2::new(30.0, -25.0),
+        Vec2::new(55.0, 5.0),
+    );
+
+    #[test]
+    fn mirroring_twice_gives_back_the_curve() {
+        let (a, b, c, d) = CURVE;
+        let (axis_point, axis_dir) = (Vec2::new(3.0, -7.0), Vec2::new(1.0, 2.5));
+        let (a2, b2, c2, d2) = mirror_bezier(a, b, c, d, axis_point, axis_dir);
+        let (a3, b3, c3, d3) = mirror_bezier(a2, b2, c2, d2, axis_point, axis_dir);
+        for (back, original) in [(a3, a), (b3, b), (c3, c), (d3, d)] {
+            assert!(back.distance(original) < 1e-4);
+        }
+    }
+
+    #[test]
+    fn transforming_the_points_transforms_the_curve() {
+        let mut transform = Transform::from_xyz(12.0, -30.0, 0.0);
+        transform.rotate_z(0.7);
+        transform.scale.x = 1.5;
+        transform.scale.y = 0.5;
+        let point_at = |(a, b, c, d): (Vec2, Vec2, Vec2, Vec2), t: f32| {
+            let (ab, bc, cd) = (a.lerp(b, t), b.lerp(c, t), c.lerp(d, t));
+            ab.lerp(bc, t).lerp(bc.lerp(cd, t), t)
+        };
+        let transformed = transform_bezier(CURVE, &transform);
+        for i in 0..=10 {
+            let t = i as f32 / 10.0;
+            let expected = transform
+                .transform_point(point_at(CURVE, t).extend(0.0))
+                .truncate();
+            assert!(point_at(transformed, t).distance(expected) < 1e-3);
+        }
+    }
+
+    #[test]
+    fn merging_the_halves_of_a_split_gives_back_the_curve() {
+        let (a, b, c, d) = CURVE;
+        let (left, right) = split_bezier(a, b, c, d, 0.3);
+        let (first, second) = (
+            CubicBezier::new(left.0, left.1, left.2, left.3),
+            CubicBezier::new(right.0, right.1, right.2, right.3),
+        );
+        let merged = merge_beziers(&first, &second);
+        for (point, original) in merged.points().iter().zip([a, b, c, d]) {
+            assert!(point.distance(original) < 1e-3);
+        }
+    }
+
+    /// Stops dead at `t = 0.5`, where both dx/dt and dy/dt vanish.
+    const CUSP: (Vec2, Vec2, Vec2, Vec2) = (
+        Vec2::new(0.0, 0.0),
+        Vec2::new(100.0, 100.0),
+        Vec2::new(0.0, 100.0),
+        Vec2::new(100.0, 0.0),
+    );
+
+    /// The usual cubic approximation of a quarter of the circle of radius
+    /// 100 around the origin.
+    const QUARTER_CIRCLE: (Vec2, Vec2, Vec2, Vec2) = (
+        Vec2::new(100.0, 0.0),
+        Vec2::new(100.0, 55.228_475),
+        Vec2::new(55.228_475, 100.0),
+        Vec2::new(0.0, 100.0),
+    );
+
+    #[test]
+    fn an_s_curve_inflects_once() {
+        let inflections = bezier_inflections(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(20.0, -10.0),
+            Vec2::new(30.0, 0.0),
+        );
+        assert_eq!(inflections, vec![0.5]);
+    }
+
+    #[test]
+    fn lines_and_arcs_have_no_inflections() {
+        let collinear = bezier_inflections(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(30.0, 20.0),
+            Vec2::new(60.0, 40.0),
+            Vec2::new(90.0, 60.0),
+        );
+        assert!(collinear.is_empty());
+        let point = Vec2::new(5.0, 5.0);
+        assert!(bezier_inflections(point, point, point, point).is_empty());
+        let (a, b, c, d) = QUARTER_CIRCLE;
+        assert!(bezier_inflections(a, b, c, d).is_empty());
+    }
+
+    #[test]
+    fn inflections_at_the_anchors_are_kept() {
+        let (a, b, c, d) = (
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(20.0, 0.0),
+            Vec2::new(30.0, 10.0),
+        );
+        assert_eq!(bezier_inflections(a, b, c, d), vec![0.0]);
+        assert_eq!(bezier_inflections(d, c, b, a), vec![1.0]);
+    }
+
+    #[test]
+    fn a_cusp_counts_as_one_inflection() {
+        let (a, b, c, d) = CUSP;
+        assert_eq!(bezier_inflections(a, b, c, d), vec![0.5]);
+    }
+
+    #[test]
+    fn straight_curves_classify_as_lines() {
+        let (a, b, c, d) = (
+            Vec2::new(0.0, 0.0),
+            Vec2::new(30.0, 20.0),
+            Vec2::new(60.0, 40.0),
+            Vec2::new(90.0, 60.0),
+        );
+        assert_eq!(classify_bezier(a, b, c, d, 0.1), BezierClass::Line);
+        // collinear, but the controls overshoot the chord so it doubles back
+        let (b, c) = (Vec2::new(120.0, 80.0), Vec2::new(-30.0, -20.0));
+        assert_eq!(classify_bezier(a, b, c, d, 0.1), BezierClass::General);
+    }
+
+    #[test]
+    fn a_quarter_circle_classifies_as_an_arc() {
+        let (a, b, c, d) = QUARTER_CIRCLE;
+        let BezierClass::Arc { center, radius } = classify_bezier(a, b, c, d, 0.1) else {
+            panic!("a quarter circle is an arc");
+        };
+        assert!(center.length() < 0.05);
+        assert!((radius - 100.0).abs() < 0.05);
+        // the cubic strays from the circle by more than this
+        assert_eq!(classify_bezier(a, b, c, d, 0.001), BezierClass::General);
+    }
+
+    #[test]
+    fn closed_and_cusped_curves_classify_as_general() {
+        let (a, b, c, _) = CUSP;
+        assert_eq!(classify_bezier(a, b, c, a, 0.1), BezierClass::General);
+        let point = Vec2::new(5.0, 5.0);
+        assert_eq!(
+            classify_bezier(point, point, point, point, 0.1),
+            BezierClass::General
+        );
+        let (a, b, c, d) = CUSP;
+        assert_eq!(classify_bezier(a, b, c, d, 0.1), BezierClass::General);
+    }
+
+    #[test]
+    fn closest_point_clamps_to_the_anchors() {
+        let line = CubicBezier::new(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(30.0, 0.0),
+            Vec2::new(60.0, 0.0),
+            Vec2::new(90.0, 0.0),
+        );
+        let (t, point, distance) = line.closest_point(Vec2::new(-10.0, 5.0));
+        assert!(t.abs() < 1e-4);
+        assert!(point.distance(line.a) < 1e-3);
+        assert!((distance - 125f32.sqrt()).abs() < 1e-3);
+        let (t, point, distance) = line.closest_point(Vec2::new(100.0, -5.0));
+        assert!((t - 1.0).abs() < 1e-4);
+        assert!(point.distance(line.d) < 1e-3);
+        assert!((distance - 125f32.sqrt()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn closest_point_finds_points_on_the_curve() {
+        let (a, b, c, d) = CURVE;
+        let curve = CubicBezier::new(a, b, c, d);
+        let on_curve = curve.point_at(0.3);
+        let (t, point, distance) = curve.closest_point(on_curve);
+        assert!((t - 0.3).abs() < 1e-3);
+        assert!(point.distance(on_curve) < 1e-3);
+        assert!(distance < 1e-3);
+    }
+
+    #[test]
+    fn closest_point_of_a_degenerate_curve_is_its_point() {
+        let point = Vec2::new(5.0, 5.0);
+        let curve = CubicBezier::new(point, point, point, point);
+        let (_, closest, distance) = curve.closest_point(Vec2::new(8.0, 9.0));
+        assert_eq!(closest, point);
+        assert!((distance - 5.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn closest_point_reaches_a_cusp() {
+        let (a, b, c, d) = CUSP;
+        let curve = CubicBezier::new(a, b, c, d);
+        // the cusp at (50, 75) is the curve's highest point
+        let (t, point, distance) = curve.closest_point(Vec2::new(50.0, 90.0));
+        assert!((t - 0.5).abs() < 1e-2);
+        assert!(point.distance(Vec2::new(50.0, 75.0)) < 1e-2);
+        assert!((distance - 15.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn a_looped_curve_crosses_itself_once() {
+        // symmetric about x = 50; it crosses there at t = (7 ∓ √21) / 14,
+        // where t (1 - t) = 1 / 7
+        let curve = CubicBezier::new(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(150.0, 100.0),
+            Vec2::new(-50.0, 100.0),
+            Vec2::new(100.0, 0.0),
+        );
+        let crossings = curve.self_intersections();
+        assert_eq!(crossings.len(), 1);
+        let (t, u, point) = crossings[0];
+        let offset = 21f32.sqrt() / 14.0;
+        assert!((t - (0.5 - offset)).abs() < 1e-2);
+        assert!((u - (0.5 + offset)).abs() < 1e-2);
+        assert!(point.distance(Vec2::new(50.0, 300.0 / 7.0)) < 0.1);
+    }
+
+    #[test]
+    fn curves_without_a_loop_never_cross_themselves() {
+        let collinear = CubicBezier::new(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(30.0, 20.0),
+            Vec2::new(60.0, 40.0),
+            Vec2::new(90.0, 60.0),
+        );
+        assert!(collinear.self_intersections().is_empty());
+        let (a, b, c, d) = QUARTER_CIRCLE;
+        assert!(CubicBezier::new(a, b, c, d).self_intersections().is_empty());
+        // the two sides of a cusp meet there without crossing
+        let (a, b, c, d) = CUSP;
+        assert!(CubicBezier::new(a, b, c, d).self_intersections().is_empty());
+    }
+
+    #[test]
+    fn x_monotonic_curves_never_run_back_in_x() {
+        let collinear = CubicBezier::new(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(30.0, 20.0),
+            Vec2::new(60.0, 40.0),
+            Vec2::new(90.0, 60.0),
+        );
+        assert!(collinear.is_x_monotonic());
+        // an ease-in-out, flat in x at both anchors
+        let ease = CubicBezier::new(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.0, 0.0),
+            Vec2::new(100.0, 100.0),
+            Vec2::new(100.0, 100.0),
+        );
+        assert!(ease.is_x_monotonic());
+        // dx/dt only touches zero at the cusp
+        let (a, b, c, d) = CUSP;
+        assert!(CubicBezier::new(a, b, c, d).is_x_monotonic());
+    }
+
+    #[test]
+    fn curves_running_back_in_x_are_not_x_monotonic() {
+        let middle = CubicBezier::new(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(100.0, 50.0),
+            Vec2::new(-20.0, 50.0),
+            Vec2::new(80.0, 100.0),
+        );
+        assert!(!middle.is_x_monotonic());
+        let start = CubicBezier::new(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(-10.0, 30.0),
+            Vec2::new(60.0, 100.0),
+            Vec2::new(100.0, 100.0),
+        );
+        assert!(!start.is_x_monotonic());
+        let end = CubicBezier::new(
+            Vec2::new(0.0, 0.0),
+            Vec2::new(40.0, 0.0),
+            Vec2::new(110.0, 70.0),
+            Vec2::new(100.0, 100.0),
+        );
+        assert!(!end.is_x_monotonic());
+    }
+
+    #[test]
+    fn winding_number_follows_the_outline_direction() {
+        let square = [
+            Vec2::new(-10.0, -10.0),
+            Vec2::new(10.0, -10.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(-10.0, 10.0),
+        ];
+        assert_eq!(winding_number(&square, Vec2::ZERO), 1);
+        assert_eq!(winding_number(&square, Vec2::new(20.0, 0.0)), 0);
+        let clockwise: Vec<Vec2> = square.iter().rev().copied().collect();
+        assert_eq!(winding_number(&clockwise, Vec2::ZERO), -1);
+        let twice: Vec<Vec2> = square.iter().chain(&square).copied().collect();
+        assert_eq!(winding_number(&twice, Vec2::ZERO), 2);
+    }
+
+    #[test]
+    fn a_ray_through_vertices_counts_each_crossing_once() {
+        let diamond = [
+            Vec2::new(0.0, -10.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(0.0, 10.0),
+            Vec2::new(-10.0, 0.0),
+        ];
+        assert_eq!(winding_number(&diamond, Vec2::ZERO), 1);
+        assert_eq!(winding_number(&diamond, Vec2::new(-20.0, 0.0)), 0);
+    }
+
+    #[test]
+    fn degenerate_outlines_wind_around_nothing() {
+        assert_eq!(winding_number(&[], Vec2::ZERO), 0);
+        let collinear = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 0.0),
+            Vec2::new(20.0, 0.0),
+        ];
+        assert_eq!(winding_number(&collinear, Vec2::new(5.0, 0.0)), 0);
+        let slanted = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(20.0, 20.0),
+        ];
+        assert_eq!(winding_number(&slanted, Vec2::new(5.0, 5.0)), 0);
+    }
+}
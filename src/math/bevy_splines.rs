@@ -0,0 +1,73 @@
+use super::*;
+use bevy_math::cubic_splines::{
+    CubicBezier as BevyCubicBezier, CubicCurve, CubicGenerator, CubicSegment,
+};
+
+impl From<CubicBezier> for CubicSegment<Vec2> {
+    /// The same curve in the power basis Bevy evaluates segments in.
+    fn from(curve: CubicBezier) -> Self {
+        let CubicBezier { a, b, c, d } = curve;
+        CubicSegment {
+            coeff: [
+                a,
+                3.0 * (b - a),
+                3.0 * (a - 2.0 * b + c),
+                d - a + 3.0 * (b - c),
+            ],
+        }
+    }
+}
+
+impl From<CubicSegment<Vec2>> for CubicBezier {
+    fn from(segment: CubicSegment<Vec2>) -> Self {
+        let [c0, c1, c2, c3] = segment.coeff;
+        CubicBezier::new(
+            c0,
+            c0 + c1 / 3.0,
+            c0 + (2.0 * c1 + c2) / 3.0,
+            c0 + c1 + c2 + c3,
+        )
+    }
+}
+
+impl From<CubicBezier> for BevyCubicBezier<Vec2> {
+    fn from(curve: CubicBezier) -> Self {
+        BevyCubicBezier::new([curve.points()])
+    }
+}
+
+impl CubicBezier {
+    /// The curve as a one-segment Bevy `CubicCurve`, which implements
+    /// Bevy's `Curve` trait for sampling, easing and animation.
+    pub fn to_bevy_curve(&self) -> CubicCurve<Vec2> {
+        bevy_curve(std::slice::from_ref(self)).expect("one segment is enough for a curve")
+    }
+}
+
+/// `curves` chained into one Bevy `CubicCurve`, a segment each, e.g. the
+/// segments of a spline in order. `None` for no curves.
+pub fn bevy_curve(curves: &[CubicBezier]) -> Option<CubicCurve<Vec2>> {
+    BevyCubicBezier::new(curves.iter().map(CubicBezier::points))
+        .to_curve()
+        .ok()
+}
+
+/// The segments of a Bevy `CubicCurve`, from whichever spline built it
+/// (Hermite, Catmull-Rom, B-spline...), as bezier curves.
+pub fn from_bevy_curve(curve: &CubicCurve<Vec2>) -> Vec<CubicBezier> {
+    curve
+        .segments()
+        .iter()
+        .copied()
+        .map(CubicBezier::from)
+        .collect()
+}
+
+/// The curves of a Bevy `CubicBezier`, one per set of control points.
+pub fn from_bevy_bezier(bezier: &BevyCubicBezier<Vec2>) -> Vec<CubicBezier> {
+    bezier
+        .control_points
+        .iter()
+        .map(|&points| CubicBezier::from(points))
+        .collect()
+}
@@ -0,0 +1,110 @@
+use super::*;
+use bevy_math::Vec3;
+
+const ARC_LENGTH_SAMPLES: usize = 64;
+
+/// A cubic bezier segment in 3D, e.g. a camera path: anchors `a` and `d`,
+/// controls `b` and `c`. The 3D counterpart of `CubicBezier`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CubicBezier3 {
+    pub a: Vec3,
+    pub b: Vec3,
+    pub c: Vec3,
+    pub d: Vec3,
+}
+
+impl CubicBezier3 {
+    pub fn new(a: Vec3, b: Vec3, c: Vec3, d: Vec3) -> Self {
+        Self { a, b, c, d }
+    }
+    pub fn points(&self) -> [Vec3; 4] {
+        [self.a, self.b, self.c, self.d]
+    }
+    /// Point on the curve at `t` in `[0, 1]`.
+    pub fn point_at(&self, t: f32) -> Vec3 {
+        let mt = 1.0 - t;
+        self.a * (mt * mt * mt)
+            + self.b * (3.0 * mt * mt * t)
+            + self.c * (3.0 * mt * t * t)
+            + self.d * (t * t * t)
+    }
+    /// First derivative at `t`.
+    pub fn derivative_at(&self, t: f32) -> Vec3 {
+        let mt = 1.0 - t;
+        3.0 * mt * mt * (self.b - self.a)
+            + 6.0 * mt * t * (self.c - self.b)
+            + 3.0 * t * t * (self.d - self.c)
+    }
+    /// Unit direction of travel at `t`, or zero where the curve stalls.
+    pub fn tangent_at(&self, t: f32) -> Vec3 {
+        self.derivative_at(t).normalize_or_zero()
+    }
+    /// Splits the curve at `t` into two curves that together trace it.
+    pub fn split(&self, t: f32) -> (CubicBezier3, CubicBezier3) {
+        let ab = self.a.lerp(self.b, t);
+        let bc = self.b.lerp(self.c, t);
+        let cd = self.c.lerp(self.d, t);
+        let abc = ab.lerp(bc, t);
+        let bcd = bc.lerp(cd, t);
+        let mid = abc.lerp(bcd, t);
+        (
+            CubicBezier3::new(self.a, ab, abc, mid),
+            CubicBezier3::new(mid, bcd, cd, self.d),
+        )
+    }
+    /// `n` points at evenly spaced parameters, from `a` to `d` inclusive.
+    pub fn sample(&self, n: usize) -> Vec<Vec3> {
+        match n {
+            0 => Vec::new(),
+            1 => vec![self.a],
+            _ => (0..n)
+                .map(|i| self.point_at(i as f32 / (n - 1) as f32))
+                .collect(),
+        }
+    }
+    /// Polyline through the curve staying within `tolerance` of it.
+    pub fn flatten(&self, tolerance: f32) -> Vec<Vec3> {
+        let mut points = vec![self.a];
+        flatten_into3(*self, tolerance.max(f32::EPSILON), 0, &mut points);
+        points
+    }
+    /// Length of the curve, summed over a fine polyline.
+    pub fn arc_length(&self) -> f32 {
+        self.sample(ARC_LENGTH_SAMPLES + 1)
+            .windows(2)
+            .map(|pair| pair[0].distance(pair[1]))
+            .sum()
+    }
+    /// The curve seen from above the xy plane, dropping `z`.
+    pub fn to_2d(&self) -> CubicBezier {
+        CubicBezier::new(
+            self.a.truncate(),
+            self.b.truncate(),
+            self.c.truncate(),
+            self.d.truncate(),
+        )
+    }
+}
+
+impl From<CubicBezier> for CubicBezier3 {
+    /// The curve in the xy plane at `z = 0`.
+    fn from(curve: CubicBezier) -> Self {
+        let [a, b, c, d] = curve.points().map(|point| point.extend(0.0));
+        Self { a, b, c, d }
+    }
+}
+
+fn flatten_into3(curve: CubicBezier3, tolerance: f32, depth: u32, points: &mut Vec<Vec3>) {
+    let u = 3.0 * curve.b - 2.0 * curve.a - curve.d;
+    let v = 3.0 * curve.c - curve.a - 2.0 * curve.d;
+    let flatness = (u * u).max(v * v);
+    if depth >= MAX_FLATTEN_DEPTH
+        || flatness.x + flatness.y + flatness.z <= 16.0 * tolerance * tolerance
+    {
+        points.push(curve.d);
+        return;
+    }
+    let (first, second) = curve.split(0.5);
+    flatten_into3(first, tolerance, depth + 1, points);
+    flatten_into3(second, tolerance, depth + 1, points);
+}
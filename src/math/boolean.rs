@@ -0,0 +1,267 @@
+use super::*;
+use std::collections::HashMap;
+
+const BOOLEAN_FLATTEN_TOLERANCE: f32 = 0.1;
+const BOOLEAN_FIT_ERROR: f32 = 0.5;
+
+/// A closed outline made of cubic segments, each starting where the
+/// previous one ends; the last ends where the first starts.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ClosedPath {
+    pub segments: Vec<CubicBezier>,
+}
+
+impl ClosedPath {
+    pub fn new(segments: Vec<CubicBezier>) -> Self {
+        Self { segments }
+    }
+    /// Polygon within `tolerance` of the outline, without repeating the
+    /// first point at the end.
+    pub fn flatten(&self, tolerance: f32) -> Vec<Vec2> {
+        let mut outline: Vec<Vec2> = Vec::new();
+        for segment in &self.segments {
+            let flat = segment.flatten(tolerance);
+            let skip = usize::from(outline.last() == flat.first());
+            outline.extend(flat.into_iter().skip(skip));
+        }
+        if outline.len() > 1 && outline.first() == outline.last() {
+            outline.pop();
+        }
+        outline
+    }
+    /// Area enclosed by the path, positive when it runs counter-clockwise
+    /// and negative when clockwise. Exact for the cubics (Green's theorem),
+    /// not an estimate from a flattened outline. Parts where the path
+    /// crosses itself count once per winding.
+    pub fn signed_area(&self) -> f32 {
+        self.segments
+            .iter()
+            .map(|segment| {
+                green_integral(segment, |point, velocity| point.perp_dot(velocity) * 0.5)
+            })
+            .sum()
+    }
+    /// Area enclosed by the path, whichever way it runs.
+    pub fn area(&self) -> f32 {
+        self.signed_area().abs()
+    }
+    /// Center of mass of the enclosed region, e.g. for a label or pivot at
+    /// the visual center of a shape. `None` when the path encloses no area.
+    pub fn centroid(&self) -> Option<Vec2> {
+        let area = self.signed_area();
+        if area.abs() <= f32::EPSILON {
+            return None;
+        }
+        // ∬ x dA = ∮ x²/2 dy and ∬ y dA = -∮ y²/2 dx
+        let moment = |f: fn(Vec2, Vec2) -> f32| -> f32 {
+            self.segments
+                .iter()
+                .map(|segment| green_integral(segment, f))
+                .sum()
+        };
+        let x = moment(|point, velocity| point.x * point.x * velocity.y * 0.5);
+        let y = moment(|point, velocity| -point.y * point.y * velocity.x * 0.5);
+        Some(Vec2::new(x, y) / area)
+    }
+}
+
+/// Five point Gauss-Legendre nodes and weights on `[0, 1]`, exact for the
+/// polynomials up to degree nine that the integrands of a cubic make.
+const GAUSS_LEGENDRE_5: [(f32, f32); 5] = [
+    (0.046_910_077, 0.118_463_44),
+    (0.230_765_34, 0.239_314_34),
+    (0.5, 0.284_444_44),
+    (0.769_234_66, 0.239_314_34),
+    (0.953_089_9, 0.118_463_44),
+];
+
+/// `∫ f(B(t), B'(t)) dt` over `segment`.
+fn green_integral(segment: &CubicBezier, f: impl Fn(Vec2, Vec2) -> f32) -> f32 {
+    GAUSS_LEGENDRE_5
+        .iter()
+        .map(|&(t, weight)| weight * f(segment.point_at(t), segment.derivative_at(t)))
+        .sum()
+}
+
+/// How `path_boolean` combines two regions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BooleanOp {
+    Union,
+    Intersection,
+    /// The first region with the second cut out of it.
+    Difference,
+}
+
+/// Combines the regions enclosed by `a` and `b`. Both are flattened, the
+/// polygon edges are split where they cross, the pieces each op keeps are
+/// chained back into loops, and those are re-fitted to cubics, with sharp
+/// corners kept where the outlines crossed.
+///
+/// A result can hold several paths: disjoint pieces, or an outline and the
+/// holes cut into it (wound the other way), which fill correctly under
+/// either fill rule.
+pub fn path_boolean(a: &ClosedPath, b: &ClosedPath, op: BooleanOp) -> Vec<ClosedPath> {
+    let polygon_a = counter_clockwise(a.flatten(BOOLEAN_FLATTEN_TOLERANCE));
+    let polygon_b = counter_clockwise(b.flatten(BOOLEAN_FLATTEN_TOLERANCE));
+    if polygon_a.len() < 3 || polygon_b.len() < 3 {
+        return match op {
+            BooleanOp::Union | BooleanOp::Difference if polygon_a.len() >= 3 => vec![a.clone()],
+            BooleanOp::Union if polygon_b.len() >= 3 => vec![b.clone()],
+            _ => Vec::new(),
+        };
+    }
+    let (split_a, split_b) = split_at_crossings(&polygon_a, &polygon_b);
+
+    let (keep_a_inside, keep_b_inside, reverse_b) = match op {
+        BooleanOp::Union => (false, false, false),
+        BooleanOp::Intersection => (true, true, false),
+        BooleanOp::Difference => (false, true, true),
+    };
+    let mut edges = Vec::new();
+    for ([start, end], [crossing, _]) in kept_edges(&split_a, &polygon_b, keep_a_inside) {
+        edges.push((start, end, crossing));
+    }
+    for ([start, end], [start_crossing, end_crossing]) in
+        kept_edges(&split_b, &polygon_a, keep_b_inside)
+    {
+        if reverse_b {
+            edges.push((end, start, end_crossing));
+        } else {
+            edges.push((start, end, start_crossing));
+        }
+    }
+    chain_loops(&edges)
+        .into_iter()
+        .filter_map(|(points, corners)| fit_loop(&points, &corners))
+        .collect()
+}
+
+/// The closed polygons with a vertex added wherever an edge of one crosses
+/// an edge of the other; each vertex is flagged if it is such a crossing.
+/// Both get the very same crossing points so their pieces chain exactly.
+#[allow(clippy::type_complexity)]
+fn split_at_crossings(a: &[Vec2], b: &[Vec2]) -> (Vec<(Vec2, bool)>, Vec<(Vec2, bool)>) {
+    let mut cuts_a: Vec<Vec<(f32, Vec2)>> = vec![Vec::new(); a.len()];
+    let mut cuts_b: Vec<Vec<(f32, Vec2)>> = vec![Vec::new(); b.len()];
+    for i in 0..a.len() {
+        let (p0, p1) = (a[i], a[(i + 1) % a.len()]);
+        for j in 0..b.len() {
+            let (q0, q1) = (b[j], b[(j + 1) % b.len()]);
+            if let Some((s, t)) = segment_crossing(p0, p1, q0, q1) {
+                let point = p0.lerp(p1, s);
+                cuts_a[i].push((s, point));
+                cuts_b[j].push((t, point));
+            }
+        }
+    }
+    (with_cuts(a, cuts_a), with_cuts(b, cuts_b))
+}
+
+fn with_cuts(polygon: &[Vec2], mut cuts: Vec<Vec<(f32, Vec2)>>) -> Vec<(Vec2, bool)> {
+    let mut split = Vec::with_capacity(polygon.len());
+    for (point, edge_cuts) in polygon.iter().zip(cuts.iter_mut()) {
+        split.push((*point, false));
+        edge_cuts.sort_by(|x, y| x.0.total_cmp(&y.0));
+        split.extend(edge_cuts.iter().map(|&(_, point)| (point, true)));
+    }
+    split.dedup_by(|x, y| x.0 == y.0);
+    split
+}
+
+/// Parameters along `p0..p1` and `q0..q1` where the two segments properly
+/// cross. Touching at an end of `p` counts once, at its start.
+fn segment_crossing(p0: Vec2, p1: Vec2, q0: Vec2, q1: Vec2) -> Option<(f32, f32)> {
+    let r = p1 - p0;
+    let s = q1 - q0;
+    let denominator = r.perp_dot(s);
+    if denominator.abs() <= f32::EPSILON {
+        return None;
+    }
+    let offset = q0 - p0;
+    let t = offset.perp_dot(s) / denominator;
+    let u = offset.perp_dot(r) / denominator;
+    ((0.0..1.0).contains(&t) && (0.0..1.0).contains(&u)).then_some((t, u))
+}
+
+/// Edges of `split` whose midpoint is inside (or outside) `other`, with
+/// whether each end is a crossing.
+fn kept_edges(split: &[(Vec2, bool)], other: &[Vec2], inside: bool) -> Vec<([Vec2; 2], [bool; 2])> {
+    (0..split.len())
+        .filter_map(|i| {
+            let (start, start_crossing) = split[i];
+            let (end, end_crossing) = split[(i + 1) % split.len()];
+            let midpoint = start.lerp(end, 0.5);
+            (start != end && (winding_number(other, midpoint) != 0) == inside)
+                .then_some(([start, end], [start_crossing, end_crossing]))
+        })
+        .collect()
+}
+
+/// `polygon` wound counter-clockwise, so edges of both operands chain in
+/// the same direction.
+fn counter_clockwise(mut polygon: Vec<Vec2>) -> Vec<Vec2> {
+    let area: f32 = (0..polygon.len())
+        .map(|i| polygon[i].perp_dot(polygon[(i + 1) % polygon.len()]))
+        .sum();
+    if area < 0.0 {
+        polygon.reverse();
+    }
+    polygon
+}
+
+/// Loops formed by following edges end to start, each with the indices of
+/// its points that are crossings.
+fn chain_loops(edges: &[(Vec2, Vec2, bool)]) -> Vec<(Vec<Vec2>, Vec<usize>)> {
+    let key = |point: Vec2| (point.x.to_bits(), point.y.to_bits());
+    let mut starting: HashMap<(u32, u32), Vec<usize>> = HashMap::new();
+    for (index, (start, _, _)) in edges.iter().enumerate() {
+        starting.entry(key(*start)).or_default().push(index);
+    }
+    let mut used = vec![false; edges.len()];
+    let mut loops = Vec::new();
+    for first in 0..edges.len() {
+        if used[first] {
+            continue;
+        }
+        let mut points = Vec::new();
+        let mut corners = Vec::new();
+        let mut current = first;
+        loop {
+            used[current] = true;
+            let (start, end, crossing) = edges[current];
+            if crossing {
+                corners.push(points.len());
+            }
+            points.push(start);
+            let next = starting
+                .get(&key(end))
+                .and_then(|candidates| candidates.iter().copied().find(|&i| !used[i]));
+            match next {
+                Some(next) => current = next,
+                None => break,
+            }
+        }
+        if points.len() >= 3 {
+            loops.push((points, corners));
+        }
+    }
+    loops
+}
+
+/// Fits cubics to a closed polygon, one run of `fit_points` between each
+/// pair of consecutive corners so they stay sharp.
+fn fit_loop(points: &[Vec2], corners: &[usize]) -> Option<ClosedPath> {
+    let count = points.len();
+    let corners: Vec<usize> = if corners.is_empty() {
+        vec![0]
+    } else {
+        corners.to_vec()
+    };
+    let mut segments = Vec::new();
+    for (i, &from) in corners.iter().enumerate() {
+        let to = corners.get(i + 1).copied().unwrap_or(corners[0] + count);
+        let run: Vec<Vec2> = (from..=to).map(|index| points[index % count]).collect();
+        segments.extend(fit_points(&run, BOOLEAN_FIT_ERROR));
+    }
+    (!segments.is_empty()).then(|| ClosedPath::new(segments))
+}
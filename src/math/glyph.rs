@@ -0,0 +1,83 @@
+use super::*;
+use ttf_parser::OutlineBuilder;
+
+/// Why `glyph_to_curves` couldn't outline a character.
+#[derive(Debug)]
+pub enum GlyphError {
+    Font(ttf_parser::FaceParsingError),
+    MissingGlyph(char),
+}
+
+impl std::fmt::Display for GlyphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GlyphError::Font(error) => write!(f, "couldn't parse the font: {}", error),
+            GlyphError::MissingGlyph(c) => write!(f, "the font has no glyph for '{}'", c),
+        }
+    }
+}
+
+impl std::error::Error for GlyphError {}
+
+/// The outline of `c` in the TrueType or OpenType font `font_bytes`, one
+/// `ClosedPath` per contour, in font units with y up. TrueType quadratics
+/// are elevated to cubics and straight edges become cubics with controls on
+/// the line. Outer contours and holes wind opposite ways, so the result
+/// fills correctly under the non-zero rule. Whitespace glyphs have no
+/// contours.
+pub fn glyph_to_curves(font_bytes: &[u8], c: char) -> Result<Vec<ClosedPath>, GlyphError> {
+    let face = ttf_parser::Face::parse(font_bytes, 0).map_err(GlyphError::Font)?;
+    let glyph = face.glyph_index(c).ok_or(GlyphError::MissingGlyph(c))?;
+    let mut outline = GlyphOutline::default();
+    face.outline_glyph(glyph, &mut outline);
+    outline.close();
+    Ok(outline.paths)
+}
+
+#[derive(Default)]
+struct GlyphOutline {
+    paths: Vec<ClosedPath>,
+    segments: Vec<CubicBezier>,
+    start: Vec2,
+    current: Vec2,
+}
+
+impl GlyphOutline {
+    fn push(&mut self, b: Vec2, c: Vec2, d: Vec2) {
+        let a = self.current;
+        if a != d || b != a || c != a {
+            self.segments.push(CubicBezier { a, b, c, d });
+        }
+        self.current = d;
+    }
+}
+
+impl OutlineBuilder for GlyphOutline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.close();
+        self.start = Vec2::new(x, y);
+        self.current = self.start;
+    }
+    fn line_to(&mut self, x: f32, y: f32) {
+        let (a, d) = (self.current, Vec2::new(x, y));
+        self.push(a.lerp(d, 1.0 / 3.0), a.lerp(d, 2.0 / 3.0), d);
+    }
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let d = Vec2::new(x, y);
+        let (b, c) = elevate_quadratic(self.current, Vec2::new(x1, y1), d);
+        self.push(b, c, d);
+    }
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.push(Vec2::new(x1, y1), Vec2::new(x2, y2), Vec2::new(x, y));
+    }
+    fn close(&mut self) {
+        if self.current != self.start {
+            self.line_to(self.start.x, self.start.y);
+        }
+        if !self.segments.is_empty() {
+            self.paths
+                .push(ClosedPath::new(std::mem::take(&mut self.segments)));
+        }
+        self.current = self.start;
+    }
+}
@@ -0,0 +1,90 @@
+use super::*;
+use std::fmt::Write;
+
+/// `curves` flattened to within `tolerance`, as the strokes a plotter
+/// draws: curves that start where the previous one ended are chained into
+/// one polyline so the pen stays down across the join.
+pub fn export_polyline(curves: &[CubicBezier], tolerance: f32) -> Vec<Vec<Vec2>> {
+    let mut polylines: Vec<Vec<Vec2>> = Vec::new();
+    for curve in curves {
+        let flat = curve.flatten(tolerance);
+        match polylines.last_mut() {
+            Some(polyline) if polyline.last() == flat.first() => {
+                polyline.extend(flat.into_iter().skip(1));
+            }
+            _ => polylines.push(flat),
+        }
+    }
+    polylines
+}
+
+/// How `to_gcode` writes its program.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GcodeSettings {
+    /// Speed of the drawing `G1` moves, in units per minute.
+    pub feed_rate: f32,
+    /// Speed of the `G0` travel moves between strokes, or the machine's
+    /// rapid rate when `None`.
+    pub travel_rate: Option<f32>,
+    /// Machine units per world unit.
+    pub scale: f32,
+    /// Lines sent before each stroke and after it to lower and lift the pen,
+    /// e.g. `M3 S30` and `M5` for a servo pen, or `G1 Z-1` and `G0 Z5`.
+    pub pen_down: Vec<String>,
+    pub pen_up: Vec<String>,
+    /// Digits after the decimal point in coordinates.
+    pub precision: usize,
+}
+
+impl Default for GcodeSettings {
+    fn default() -> Self {
+        Self {
+            feed_rate: 1000.0,
+            travel_rate: None,
+            scale: 1.0,
+            pen_down: vec!["M3".to_string()],
+            pen_up: vec!["M5".to_string()],
+            precision: 3,
+        }
+    }
+}
+
+/// A GCode program drawing `polylines`, e.g. from `export_polyline`: a
+/// `G0` travel to the start of each stroke with the pen up, then `G1`
+/// moves along it with the pen down. Absolute millimetres (`G21`, `G90`);
+/// the pen ends up lifted.
+pub fn to_gcode(polylines: &[Vec<Vec2>], settings: &GcodeSettings) -> String {
+    let precision = settings.precision;
+    let position = |point: Vec2| {
+        let point = point * settings.scale;
+        format!("X{:.precision$} Y{:.precision$}", point.x, point.y)
+    };
+    let mut program = String::new();
+    let mut line = |text: &str| {
+        program.push_str(text);
+        program.push('\n');
+    };
+    line("G21");
+    line("G90");
+    settings.pen_up.iter().for_each(|command| line(command));
+    for polyline in polylines {
+        let Some((&first, rest)) = polyline.split_first() else {
+            continue;
+        };
+        let mut travel = format!("G0 {}", position(first));
+        if let Some(rate) = settings.travel_rate {
+            let _ = write!(travel, " F{}", rate);
+        }
+        line(&travel);
+        settings.pen_down.iter().for_each(|command| line(command));
+        for (i, &point) in rest.iter().enumerate() {
+            if i == 0 {
+                line(&format!("G1 {} F{}", position(point), settings.feed_rate));
+            } else {
+                line(&format!("G1 {}", position(point)));
+            }
+        }
+        settings.pen_up.iter().for_each(|command| line(command));
+    }
+    program
+}
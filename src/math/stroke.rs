@@ -0,0 +1,233 @@
+use super::*;
+
+/// Shape of the ends of an outlined stroke.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StrokeCap {
+    /// Cut off square at the end point.
+    #[default]
+    Butt,
+    /// A half circle around the end point.
+    Round,
+    /// Cut off square half the width past the end point.
+    Square,
+}
+
+/// Shape of the outer side of a corner in an outlined stroke.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StrokeJoin {
+    /// The sides meet in a point, unless it would stick out more than
+    /// `limit` times half the width, where the corner is beveled instead.
+    Miter(f32),
+    Round,
+    Bevel,
+}
+
+impl Default for StrokeJoin {
+    fn default() -> Self {
+        StrokeJoin::Miter(4.0)
+    }
+}
+
+const CUSP_TOLERANCE: f32 = 1e-3;
+
+/// The outline of `curve` stroked `width` wide, as a closed path of cubics:
+/// the left side, the end cap, the right side backwards and the start cap.
+/// Corners where the curve stalls and turns back (cusps) are joined with
+/// `join`. Fill it with the non-zero rule, as its sides cross on tight
+/// bends.
+pub fn outline_stroke(
+    curve: &CubicBezier,
+    width: f32,
+    cap: StrokeCap,
+    join: StrokeJoin,
+) -> ClosedPath {
+    let half = width * 0.5;
+    if half <= 0.0 {
+        return ClosedPath::new(Vec::new());
+    }
+    let mut pieces = Vec::new();
+    let mut from = 0.0;
+    for t in cusp_parameters(curve).into_iter().chain([1.0]) {
+        pieces.push(curve.trim(from, t));
+        from = t;
+    }
+    let left = stroke_side(&pieces, half, join);
+    let right: Vec<CubicBezier> = stroke_side(&pieces, -half, join)
+        .into_iter()
+        .rev()
+        .map(|piece| CubicBezier::new(piece.d, piece.c, piece.b, piece.a))
+        .collect();
+    let (
+        Some(first),
+        Some(last),
+        Some(left_first),
+        Some(left_last),
+        Some(right_first),
+        Some(right_last),
+    ) = (
+        pieces.first(),
+        pieces.last(),
+        left.first(),
+        left.last(),
+        right.first(),
+        right.last(),
+    )
+    else {
+        return ClosedPath::new(Vec::new());
+    };
+    let end_cap = stroke_cap(
+        last.d,
+        direction_at(last, 1.0),
+        left_last.d,
+        right_first.a,
+        half,
+        cap,
+    );
+    let start_cap = stroke_cap(
+        first.a,
+        -direction_at(first, 0.0),
+        right_last.d,
+        left_first.a,
+        half,
+        cap,
+    );
+    let mut segments = left;
+    segments.extend(end_cap);
+    segments.extend(right);
+    segments.extend(start_cap);
+    ClosedPath::new(segments)
+}
+
+/// Parameters strictly inside `curve` where its velocity vanishes, i.e.
+/// where it can turn back on itself.
+fn cusp_parameters(curve: &CubicBezier) -> Vec<f32> {
+    // B'(t) / 3 = (p - 2q + r) t² + 2 (q - p) t + p
+    let (p, q, r) = (curve.b - curve.a, curve.c - curve.b, curve.d - curve.c);
+    let (u, v) = (p - 2.0 * q + r, 2.0 * (q - p));
+    let scale = p.length() + q.length() + r.length();
+    let mut cusps: Vec<f32> = solve_quadratic(u.x, v.x, p.x)
+        .into_iter()
+        .chain(solve_quadratic(u.y, v.y, p.y))
+        .filter(|&t| t > CUSP_TOLERANCE && t < 1.0 - CUSP_TOLERANCE)
+        .filter(|&t| (u * t * t + v * t + p).length() <= CUSP_TOLERANCE * scale)
+        .collect();
+    cusps.sort_by(f32::total_cmp);
+    cusps.dedup_by(|t, previous| *t - *previous <= CUSP_TOLERANCE);
+    cusps
+}
+
+/// Unit direction of travel at `t`, see `offset_normal`.
+fn direction_at(curve: &CubicBezier, t: f32) -> Vec2 {
+    -offset_normal(curve.a, curve.b, curve.c, curve.d, t).perp()
+}
+
+/// Straight cubic from `from` to `to`.
+fn line_segment(from: Vec2, to: Vec2) -> CubicBezier {
+    CubicBezier::new(from, from.lerp(to, 1.0 / 3.0), from.lerp(to, 2.0 / 3.0), to)
+}
+
+/// One side of the stroke, `distance` to the left of `pieces` (right when
+/// negative), with joins where consecutive pieces meet at an angle.
+fn stroke_side(pieces: &[CubicBezier], distance: f32, join: StrokeJoin) -> Vec<CubicBezier> {
+    let mut side: Vec<CubicBezier> = Vec::new();
+    let mut previous: Option<&CubicBezier> = None;
+    for piece in pieces {
+        let offset = piece.offset(distance);
+        if let (Some(before), Some(last), Some(next)) = (previous, side.last(), offset.first()) {
+            let corner = stroke_join(
+                piece.a,
+                direction_at(before, 1.0),
+                direction_at(piece, 0.0),
+                last.d,
+                next.a,
+                distance,
+                join,
+            );
+            side.extend(corner);
+        }
+        side.extend(offset);
+        previous = Some(piece);
+    }
+    side
+}
+
+/// Segments from `from` to `to` around `corner`, where the stroke turns
+/// from `incoming` to `outgoing` on the side `distance` to the left.
+fn stroke_join(
+    corner: Vec2,
+    incoming: Vec2,
+    outgoing: Vec2,
+    from: Vec2,
+    to: Vec2,
+    distance: f32,
+    join: StrokeJoin,
+) -> Vec<CubicBezier> {
+    if from.distance(to) <= CUSP_TOLERANCE {
+        return Vec::new();
+    }
+    // on the inside of the turn the sides overlap; going through the corner
+    // keeps the overlap filled under the non-zero rule
+    if incoming.perp_dot(outgoing) * distance > 0.0 {
+        return vec![line_segment(from, corner), line_segment(corner, to)];
+    }
+    let radius = distance.abs();
+    match join {
+        StrokeJoin::Bevel => vec![line_segment(from, to)],
+        StrokeJoin::Round => {
+            let sweep = (from - corner).angle_to(to - corner);
+            arc_between(corner, radius, from, to, sweep)
+        }
+        StrokeJoin::Miter(limit) => {
+            let bisector = ((from - corner) + (to - corner)).normalize_or_zero();
+            let cos_half = bisector.dot((from - corner) / radius);
+            if cos_half <= 1.0 / limit.max(1.0) {
+                return vec![line_segment(from, to)];
+            }
+            let tip = corner + bisector * radius / cos_half;
+            vec![line_segment(from, tip), line_segment(tip, to)]
+        }
+    }
+}
+
+/// Segments from `from` to `to` around the end point `center` of a stroke
+/// `half` wide whose end faces `outward`.
+fn stroke_cap(
+    center: Vec2,
+    outward: Vec2,
+    from: Vec2,
+    to: Vec2,
+    half: f32,
+    cap: StrokeCap,
+) -> Vec<CubicBezier> {
+    match cap {
+        StrokeCap::Butt => vec![line_segment(from, to)],
+        StrokeCap::Square => {
+            let (from_out, to_out) = (from + outward * half, to + outward * half);
+            vec![
+                line_segment(from, from_out),
+                line_segment(from_out, to_out),
+                line_segment(to_out, to),
+            ]
+        }
+        // clockwise from the left side round to the right one
+        StrokeCap::Round => arc_between(center, half, from, to, -std::f32::consts::PI),
+    }
+}
+
+/// Arc of `radius` around `center` turning `sweep` radians from `from`,
+/// snapped onto `from` and `to` so the outline stays closed.
+fn arc_between(center: Vec2, radius: f32, from: Vec2, to: Vec2, sweep: f32) -> Vec<CubicBezier> {
+    let start_angle = (from - center).to_angle();
+    let mut arc: Vec<CubicBezier> =
+        arc_to_beziers(center, radius, start_angle, start_angle + sweep)
+            .into_iter()
+            .map(|(a, b, c, d)| CubicBezier::new(a, b, c, d))
+            .collect();
+    if let Some(first) = arc.first_mut() {
+        first.a = from;
+    }
+    if let Some(last) = arc.last_mut() {
+        last.d = to;
+    }
+    arc
+}
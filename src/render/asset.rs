@@ -0,0 +1,135 @@
+use super::*;
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+
+/// Curves loaded from a `.bez` file: RON in the format `save_curves`
+/// writes, a list of `(id, a, b, c, d)` records. The other fields it
+/// writes, such as `closed` and labels, are skipped.
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct BezierAsset {
+    pub curves: Vec<BezierCurve>,
+}
+
+/// Why a `.bez` file couldn't be loaded.
+#[derive(Debug)]
+pub enum BezierAssetError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl std::fmt::Display for BezierAssetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BezierAssetError::Io(error) => write!(f, "couldn't read the curve file: {}", error),
+            BezierAssetError::Ron(error) => write!(f, "couldn't parse the curve file: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for BezierAssetError {}
+
+/// Loads `.bez` files as `BezierAsset`s.
+#[derive(Default)]
+pub struct BezierAssetLoader;
+
+impl AssetLoader for BezierAssetLoader {
+    type Asset = BezierAsset;
+    type Settings = ();
+    type Error = BezierAssetError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<BezierAsset, BezierAssetError> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(BezierAssetError::Io)?;
+        let curves = ron::de::from_bytes(&bytes).map_err(BezierAssetError::Ron)?;
+        Ok(BezierAsset { curves })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["bez"]
+    }
+}
+
+/// Spawns the curves of a `BezierAsset` once it loads, under the ids in
+/// the file, and keeps them in step with it: when the asset changes, e.g.
+/// hot-reloaded with Bevy's `file_watcher` feature, curves still in it are
+/// updated in place, new ones spawned and dropped ones despawned.
+#[derive(Component, Clone, Debug)]
+pub struct BezierAssetInstance {
+    pub handle: Handle<BezierAsset>,
+    /// Ids of the curves spawned from the asset so far.
+    pub spawned: Vec<usize>,
+}
+
+impl BezierAssetInstance {
+    pub fn new(handle: Handle<BezierAsset>) -> Self {
+        Self {
+            handle,
+            spawned: Vec::new(),
+        }
+    }
+}
+
+/// Spawns and updates the curves of `BezierAssetInstance`s whose asset
+/// loaded or changed.
+pub fn sync_bezier_assets(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<BezierAsset>>,
+    assets: Res<Assets<BezierAsset>>,
+    mut instances: Query<&mut BezierAssetInstance>,
+    mut query: Query<(Entity, &mut Shape, &mut ShapeType)>,
+    mut modified: EventWriter<BezierModified>,
+    style: Res<BezierStyle>,
+) {
+    let changed: HashSet<AssetId<BezierAsset>> = events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    for mut instance in instances.iter_mut() {
+        let fresh = instance.is_added() && instance.spawned.is_empty();
+        if !fresh && !changed.contains(&instance.handle.id()) {
+            continue;
+        }
+        let Some(asset) = assets.get(&instance.handle) else {
+            continue;
+        };
+        let existing = collect_bezier_points(query.iter().map(|(_, _, shape_type)| shape_type));
+        for curve in &asset.curves {
+            let (id, points) = (curve.id, curve.points());
+            match existing.get(&id) {
+                Some(&current) if current == points => {}
+                Some(_) => {
+                    update_bezier_in_place(&style, &mut query, id, points);
+                    modified.write(BezierModified { id, points });
+                }
+                None => {
+                    let [a, b, c, d] = points;
+                    spawn_bezier_with_id(
+                        &mut commands,
+                        &style,
+                        id,
+                        bezier_open(&style, id, a, b, c, d),
+                    );
+                }
+            }
+        }
+        let kept: Vec<usize> = asset.curves.iter().map(|curve| curve.id).collect();
+        for &id in instance.spawned.iter().filter(|id| !kept.contains(id)) {
+            despawn_bezier(&mut commands, id);
+        }
+        if let Some(&max_id) = kept.iter().max() {
+            reserve_ids_through(&mut commands, max_id);
+        }
+        instance.spawned = kept;
+    }
+}
@@ -0,0 +1,83 @@
+use super::*;
+
+/// Put on a curve's `Start` or `End` entity to keep that anchor at the
+/// `GlobalTransform` of another entity, e.g. the port of a node in a node
+/// graph. The anchor's control point moves along so the wire keeps its
+/// shape where it leaves the port. Anchors of `BezierSpace::Ui` curves can
+/// follow UI nodes.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component)]
+pub struct AnchorAttachedTo(pub Entity);
+
+/// Attaches the `part` anchor (`Start` or `End`) of curve `id` to `target`.
+pub fn attach_bezier_anchor(
+    commands: &mut Commands,
+    id: usize,
+    part: BezierShapeType,
+    target: Entity,
+) {
+    commands.queue(move |world: &mut World| {
+        let mut query = world.query::<(Entity, &ShapeType)>();
+        let anchor = query
+            .iter(world)
+            .find_map(|(entity, shape_type)| match shape_type {
+                ShapeType::Bezier(bezier_shape)
+                    if bezier_shape.id == id && bezier_shape.shape_type == part =>
+                {
+                    Some(entity)
+                }
+                _ => None,
+            });
+        if let Some(anchor) = anchor {
+            world.entity_mut(anchor).insert(AnchorAttachedTo(target));
+        }
+    });
+}
+
+/// Moves attached anchors to their targets and redraws the curves.
+pub fn follow_attached_anchors(
+    attached: Query<(Entity, &AnchorAttachedTo)>,
+    targets: Query<(&GlobalTransform, Option<&ComputedNode>)>,
+    spaces: Query<&BezierSpace>,
+    mut query: Query<(Entity, &mut Shape, &mut ShapeType)>,
+    mut document: Option<ResMut<BezierDocument>>,
+    mut modified: EventWriter<BezierModified>,
+    style: Res<BezierStyle>,
+) {
+    if attached.is_empty() {
+        return;
+    }
+    let mut curves = collect_bezier_points(query.iter().map(|(_, _, shape_type)| shape_type));
+    let mut changed = BTreeMap::new();
+    for (entity, attached_to) in attached.iter() {
+        let Ok((target, node)) = targets.get(attached_to.0) else {
+            continue;
+        };
+        let space = spaces.get(entity).copied().unwrap_or_default();
+        let Ok((_, _, ShapeType::Bezier(bezier_shape))) = query.get(entity) else {
+            continue;
+        };
+        let Some(points) = curves.get_mut(&bezier_shape.id) else {
+            continue;
+        };
+        let (anchor, control) = match bezier_shape.shape_type {
+            BezierShapeType::Start => (0, 1),
+            BezierShapeType::End => (3, 2),
+            _ => continue,
+        };
+        let delta = position_in_space(space, target, node) - points[anchor];
+        if delta == Vec2::ZERO {
+            continue;
+        }
+        points[anchor] += delta;
+        points[control] += delta;
+        changed.insert(bezier_shape.id, *points);
+    }
+    for (id, points) in changed {
+        update_bezier_in_place(&style, &mut query, id, points);
+        modified.write(BezierModified { id, points });
+        if let Some(document) = document.as_mut() {
+            document.write_back(id, points);
+        }
+    }
+}
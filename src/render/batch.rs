@@ -0,0 +1,71 @@
+use super::*;
+
+/// The shapes of a cubic curve drawn with `BezierStyle::batch_handles`:
+/// `curve`, one entity for the guide lines, one `Handles` entity for the
+/// four points, then the hit strokes, caps, comb and move grip as `bezier_shapes`
+/// builds them. The `Handles` entity is picked as a whole and
+/// `BezierShape::part_at` finds which point was grabbed.
+pub fn batched_bezier_shapes(
+    style: &BezierStyle,
+    id: usize,
+    points: [Vec2; 4],
+    closed: bool,
+    curve: Shape,
+) -> Vec<(Shape, ShapeType)> {
+    let radius = style.intersection_radius - 1.0;
+    let part = |shape_type: BezierShapeType, handles: Option<[Vec2; 4]>| {
+        ShapeType::Bezier(BezierShape {
+            shape_type,
+            id,
+            point: None,
+            closed,
+            index: 0,
+            handles,
+        })
+    };
+    let mut shapes = vec![(curve, part(BezierShapeType::BezierLine, None))];
+
+    shapes.push((
+        ShapeBuilder::with(&dashed_path(&points, style.sketch_dash.as_deref()))
+            .stroke((style.sketch_color, style.sketch_stroke_width))
+            .build(),
+        part(BezierShapeType::Line, None),
+    ));
+    let handles = points
+        .iter()
+        .fold(ShapeBuilder::new(), |builder, &center| {
+            builder.add(&shapes::Circle { radius, center })
+        })
+        .fill(style.intersection_color)
+        .build();
+    shapes.push((handles, part(BezierShapeType::Handles, Some(points))));
+
+    let hit_width = 2.0 * style.pick_tolerance;
+    if hit_width > 0.0 {
+        let [a, b, c, d] = points;
+        let hit_path = ShapePath::new().move_to(a).cubic_bezier_to(b, c, d);
+        shapes.push((
+            ShapeBuilder::with(&hit_path)
+                .stroke((Color::NONE, style.bezier_stroke_width + hit_width))
+                .build(),
+            part(BezierShapeType::BezierLine, None),
+        ));
+        shapes.push((
+            ShapeBuilder::with(&dashed_path(&points, None))
+                .stroke((Color::NONE, style.sketch_stroke_width + hit_width))
+                .build(),
+            part(BezierShapeType::Line, None),
+        ));
+    }
+
+    shapes.extend(cap_shapes(style, id, closed, points));
+    shapes.push(comb_shape(style, id, closed, points));
+    shapes.push(move_grip_shape(style, id, closed, points));
+
+    for (index, (_shape, shape_type)) in shapes.iter_mut().enumerate() {
+        if let ShapeType::Bezier(bezier_shape) = shape_type {
+            bezier_shape.index = index;
+        }
+    }
+    shapes
+}
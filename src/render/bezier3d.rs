@@ -0,0 +1,161 @@
+use super::*;
+use bevy::window::PrimaryWindow;
+
+/// A 3D curve edited in place, e.g. a camera path. Drawn with gizmos; its
+/// anchors and controls are dragged across a `Bezier3dWorkPlane` under the
+/// pointer. Points are in world space.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct Bezier3d {
+    #[reflect(ignore)]
+    pub curve: CubicBezier3,
+}
+
+/// Plane a 3D point is dragged across, through the point's position when
+/// the drag starts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+pub enum Bezier3dWorkPlane {
+    /// Facing the camera, so the point follows the pointer on screen.
+    #[default]
+    Screen,
+    /// A fixed plane, e.g. `Dir3::Y` to drag along the ground.
+    Normal(Dir3),
+}
+
+/// Look and feel of `Bezier3d` editing.
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct Bezier3dSettings {
+    pub work_plane: Bezier3dWorkPlane,
+    /// How close to a handle on screen, in logical pixels, a press grabs it.
+    pub pick_radius: f32,
+    /// Radius of the drawn handles in world units.
+    pub handle_radius: f32,
+    pub curve_color: Color,
+    pub handle_color: Color,
+    pub guide_color: Color,
+}
+
+impl Default for Bezier3dSettings {
+    fn default() -> Self {
+        Self {
+            work_plane: Bezier3dWorkPlane::Screen,
+            pick_radius: 10.0,
+            handle_radius: 0.05,
+            curve_color: Color::srgba_u8(200, 172, 110, 255),
+            handle_color: Color::srgba(1.0, 0.0, 0.0, 1.0),
+            guide_color: Color::srgba(0.5, 0.5, 0.5, 1.0),
+        }
+    }
+}
+
+/// The 3D point being dragged: which curve, which of its four points, and
+/// the plane it moves across.
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct Bezier3dDrag {
+    dragging: Option<(Entity, usize, Vec3, Dir3)>,
+}
+
+/// Draws every `Bezier3d` with its guide lines and handles.
+pub fn draw_bezier3d_gizmos(
+    mut gizmos: Gizmos,
+    curves: Query<&Bezier3d>,
+    settings: Res<Bezier3dSettings>,
+    mode: Res<BezierEditMode>,
+) {
+    for bezier in curves.iter() {
+        let curve = bezier.curve;
+        gizmos.linestrip(curve.flatten(0.01), settings.curve_color);
+        if *mode == BezierEditMode::Display {
+            continue;
+        }
+        gizmos.line(curve.a, curve.b, settings.guide_color);
+        gizmos.line(curve.c, curve.d, settings.guide_color);
+        for point in curve.points() {
+            gizmos.sphere(
+                Isometry3d::from_translation(point),
+                settings.handle_radius,
+                settings.handle_color,
+            );
+        }
+    }
+}
+
+/// Picks the `Bezier3d` point nearest the pointer on screen when the left
+/// button is pressed, and moves it across its work plane while held.
+#[allow(clippy::too_many_arguments)]
+pub fn drag_bezier3d_points(
+    mut curves: Query<(Entity, &mut Bezier3d)>,
+    mut drag: ResMut<Bezier3dDrag>,
+    settings: Res<Bezier3dSettings>,
+    mode: Res<BezierEditMode>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+) {
+    if mouse.just_released(MouseButton::Left) || *mode == BezierEditMode::Display {
+        drag.dragging = None;
+        return;
+    }
+    let Some(cursor) = windows.single().ok().and_then(Window::cursor_position) else {
+        return;
+    };
+    let Some((camera, camera_transform)) = cameras.iter().find(|(camera, _)| camera.is_active)
+    else {
+        return;
+    };
+
+    if mouse.just_pressed(MouseButton::Left) {
+        let mut nearest: Option<(Entity, usize, Vec3, f32)> = None;
+        for (entity, bezier) in curves.iter() {
+            for (index, point) in bezier.curve.points().into_iter().enumerate() {
+                let Ok(on_screen) = camera.world_to_viewport(camera_transform, point) else {
+                    continue;
+                };
+                let distance = on_screen.distance(cursor);
+                if distance <= settings.pick_radius
+                    && nearest.is_none_or(|(_, _, _, best)| distance < best)
+                {
+                    nearest = Some((entity, index, point, distance));
+                }
+            }
+        }
+        drag.dragging = nearest.map(|(entity, index, point, _)| {
+            let normal = match settings.work_plane {
+                Bezier3dWorkPlane::Screen => camera_transform.back(),
+                Bezier3dWorkPlane::Normal(normal) => normal,
+            };
+            (entity, index, point, normal)
+        });
+    }
+
+    let Some((entity, index, origin, normal)) = drag.dragging else {
+        return;
+    };
+    let Ok(ray) = camera.viewport_to_world(camera_transform, cursor) else {
+        return;
+    };
+    let Some(distance) = ray.intersect_plane(origin, InfinitePlane3d::new(normal)) else {
+        return;
+    };
+    let Ok((_, mut bezier)) = curves.get_mut(entity) else {
+        drag.dragging = None;
+        return;
+    };
+    let target = ray.get_point(distance);
+    let curve = &mut bezier.curve;
+    let point = match index {
+        0 => &mut curve.a,
+        1 => &mut curve.b,
+        2 => &mut curve.c,
+        _ => &mut curve.d,
+    };
+    let delta = target - *point;
+    *point = target;
+    // anchors carry their control along, as in 2D
+    match index {
+        0 => curve.b += delta,
+        3 => curve.c += delta,
+        _ => {}
+    }
+}
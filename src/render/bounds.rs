@@ -0,0 +1,38 @@
+use super::*;
+
+/// Keeps the points of the curve on whose `BezierId` root it sits inside a
+/// region while they are dragged, e.g. the visible canvas or a node's port
+/// area. A point that would leave is stopped at the edge of the rect; a
+/// whole-curve drag stops when its first point reaches it. Only pointer
+/// drags are held, edits through `BezierCommands` are not.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct BezierBounds(pub Rect);
+
+impl BezierDragState {
+    /// `delta` cut short so the dragged part, moved from where it was when
+    /// the drag started, stays inside `bounds`.
+    pub fn bounded_delta(&self, delta: Vec2) -> Vec2 {
+        let (Some(bounds), Some([a, b, c, d])) = (self.bounds, self.start_points) else {
+            return delta;
+        };
+        let moving = match self.dragging {
+            BezierShapeType::Start => vec![a],
+            BezierShapeType::ControlStart => vec![b],
+            BezierShapeType::ControlEnd => vec![c],
+            BezierShapeType::End => vec![d],
+            BezierShapeType::Control => vec![reduce_to_quadratic(a, b, c, d)],
+            BezierShapeType::BezierLine if self.quadratic => {
+                vec![a, reduce_to_quadratic(a, b, c, d), d]
+            }
+            BezierShapeType::BezierLine => vec![a, b, c, d],
+            BezierShapeType::Line | BezierShapeType::Handles | BezierShapeType::MoveGrip => {
+                return delta;
+            }
+        };
+        let min = moving.iter().fold(Vec2::MAX, |min, point| min.min(*point));
+        let max = moving.iter().fold(Vec2::MIN, |max, point| max.max(*point));
+        // not `clamp`, which panics on a curve wider than the bounds
+        delta.max(bounds.min - min).min(bounds.max - max)
+    }
+}
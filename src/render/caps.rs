@@ -0,0 +1,78 @@
+use super::*;
+
+/// Decoration drawn at an end of an open curve, pointing along the curve.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum BezierCap {
+    #[default]
+    None,
+    Arrow,
+    Circle,
+    Square,
+}
+
+/// The start and end caps of curve `id`. Both shapes are always built, empty
+/// for `BezierCap::None` and on closed curves, so changing caps in the style
+/// can restyle curves in place.
+pub fn cap_shapes(
+    style: &BezierStyle,
+    id: usize,
+    closed: bool,
+    points: [Vec2; 4],
+) -> [(Shape, ShapeType); 2] {
+    let [a, b, c, d] = points;
+    let direction = |from: [Vec2; 3], to: Vec2| {
+        from.iter()
+            .map(|&p| (to - p).normalize_or_zero())
+            .find(|direction| *direction != Vec2::ZERO)
+            .unwrap_or(Vec2::X)
+    };
+    // outward directions at each end, skipping controls sitting on anchors
+    let start = (style.start_cap, a, direction([b, c, d], a));
+    let end = (style.end_cap, d, direction([c, b, a], d));
+    [start, end].map(|(cap, tip, outward)| {
+        let cap = if closed { BezierCap::None } else { cap };
+        (
+            cap_shape(style, cap, tip, outward),
+            ShapeType::Bezier(BezierShape {
+                shape_type: BezierShapeType::BezierLine,
+                id,
+                point: None,
+                closed,
+                index: 0,
+                handles: None,
+            }),
+        )
+    })
+}
+
+fn cap_shape(style: &BezierStyle, cap: BezierCap, tip: Vec2, outward: Vec2) -> Shape {
+    let size = style.cap_size;
+    let side = outward.perp();
+    let builder = match cap {
+        BezierCap::None => ShapeBuilder::with(&ShapePath::new()),
+        BezierCap::Arrow => {
+            let base = tip - outward * size;
+            ShapeBuilder::with(&shapes::Polygon {
+                points: vec![tip, base + side * size * 0.5, base - side * size * 0.5],
+                closed: true,
+            })
+        }
+        BezierCap::Circle => ShapeBuilder::with(&shapes::Circle {
+            radius: size * 0.5,
+            center: tip,
+        }),
+        BezierCap::Square => {
+            let (along, across) = (outward * size * 0.5, side * size * 0.5);
+            ShapeBuilder::with(&shapes::Polygon {
+                points: vec![
+                    tip + along + across,
+                    tip - along + across,
+                    tip - along - across,
+                    tip + along - across,
+                ],
+                closed: true,
+            })
+        }
+    };
+    builder.fill(style.cap_color).build()
+}
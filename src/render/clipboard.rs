@@ -0,0 +1,62 @@
+use super::*;
+
+/// Puts the curves `ids` on the system clipboard in the `export_beziers`
+/// JSON format, so another app instance, or a text editor, can take them.
+pub fn copy_beziers_to_clipboard(
+    query: &Query<(Entity, &ShapeType)>,
+    labels: &Query<&BezierLabel>,
+    user_data: &Query<(&BezierId, &BezierUserData)>,
+    ids: &[usize],
+) -> Result<(), arboard::Error> {
+    let json = export_bezier_ids(query, labels, user_data, ids);
+    arboard::Clipboard::new()?.set_text(json)
+}
+
+/// Spawns the curves on the system clipboard under fresh ids, see
+/// `import_beziers_as_new`. Clipboard text that isn't a curve document is
+/// ignored, returning no ids.
+pub fn paste_beziers_from_clipboard(
+    commands: &mut Commands,
+    style: &BezierStyle,
+    ids: &mut BezierIdAllocator,
+) -> Result<Vec<usize>, arboard::Error> {
+    let text = arboard::Clipboard::new()?.get_text()?;
+    Ok(import_beziers_as_new(commands, style, ids, &text).unwrap_or_default())
+}
+
+/// Copies the selected curves on Ctrl+C and pastes curves on Ctrl+V,
+/// selecting the pasted ones. Follows `BezierEditSettings::keybindings`.
+#[allow(clippy::too_many_arguments)]
+pub fn bezier_clipboard_keys(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    settings: Res<BezierEditSettings>,
+    mut selection: ResMut<BezierSelection>,
+    mut ids: ResMut<BezierIdAllocator>,
+    query: Query<(Entity, &ShapeType)>,
+    labels: Query<&BezierLabel>,
+    user_data: Query<(&BezierId, &BezierUserData)>,
+    style: Res<BezierStyle>,
+) {
+    if !settings.keybindings || !keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        return;
+    }
+    if keys.just_pressed(KeyCode::KeyC) && !selection.is_empty() {
+        if let Err(error) =
+            copy_beziers_to_clipboard(&query, &labels, &user_data, &selection.curves())
+        {
+            warn!("couldn't copy beziers to the clipboard: {error}");
+        }
+    } else if keys.just_pressed(KeyCode::KeyV) {
+        match paste_beziers_from_clipboard(&mut commands, &style, &mut ids) {
+            Ok(pasted) if !pasted.is_empty() => {
+                selection.clear();
+                for id in pasted {
+                    selection.parts.push((id, BezierShapeType::BezierLine));
+                }
+            }
+            Ok(_) => {}
+            Err(error) => warn!("couldn't paste beziers from the clipboard: {error}"),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use super::*;
+
+/// A curvature comb: teeth along the curve whose length is proportional to
+/// the curvature there, joined at their tips. Bumps and flat spots in the
+/// tip outline show where a curve isn't fair.
+#[derive(Clone, Debug, Reflect)]
+pub struct CurvatureComb {
+    /// Number of teeth.
+    pub samples: usize,
+    /// World units of tooth length per unit of curvature.
+    pub scale: f32,
+    pub color: Color,
+    pub stroke_width: f32,
+}
+
+impl Default for CurvatureComb {
+    fn default() -> Self {
+        Self {
+            samples: 48,
+            scale: 2000.0,
+            color: Color::srgba(0.3, 0.7, 1.0, 0.8),
+            stroke_width: 1.0,
+        }
+    }
+}
+
+/// The comb of curve `id`. Always built, empty when
+/// `BezierStyle::curvature_comb` is `None`, so toggling it restyles curves in
+/// place. Teeth point away from the center of curvature.
+pub fn comb_shape(
+    style: &BezierStyle,
+    id: usize,
+    closed: bool,
+    points: [Vec2; 4],
+) -> (Shape, ShapeType) {
+    let shape_type = ShapeType::Bezier(BezierShape {
+        shape_type: BezierShapeType::Line,
+        id,
+        point: None,
+        closed,
+        index: 0,
+        handles: None,
+    });
+    let Some(comb) = &style.curvature_comb else {
+        let empty = ShapeBuilder::with(&ShapePath::new())
+            .stroke((Color::NONE, 1.0))
+            .build();
+        return (empty, shape_type);
+    };
+    let curve = CubicBezier::from(points);
+    let samples = comb.samples.max(2);
+    let mut path = ShapePath::new();
+    let mut tips = Vec::with_capacity(samples);
+    for i in 0..samples {
+        let t = i as f32 / (samples - 1) as f32;
+        let point = curve.point_at(t);
+        let normal = curve.tangent_at(t).perp();
+        let tip = point - normal * curve.curvature_at(t) * comb.scale;
+        path = path.move_to(point).line_to(tip);
+        tips.push(tip);
+    }
+    if let Some((first, rest)) = tips.split_first() {
+        path = path.move_to(*first);
+        for tip in rest {
+            path = path.line_to(*tip);
+        }
+    }
+    (
+        ShapeBuilder::with(&path)
+            .stroke((comb.color, comb.stroke_width))
+            .build(),
+        shape_type,
+    )
+}
@@ -0,0 +1,468 @@
+use super::*;
+
+/// An edit to the curves. The crate's own gestures push these onto
+/// `BezierCommands` rather than changing shapes directly, so every edit
+/// passes through `apply_bezier_commands`. Drags are the exception: they
+/// change the curve live and record their history on release.
+#[derive(Clone, Debug)]
+pub enum BezierCommand {
+    /// Moves `part` of curve `id` by `delta`, as a drag would: the curve
+    /// body moves the whole curve, and spline neighbours follow.
+    MovePoint {
+        id: usize,
+        part: BezierShapeType,
+        delta: Vec2,
+    },
+    /// Moves `part` of curve `id` to `position`, like `MovePoint` by the
+    /// difference. For the curve body, its `Start` anchor lands there.
+    SetPoint {
+        id: usize,
+        part: BezierShapeType,
+        position: Vec2,
+    },
+    /// Replaces the points of curve `id`.
+    SetPoints {
+        id: usize,
+        points: [Vec2; 4],
+    },
+    /// Reflects curve `id` in place across `axis`.
+    Mirror {
+        id: usize,
+        axis: BezierMirrorAxis,
+    },
+    /// Splits curve `id` at the point closest to `position`, see
+    /// `insert_bezier_anchor`.
+    InsertAnchor {
+        id: usize,
+        position: Vec2,
+    },
+    /// Removes anchor `index` of the spline holding curve `id`, see
+    /// `remove_anchor`.
+    RemoveAnchor {
+        id: usize,
+        index: usize,
+    },
+    /// Trims curve `id` at the intersections around `position`, see
+    /// `trim_bezier_at`.
+    Trim {
+        id: usize,
+        position: Vec2,
+    },
+    /// Spawns an open curve at `points` under `id`, moving the
+    /// `BezierIdAllocator` past it. Does nothing if curve `id` exists.
+    SpawnCurve {
+        id: usize,
+        points: [Vec2; 4],
+    },
+    DeleteCurve {
+        id: usize,
+    },
+    /// Spawns a copy of curve `id` moved by `offset`, see `duplicate_bezier`.
+    DuplicateCurve {
+        id: usize,
+        offset: Vec2,
+    },
+    /// Sets or, with `None`, clears the `BezierStyleOverride` of curve `id`.
+    SetStyle {
+        id: usize,
+        style: Option<BezierStyleOverride>,
+    },
+    /// Sets the `HandleMode` of joint `joint` of the spline holding curve
+    /// `id`, lining the joint's handles up unless it becomes `Free`.
+    SetJointMode {
+        id: usize,
+        joint: usize,
+        mode: HandleMode,
+    },
+    /// Relaxes the handles of the spline holding curve `id`, or of the
+    /// lone curve, towards Catmull-Rom tangents through its anchors, see
+    /// `smooth_spline`.
+    SmoothSpline {
+        id: usize,
+        strength: f32,
+    },
+}
+
+/// Edits waiting for `apply_bezier_commands`. Systems running before it
+/// can inspect the queue and veto entries with `retain`; a recorded
+/// `BezierCommandApplied` stream can be replayed by pushing it again.
+#[derive(Resource, Default, Debug)]
+pub struct BezierCommands {
+    queue: Vec<BezierCommand>,
+}
+
+impl BezierCommands {
+    pub fn push(&mut self, command: BezierCommand) {
+        self.queue.push(command);
+    }
+    /// Queues moving `part` of curve `id` to `position`, for property panels
+    /// and scripts; see `BezierCommand::SetPoint`.
+    pub fn set_point(&mut self, id: usize, part: BezierShapeType, position: Vec2) {
+        self.push(BezierCommand::SetPoint { id, part, position });
+    }
+    /// Queues flipping curve `id` across `axis`; see `BezierCommand::Mirror`.
+    pub fn mirror_bezier(&mut self, id: usize, axis: BezierMirrorAxis) {
+        self.push(BezierCommand::Mirror { id, axis });
+    }
+    /// Queues smoothing the spline holding curve `id` by `strength` in
+    /// `[0, 1]`; see `BezierCommand::SmoothSpline`.
+    pub fn smooth_spline(&mut self, id: usize, strength: f32) {
+        self.push(BezierCommand::SmoothSpline { id, strength });
+    }
+    /// Commands waiting to be applied, oldest first.
+    pub fn pending(&self) -> &[BezierCommand] {
+        &self.queue
+    }
+    /// Keeps only the pending commands `keep` returns true for.
+    pub fn retain(&mut self, keep: impl FnMut(&BezierCommand) -> bool) {
+        self.queue.retain(keep);
+    }
+    pub fn clear(&mut self) {
+        self.queue.clear();
+    }
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+}
+
+/// `command` was applied by `apply_bezier_commands`.
+#[derive(Event, Clone, Debug)]
+pub struct BezierCommandApplied(pub BezierCommand);
+
+/// Applies the pending `BezierCommands` in order, recording each in
+/// `BezierHistory` as one step and writing point edits back to the
+/// `BezierDocument`. Commands that spawn, remove, split or restyle curves
+/// are recorded as the `BezierStructureEdit` between the scene before and
+/// after them.
+#[allow(clippy::too_many_arguments)]
+pub fn apply_bezier_commands(
+    mut commands: Commands,
+    mut queue: ResMut<BezierCommands>,
+    mut query: Query<(Entity, &mut Shape, &mut ShapeType)>,
+    mut splines: Query<&mut BezierSpline>,
+    roots: Query<(Entity, &BezierId, Option<&BezierStyleOverride>)>,
+    welds: Query<(Entity, &WeldedTo)>,
+    mut ids: ResMut<BezierIdAllocator>,
+    mut document: Option<ResMut<BezierDocument>>,
+    mut modified: EventWriter<BezierModified>,
+    mut applied: EventWriter<BezierCommandApplied>,
+    style: Res<BezierStyle>,
+) {
+    if queue.is_empty() {
+        return;
+    }
+    for command in std::mem::take(&mut queue.queue) {
+        let structural = matches!(
+            command,
+            BezierCommand::InsertAnchor { .. }
+                | BezierCommand::RemoveAnchor { .. }
+                | BezierCommand::Trim { .. }
+                | BezierCommand::SpawnCurve { .. }
+                | BezierCommand::DeleteCurve { .. }
+                | BezierCommand::DuplicateCurve { .. }
+                | BezierCommand::SetStyle { .. }
+                | BezierCommand::SetJointMode { .. }
+        );
+        let scene_before = structural.then(|| {
+            BezierScene::capture(
+                query.iter().map(|(_, _, s)| s),
+                roots.iter().map(|(_, root_id, style)| (root_id, style)),
+                splines.iter(),
+            )
+        });
+        let mut edits = Vec::new();
+        match &command {
+            BezierCommand::MovePoint { id, part, .. }
+            | BezierCommand::SetPoint { id, part, .. } => {
+                let Some(before) =
+                    collect_bezier_points(query.iter().map(|(_, _, s)| s)).remove(id)
+                else {
+                    continue;
+                };
+                let quadratic = query.iter().any(|(_, _, shape_type)| {
+                    matches!(shape_type, ShapeType::Bezier(bezier_shape)
+                        if bezier_shape.id == *id
+                            && matches!(bezier_shape.shape_type, BezierShapeType::Control))
+                });
+                let [a, b, c, d] = before;
+                let delta = match &command {
+                    BezierCommand::SetPoint { position, .. } => {
+                        let current = match part {
+                            BezierShapeType::Start | BezierShapeType::BezierLine => a,
+                            BezierShapeType::ControlStart => b,
+                            BezierShapeType::ControlEnd => c,
+                            BezierShapeType::End => d,
+                            BezierShapeType::Control => reduce_to_quadratic(a, b, c, d),
+                            BezierShapeType::Line
+                            | BezierShapeType::Handles
+                            | BezierShapeType::MoveGrip => continue,
+                        };
+                        *position - current
+                    }
+                    BezierCommand::MovePoint { delta, .. } => *delta,
+                    _ => continue,
+                };
+                let mut state = BezierDragState {
+                    bezier_id: *id,
+                    dragging: part.clone(),
+                    a: Some(a),
+                    b: Some(b),
+                    c: Some(c),
+                    d: Some(d),
+                    quadratic,
+                    ..default()
+                };
+                state.add_delta(delta);
+                let Some(after) = state.points() else {
+                    continue;
+                };
+                edits.push(set_points(
+                    &style,
+                    &mut query,
+                    &mut document,
+                    &mut modified,
+                    *id,
+                    before,
+                    after,
+                ));
+                edits.extend(follow_spline_neighbors(
+                    &style,
+                    &mut query,
+                    &splines.as_readonly(),
+                    &state,
+                    &mut modified,
+                ));
+                edits.extend(follow_welded_anchors(
+                    &style,
+                    &mut query,
+                    &welds,
+                    *id,
+                    &mut document,
+                    &mut modified,
+                ));
+            }
+            BezierCommand::SetPoints { id, .. } | BezierCommand::Mirror { id, .. } => {
+                let Some(before) =
+                    collect_bezier_points(query.iter().map(|(_, _, s)| s)).remove(id)
+                else {
+                    continue;
+                };
+                let points = match &command {
+                    BezierCommand::Mirror { axis, .. } => axis.mirror(before),
+                    BezierCommand::SetPoints { points, .. } => *points,
+                    _ => continue,
+                };
+                edits.push(set_points(
+                    &style,
+                    &mut query,
+                    &mut document,
+                    &mut modified,
+                    *id,
+                    before,
+                    points,
+                ));
+                let [a, b, c, d] = points;
+                let state = BezierDragState {
+                    bezier_id: *id,
+                    dragging: BezierShapeType::Handles,
+                    a: Some(a),
+                    b: Some(b),
+                    c: Some(c),
+                    d: Some(d),
+                    ..default()
+                };
+                edits.extend(follow_spline_neighbors(
+                    &style,
+                    &mut query,
+                    &splines.as_readonly(),
+                    &state,
+                    &mut modified,
+                ));
+                edits.extend(follow_welded_anchors(
+                    &style,
+                    &mut query,
+                    &welds,
+                    *id,
+                    &mut document,
+                    &mut modified,
+                ));
+            }
+            BezierCommand::InsertAnchor { id, position } => {
+                insert_bezier_anchor(
+                    &mut commands,
+                    &mut query,
+                    &mut splines,
+                    &mut ids,
+                    &style,
+                    *id,
+                    *position,
+                );
+            }
+            BezierCommand::RemoveAnchor { id, index } => {
+                remove_anchor(&mut commands, *id, *index);
+            }
+            BezierCommand::Trim { id, position } => {
+                trim_bezier_at(&mut commands, &mut query, &mut ids, &style, *id, *position);
+            }
+            BezierCommand::SpawnCurve { id, points } => {
+                if roots.iter().any(|(_, root_id, _)| root_id.0 == *id) {
+                    continue;
+                }
+                ids.set_next_id(*id + 1);
+                let [a, b, c, d] = *points;
+                spawn_bezier_with_id(
+                    &mut commands,
+                    &style,
+                    *id,
+                    bezier_open(&style, *id, a, b, c, d),
+                );
+            }
+            BezierCommand::DeleteCurve { id } => {
+                despawn_bezier(&mut commands, *id);
+            }
+            BezierCommand::DuplicateCurve { id, offset } => {
+                duplicate_bezier(&mut commands, *id, *offset);
+            }
+            BezierCommand::SetStyle {
+                id,
+                style: style_override,
+            } => {
+                let Some((root, _, _)) = roots.iter().find(|(_, root_id, _)| root_id.0 == *id)
+                else {
+                    continue;
+                };
+                match style_override {
+                    Some(style_override) => {
+                        commands.entity(root).insert(style_override.clone());
+                    }
+                    None => {
+                        commands.entity(root).remove::<BezierStyleOverride>();
+                    }
+                }
+            }
+            BezierCommand::SetJointMode { id, joint, mode } => {
+                let Some(mut spline) = splines
+                    .iter_mut()
+                    .find(|spline| spline.segments.contains(id))
+                else {
+                    continue;
+                };
+                let (Some(&first), Some(second)) = (
+                    spline.segments.get(*joint),
+                    spline
+                        .next_index(*joint)
+                        .map(|index| spline.segments[index]),
+                ) else {
+                    continue;
+                };
+                spline.set_handle_mode(*joint, *mode);
+                let curves = collect_bezier_points(query.iter().map(|(_, _, s)| s));
+                if let (true, Some(&before_first), Some(&before_second)) = (
+                    *mode != HandleMode::Free,
+                    curves.get(&first),
+                    curves.get(&second),
+                ) {
+                    let (incoming, outgoing) = smooth_joint(before_first, before_second, *mode);
+                    for (id, before, after) in [
+                        (first, before_first, incoming),
+                        (second, before_second, outgoing),
+                    ] {
+                        // part of the structure step, which has the points
+                        set_points(
+                            &style,
+                            &mut query,
+                            &mut document,
+                            &mut modified,
+                            id,
+                            before,
+                            after,
+                        );
+                    }
+                }
+            }
+            BezierCommand::SmoothSpline { id, strength } => {
+                let curves = collect_bezier_points(query.iter().map(|(_, _, s)| s));
+                let (segments, closed) =
+                    match splines.iter().find(|spline| spline.segments.contains(id)) {
+                        Some(spline) => (spline.segments.clone(), spline.closed),
+                        None => (vec![*id], false),
+                    };
+                let Some(before) = segments
+                    .iter()
+                    .map(|segment| curves.get(segment).copied())
+                    .collect::<Option<Vec<_>>>()
+                else {
+                    continue;
+                };
+                let cubics: Vec<CubicBezier> =
+                    before.iter().copied().map(CubicBezier::from).collect();
+                let smoothed = smooth_spline(&cubics, closed, *strength);
+                for ((id, before), after) in segments.into_iter().zip(before).zip(smoothed) {
+                    let after = after.points();
+                    if after != before {
+                        edits.push(set_points(
+                            &style,
+                            &mut query,
+                            &mut document,
+                            &mut modified,
+                            id,
+                            before,
+                            after,
+                        ));
+                    }
+                }
+            }
+        }
+        record_bezier_edits(&mut commands, edits);
+        if let Some(scene_before) = scene_before {
+            record_bezier_structure(&mut commands, scene_before);
+        }
+        applied.write(BezierCommandApplied(command));
+    }
+}
+
+/// `first` and `second`, joined at `first`'s `End`, with the handles either
+/// side of the joint lined up for `mode`: along the direction between them,
+/// keeping their lengths when `Aligned`, sharing their mean length when
+/// `Mirrored` or `Curvature`. The far handle `Curvature` moves is left to
+/// `enforce_spline_continuity`.
+fn smooth_joint(first: [Vec2; 4], second: [Vec2; 4], mode: HandleMode) -> ([Vec2; 4], [Vec2; 4]) {
+    let [a, b, c, anchor] = first;
+    let [_, next_b, next_c, next_d] = second;
+    let Some(direction) = (next_b - c)
+        .try_normalize()
+        .or_else(|| (next_d - a).try_normalize())
+    else {
+        return (first, second);
+    };
+    let (mut incoming, mut outgoing) = (c.distance(anchor), next_b.distance(anchor));
+    if matches!(mode, HandleMode::Mirrored | HandleMode::Curvature) {
+        incoming = (incoming + outgoing) * 0.5;
+        outgoing = incoming;
+    }
+    (
+        [a, b, anchor - direction * incoming, anchor],
+        [anchor, anchor + direction * outgoing, next_c, next_d],
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn set_points(
+    style: &BezierStyle,
+    query: &mut Query<(Entity, &mut Shape, &mut ShapeType)>,
+    document: &mut Option<ResMut<BezierDocument>>,
+    modified: &mut EventWriter<BezierModified>,
+    id: usize,
+    before: [Vec2; 4],
+    after: [Vec2; 4],
+) -> BezierEdit {
+    update_bezier_in_place(style, query, id, after);
+    modified.write(BezierModified { id, points: after });
+    if let Some(document) = document.as_mut() {
+        document.write_back(id, after);
+    }
+    BezierEdit { id, before, after }
+}
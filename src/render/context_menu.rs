@@ -0,0 +1,90 @@
+use super::*;
+use bevy::window::PrimaryWindow;
+
+/// What a `BezierContextMenuEvent` was opened over.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ContextTarget {
+    /// An anchor or control point of curve `id`.
+    Point { id: usize, part: BezierShapeType },
+    /// The body, a handle line or the move grip of curve `id`.
+    Curve { id: usize },
+    /// Empty space, no curve under the pointer.
+    Canvas,
+}
+
+impl ContextTarget {
+    /// The curve the menu was opened over, if any.
+    pub fn id(&self) -> Option<usize> {
+        match self {
+            ContextTarget::Point { id, .. } | ContextTarget::Curve { id } => Some(*id),
+            ContextTarget::Canvas => None,
+        }
+    }
+}
+
+/// `BezierInputMap::context_menu_button` was pressed over `target`, for the
+/// app to pop up its own menu, e.g. delete, duplicate or set style, at
+/// `screen_pos`.
+#[derive(Event, Clone, Debug)]
+pub struct BezierContextMenuEvent {
+    pub target: ContextTarget,
+    pub world_pos: Vec2,
+    /// Pointer position in the window, in logical pixels.
+    pub screen_pos: Vec2,
+}
+
+/// Sends a `BezierContextMenuEvent` when the context menu button is
+/// pressed, classifying the hovered curve parts: a point wins over the
+/// curve it is on, and nothing hovered is the canvas.
+pub fn bezier_context_menu(
+    mouse: Res<ButtonInput<MouseButton>>,
+    input: Res<BezierInputMap>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    hovered: Query<&ShapeType, With<BezierHovered>>,
+    mut events: EventWriter<BezierContextMenuEvent>,
+) {
+    let Some(button) = input.context_menu_button else {
+        return;
+    };
+    if !mouse.just_pressed(button) {
+        return;
+    }
+    let Some(cursor) = windows.single().ok().and_then(Window::cursor_position) else {
+        return;
+    };
+    let Some((camera, camera_transform)) = cameras.iter().find(|(camera, _)| camera.is_active)
+    else {
+        return;
+    };
+    let Ok(position) = camera.viewport_to_world_2d(camera_transform, cursor) else {
+        return;
+    };
+    let mut target = ContextTarget::Canvas;
+    for shape_type in hovered.iter() {
+        let ShapeType::Bezier(bezier_shape) = shape_type else {
+            continue;
+        };
+        let id = bezier_shape.id;
+        match bezier_shape.part_at(position) {
+            part @ (BezierShapeType::Start
+            | BezierShapeType::ControlStart
+            | BezierShapeType::ControlEnd
+            | BezierShapeType::End
+            | BezierShapeType::Control) => {
+                target = ContextTarget::Point { id, part };
+                break;
+            }
+            _ => {
+                if target == ContextTarget::Canvas {
+                    target = ContextTarget::Curve { id };
+                }
+            }
+        }
+    }
+    events.write(BezierContextMenuEvent {
+        target,
+        world_pos: position,
+        screen_pos: cursor,
+    });
+}
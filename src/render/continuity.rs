@@ -0,0 +1,206 @@
+use super::*;
+
+/// How spline joints keep the continuity their `HandleMode` asks for.
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct BezierContinuitySettings {
+    /// After each edit, move the handles of the segments next to the
+    /// edited ones to restore their joints' continuity.
+    pub enforce: bool,
+    /// Circle joints whose handles break their `HandleMode`, e.g. when both
+    /// sides were edited at once.
+    pub show_violations: bool,
+    /// How far, in world units, a handle may be off before the joint
+    /// counts as broken.
+    pub tolerance: f32,
+    pub color: Color,
+    /// Radius of the circle around a broken joint.
+    pub size: f32,
+}
+
+impl Default for BezierContinuitySettings {
+    fn default() -> Self {
+        Self {
+            enforce: true,
+            show_violations: true,
+            tolerance: 0.01,
+            color: Color::srgb(1.0, 0.2, 0.6),
+            size: 9.0,
+        }
+    }
+}
+
+fn reversed([a, b, c, d]: [Vec2; 4]) -> [Vec2; 4] {
+    [d, c, b, a]
+}
+
+impl HandleMode {
+    /// `next` with its start and handles moved so the joint from `previous`
+    /// into it has this continuity, `previous` staying as it is.
+    pub fn enforce_after(self, previous: [Vec2; 4], next: [Vec2; 4]) -> [Vec2; 4] {
+        let [_, b0, c0, anchor] = previous;
+        let [_, b1, mut c1, d1] = next;
+        if self == HandleMode::Curvature {
+            // equal second derivatives: b0 - 2 c0 + anchor = anchor - 2 b1 + c1
+            c1 = b0 - 4.0 * c0 + 4.0 * anchor;
+        }
+        [anchor, self.opposite(c0, anchor, b1), c1, d1]
+    }
+    /// `previous` with its end and handles moved so the joint from it into
+    /// `next` has this continuity, `next` staying as it is.
+    pub fn enforce_before(self, previous: [Vec2; 4], next: [Vec2; 4]) -> [Vec2; 4] {
+        reversed(self.enforce_after(reversed(next), reversed(previous)))
+    }
+    /// How far `next`'s handles are from where this continuity puts them.
+    pub fn violation(self, previous: [Vec2; 4], next: [Vec2; 4]) -> f32 {
+        next.iter()
+            .zip(self.enforce_after(previous, next))
+            .map(|(point, enforced)| point.distance(enforced))
+            .fold(0.0, f32::max)
+    }
+}
+
+/// Restores the continuity of the joints next to edited spline segments by
+/// moving the segments beyond them, outward from the edit until a `Free`
+/// joint or one that already holds. The moves send `BezierModified` and
+/// are recorded with the edit that caused them: in the drag's followers
+/// while it lasts, otherwise in its `BezierHistory` step.
+#[allow(clippy::too_many_arguments)]
+pub fn enforce_spline_continuity(
+    mut commands: Commands,
+    mut events: EventReader<BezierModified>,
+    settings: Res<BezierContinuitySettings>,
+    splines: Query<&BezierSpline>,
+    mut query: Query<(Entity, &mut Shape, &mut ShapeType)>,
+    mut document: Option<ResMut<BezierDocument>>,
+    mut drags: ResMut<BezierDrag>,
+    mut history: ResMut<BezierHistory>,
+    style: Res<BezierStyle>,
+) {
+    let edited: HashSet<usize> = events.read().map(|event| event.id).collect();
+    if !settings.enforce || edited.is_empty() {
+        return;
+    }
+    let curves = collect_bezier_points(query.iter().map(|(_, _, s)| s));
+    let mut changes = Vec::new();
+    for spline in splines.iter() {
+        let Some(mut points) = spline
+            .segments
+            .iter()
+            .map(|id| curves.get(id).copied())
+            .collect::<Option<Vec<_>>>()
+        else {
+            continue;
+        };
+        let sources: Vec<usize> = (0..spline.segments.len())
+            .filter(|&index| edited.contains(&spline.segments[index]))
+            .collect();
+        if sources.is_empty() {
+            continue;
+        }
+        let mut visited: HashSet<usize> = sources.iter().copied().collect();
+        let mut changed = HashSet::new();
+        for &start in &sources {
+            let mut index = start;
+            while let Some(next) = spline.next_index(index) {
+                let mode = spline.handle_mode(index);
+                let holds = mode.violation(points[index], points[next]) <= settings.tolerance;
+                if mode == HandleMode::Free
+                    || holds
+                    || (visited.contains(&next) && !sources.contains(&next))
+                {
+                    break;
+                }
+                points[next] = mode.enforce_after(points[index], points[next]);
+                changed.insert(next);
+                visited.insert(next);
+                if next == start {
+                    break;
+                }
+                index = next;
+            }
+            let mut index = start;
+            while let Some(previous) = spline.previous_index(index) {
+                let mode = spline.handle_mode(previous);
+                let holds = mode.violation(points[previous], points[index]) <= settings.tolerance;
+                if mode == HandleMode::Free || holds || visited.contains(&previous) {
+                    break;
+                }
+                points[previous] = mode.enforce_before(points[previous], points[index]);
+                changed.insert(previous);
+                visited.insert(previous);
+                index = previous;
+            }
+        }
+        changes.extend(
+            changed
+                .into_iter()
+                .map(|index| (spline.segments[index], points[index])),
+        );
+    }
+    let mut edits = Vec::new();
+    for (id, points) in changes {
+        update_bezier_in_place(&style, &mut query, id, points);
+        // sent through commands, as this system is still reading the events
+        commands.send_event(BezierModified { id, points });
+        if let Some(document) = document.as_mut() {
+            document.write_back(id, points);
+        }
+        edits.push(BezierEdit {
+            id,
+            before: curves[&id],
+            after: points,
+        });
+    }
+    if edits.is_empty() {
+        return;
+    }
+    let dragged = drags.pointers.values_mut().find(|state| {
+        edited.contains(&state.bezier_id)
+            || state.group.iter().any(|member| edited.contains(&member.id))
+    });
+    match dragged {
+        Some(state) => state.follow(edits),
+        None => history.amend(&edited, edits),
+    }
+}
+
+/// Circles the spline joints that break their `HandleMode`, see
+/// `BezierContinuitySettings::show_violations`.
+pub fn draw_bezier_continuity_violations(
+    mut gizmos: Gizmos,
+    settings: Res<BezierContinuitySettings>,
+    registry: Res<BezierRegistry>,
+    splines: Query<&BezierSpline>,
+) {
+    if !settings.show_violations {
+        return;
+    }
+    for spline in splines.iter() {
+        for index in 0..spline.segments.len() {
+            let mode = spline.handle_mode(index);
+            let Some(next) = spline.next_index(index) else {
+                continue;
+            };
+            let (
+                HandleMode::Aligned | HandleMode::Mirrored | HandleMode::Curvature,
+                Some(first),
+                Some(second),
+            ) = (
+                mode,
+                registry.points(spline.segments[index]),
+                registry.points(spline.segments[next]),
+            )
+            else {
+                continue;
+            };
+            if mode.violation(first, second) > settings.tolerance {
+                gizmos.circle_2d(
+                    Isometry2d::from_translation(first[3]),
+                    settings.size,
+                    settings.color,
+                );
+            }
+        }
+    }
+}
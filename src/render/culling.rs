@@ -0,0 +1,106 @@
+use super::*;
+
+/// Hides the anchors, controls and guide lines of curves that don't need
+/// them, for scenes with hundreds of curves. Hidden parts are skipped by
+/// rendering and by `bezier_pick_prefilter`, so they cost neither draw calls
+/// nor hit tests; the curves themselves are always drawn.
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct BezierHandleCulling {
+    /// Hide the handles of curves whose `BezierAabb` is outside the view
+    /// of the active camera.
+    pub enabled: bool,
+    /// World units the view is grown by, so handles appear just before
+    /// their curve scrolls in.
+    pub margin: f32,
+    /// Also hide the handles of curves in view, unless hovered, selected
+    /// or being dragged.
+    pub active_only: bool,
+}
+
+impl Default for BezierHandleCulling {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            margin: 50.0,
+            active_only: false,
+        }
+    }
+}
+
+/// World rectangle `camera` sees, `None` before its viewport is known.
+pub fn camera_world_view(camera: &Camera, camera_transform: &GlobalTransform) -> Option<Rect> {
+    let viewport = camera.logical_viewport_rect()?;
+    let corners = [
+        viewport.min,
+        Vec2::new(viewport.max.x, viewport.min.y),
+        viewport.max,
+        Vec2::new(viewport.min.x, viewport.max.y),
+    ];
+    corners
+        .into_iter()
+        .map(|corner| camera.viewport_to_world_2d(camera_transform, corner).ok())
+        .collect::<Option<Vec<Vec2>>>()?
+        .into_iter()
+        .map(|corner| Rect::from_center_size(corner, Vec2::ZERO))
+        .reduce(|view, corner| view.union(corner))
+}
+
+/// Shows and hides handles by `BezierHandleCulling`. Runs after
+/// `apply_bezier_edit_mode` and only hides what the edit mode shows, so
+/// `Display` still hides every handle.
+#[allow(clippy::too_many_arguments)]
+pub fn cull_bezier_handles(
+    culling: Res<BezierHandleCulling>,
+    mode: Res<BezierEditMode>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    hovered: Query<&ShapeType, With<BezierHovered>>,
+    selection: Res<BezierSelection>,
+    selected: Option<Res<SelectedBezierPoint>>,
+    drag: Res<BezierDrag>,
+    mut parts: Query<(
+        &ShapeType,
+        &BezierAabb,
+        &mut Visibility,
+        Option<&BezierSpace>,
+    )>,
+) {
+    if !culling.enabled && !culling.is_changed() {
+        return;
+    }
+    let view = cameras
+        .iter()
+        .find(|(camera, _)| camera.is_active)
+        .and_then(|(camera, camera_transform)| camera_world_view(camera, camera_transform))
+        .map(|view| view.inflate(culling.margin));
+    let mut active: HashSet<usize> = selection.curves().into_iter().collect();
+    active.extend(selected.map(|selected| selected.id));
+    active.extend(drag.pointers.values().map(|state| state.bezier_id));
+    active.extend(hovered.iter().filter_map(|shape_type| match shape_type {
+        ShapeType::Bezier(bezier_shape) => Some(bezier_shape.id),
+        _ => None,
+    }));
+    for (shape_type, aabb, mut visibility, space) in parts.iter_mut() {
+        let ShapeType::Bezier(bezier_shape) = shape_type else {
+            continue;
+        };
+        let part = &bezier_shape.shape_type;
+        if BezierEditMode::Display.shows(part) {
+            continue;
+        }
+        // UI curves are laid out in pixels, not in the camera's view
+        let in_view = matches!(space, Some(BezierSpace::Ui))
+            || view.is_none_or(|view| !view.intersect(aabb.0).is_empty());
+        let shown = !culling.enabled
+            || active.contains(&bezier_shape.id)
+            || (in_view && !culling.active_only);
+        let wanted = if mode.shows(part) && shown {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+    }
+}
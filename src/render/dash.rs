@@ -0,0 +1,45 @@
+use super::*;
+
+const DASH_FLATTEN_TOLERANCE: f32 = 0.25;
+
+/// Path through `points`, broken up by `pattern` when there is one.
+pub fn dashed_path(points: &[Vec2], pattern: Option<&[f32]>) -> ShapePath {
+    let runs = match pattern {
+        Some(pattern) => dash_polyline(points, pattern),
+        None => vec![points.to_vec()],
+    };
+    let mut path = ShapePath::new();
+    for run in runs {
+        let Some((first, rest)) = run.split_first() else {
+            continue;
+        };
+        path = path.move_to(*first);
+        for point in rest {
+            path = path.line_to(*point);
+        }
+    }
+    path
+}
+
+/// Stroke of a guide line between a handle and its anchor, dashed by
+/// `BezierStyle::sketch_dash`.
+pub fn guide_line_shape(style: &BezierStyle, start: Vec2, end: Vec2) -> Shape {
+    match style.sketch_dash.as_deref() {
+        Some(pattern) => ShapeBuilder::with(&dashed_path(&[start, end], Some(pattern))),
+        None => ShapeBuilder::new().add(&shapes::Line(start, end)),
+    }
+    .stroke((style.sketch_color, style.sketch_stroke_width))
+    .build()
+}
+
+/// Stroke of an open curve dashed by `BezierStyle::curve_dash`, or `None`
+/// when the curve is drawn solid.
+pub fn dashed_curve_shape(style: &BezierStyle, points: [Vec2; 4]) -> Option<Shape> {
+    let pattern = style.curve_dash.as_deref()?;
+    let flattened = CubicBezier::from(points).flatten(DASH_FLATTEN_TOLERANCE);
+    Some(
+        ShapeBuilder::with(&dashed_path(&flattened, Some(pattern)))
+            .stroke((style.bezier_line_color, style.bezier_stroke_width))
+            .build(),
+    )
+}
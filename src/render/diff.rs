@@ -0,0 +1,58 @@
+use super::*;
+
+/// The commands turning the curves `old` into `new`, matched by id: a
+/// `DeleteCurve` for each id only in `old`, a `SetPoints` for each id whose
+/// points changed and a `SpawnCurve` for each id only in `new`, in that
+/// order and by ascending id. Sending these rather than whole scenes keeps
+/// networked or autosaved copies in step; push them onto `BezierCommands`
+/// to apply them to the world, or use `apply_diff` on plain curves.
+pub fn diff(old: &[BezierCurve], new: &[BezierCurve]) -> Vec<BezierCommand> {
+    let old: BTreeMap<usize, [Vec2; 4]> =
+        old.iter().map(|curve| (curve.id, curve.points())).collect();
+    let new: BTreeMap<usize, [Vec2; 4]> =
+        new.iter().map(|curve| (curve.id, curve.points())).collect();
+    let deleted = old
+        .keys()
+        .filter(|id| !new.contains_key(id))
+        .map(|&id| BezierCommand::DeleteCurve { id });
+    let moved = new.iter().filter_map(|(&id, &points)| {
+        old.get(&id)
+            .filter(|&&before| before != points)
+            .map(|_| BezierCommand::SetPoints { id, points })
+    });
+    let spawned = new
+        .iter()
+        .filter(|(id, _)| !old.contains_key(id))
+        .map(|(&id, &points)| BezierCommand::SpawnCurve { id, points });
+    deleted.chain(moved).chain(spawned).collect()
+}
+
+/// `curves` with the commands of a `diff` applied, ordered by id. Commands
+/// other than `DeleteCurve`, `SetPoints` and `SpawnCurve` are skipped, as
+/// their result depends on the world.
+pub fn apply_diff(curves: &[BezierCurve], diff: &[BezierCommand]) -> Vec<BezierCurve> {
+    let mut curves: BTreeMap<usize, [Vec2; 4]> = curves
+        .iter()
+        .map(|curve| (curve.id, curve.points()))
+        .collect();
+    for command in diff {
+        match command {
+            BezierCommand::DeleteCurve { id } => {
+                curves.remove(id);
+            }
+            BezierCommand::SetPoints { id, points } => {
+                if let Some(current) = curves.get_mut(id) {
+                    *current = *points;
+                }
+            }
+            BezierCommand::SpawnCurve { id, points } => {
+                curves.entry(*id).or_insert(*points);
+            }
+            _ => {}
+        }
+    }
+    curves
+        .into_iter()
+        .map(|(id, [a, b, c, d])| BezierCurve { id, a, b, c, d })
+        .collect()
+}
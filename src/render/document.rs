@@ -0,0 +1,84 @@
+use super::*;
+
+/// Application-owned model of the curves in a scene. Edits go through the
+/// document and `sync_entities` reconciles the ECS shapes with it; drags on
+/// document curves are written back so it always holds the current points.
+#[derive(Resource, Default)]
+pub struct BezierDocument {
+    curves: BTreeMap<usize, [Vec2; 4]>,
+    dirty: HashSet<usize>,
+    removed: HashSet<usize>,
+}
+
+impl BezierDocument {
+    pub fn add(
+        &mut self,
+        ids: &mut BezierIdAllocator,
+        a: Vec2,
+        b: Vec2,
+        c: Vec2,
+        d: Vec2,
+    ) -> usize {
+        let id = ids.next_id();
+        self.curves.insert(id, [a, b, c, d]);
+        self.dirty.insert(id);
+        id
+    }
+    pub fn remove(&mut self, id: usize) -> Option<[Vec2; 4]> {
+        let points = self.curves.remove(&id)?;
+        self.dirty.remove(&id);
+        self.removed.insert(id);
+        Some(points)
+    }
+    pub fn get(&self, id: usize) -> Option<[Vec2; 4]> {
+        self.curves.get(&id).copied()
+    }
+    /// Moves the points of an existing curve; its entities are rebuilt on the
+    /// next `sync_entities`.
+    pub fn set(&mut self, id: usize, points: [Vec2; 4]) -> bool {
+        let Some(current) = self.curves.get_mut(&id) else {
+            return false;
+        };
+        *current = points;
+        self.dirty.insert(id);
+        true
+    }
+    pub fn contains(&self, id: usize) -> bool {
+        self.curves.contains_key(&id)
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (usize, [Vec2; 4])> + '_ {
+        self.curves.iter().map(|(&id, &points)| (id, points))
+    }
+    /// Records an edit made directly on the entities (e.g. by dragging),
+    /// so there is nothing to respawn.
+    pub(crate) fn write_back(&mut self, id: usize, points: [Vec2; 4]) {
+        if let Some(current) = self.curves.get_mut(&id) {
+            *current = points;
+        }
+    }
+    /// Spawns curves added since the last sync, despawns removed ones and
+    /// rebuilds the ones that were moved.
+    pub fn sync_entities(&mut self, commands: &mut Commands, style: &BezierStyle) {
+        let stale: HashSet<usize> = self.dirty.union(&self.removed).copied().collect();
+        if stale.is_empty() {
+            return;
+        }
+        commands.queue(move |world: &mut World| despawn_bezier_entities(world, &stale));
+        for id in self.dirty.drain() {
+            if let Some(&[a, b, c, d]) = self.curves.get(&id) {
+                spawn_bezier_with_id(commands, style, id, bezier_open(style, id, a, b, c, d));
+            }
+        }
+        self.removed.clear();
+    }
+}
+
+pub fn sync_bezier_document(
+    mut commands: Commands,
+    mut document: ResMut<BezierDocument>,
+    style: Res<BezierStyle>,
+) {
+    if document.is_changed() {
+        document.sync_entities(&mut commands, &style);
+    }
+}
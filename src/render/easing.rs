@@ -0,0 +1,128 @@
+use super::*;
+
+/// Turns the curve under this `BezierId` root into an easing-curve editor:
+/// the unit square of the timing function is drawn as the world rectangle at
+/// `origin` with `size`, the anchors stay pinned to its corners and the
+/// controls can't leave it sideways.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component)]
+pub struct BezierEasing {
+    pub origin: Vec2,
+    pub size: Vec2,
+}
+
+impl BezierEasing {
+    /// Points of the curve drawing `curve` in this editor.
+    pub fn to_points(&self, curve: &EasingCurve) -> [Vec2; 4] {
+        curve
+            .to_cubic()
+            .points()
+            .map(|point| self.origin + point * self.size)
+    }
+    /// The timing function curve `points` draw in this editor.
+    pub fn curve(&self, points: [Vec2; 4]) -> EasingCurve {
+        let [_, b, c, _] = points.map(|point| (point - self.origin) / self.size);
+        EasingCurve::new(b.x, b.y, c.x, c.y)
+    }
+    /// `points` with the anchors on the corners and the controls clamped.
+    pub fn constrain(&self, points: [Vec2; 4]) -> [Vec2; 4] {
+        self.to_points(&self.curve(points))
+    }
+}
+
+/// Spawns an easing-curve editor for `curve` with a fresh id.
+pub fn spawn_easing_bezier(
+    commands: &mut Commands,
+    style: &BezierStyle,
+    ids: &mut BezierIdAllocator,
+    easing: BezierEasing,
+    curve: EasingCurve,
+) -> BezierId {
+    let id = ids.next_id();
+    let [a, b, c, d] = easing.to_points(&curve);
+    let root = spawn_bezier_with_id(commands, style, id, bezier_open(style, id, a, b, c, d));
+    commands.entity(root).insert(easing);
+    BezierId(id)
+}
+
+/// Pulls edited easing curves back into their constraints.
+pub fn constrain_easing_beziers(
+    mut events: EventReader<BezierModified>,
+    editors: Query<(&BezierId, &BezierEasing)>,
+    mut query: Query<(Entity, &mut Shape, &mut ShapeType)>,
+    mut document: Option<ResMut<BezierDocument>>,
+    style: Res<BezierStyle>,
+) {
+    for event in events.read() {
+        let Some((_, easing)) = editors.iter().find(|(id, _)| id.0 == event.id) else {
+            continue;
+        };
+        let points = easing.constrain(event.points);
+        if points != event.points {
+            update_bezier_in_place(&style, &mut query, event.id, points);
+            if let Some(document) = document.as_mut() {
+                document.write_back(event.id, points);
+            }
+        }
+    }
+}
+
+/// Keeps the curve under this `BezierId` root the graph of a function
+/// y = f(x), e.g. a volume envelope, whatever is dragged: its controls
+/// can't leave the x range between its anchors and its `End` anchor can't
+/// pass its `Start`, see `to_x_monotonic`. Read it with
+/// `CubicBezier::y_for_x`.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct BezierFunction;
+
+impl BezierDragState {
+    /// Pulls the dragged points back to a function of x if the curve is a
+    /// `BezierFunction`, so they are constrained before they are drawn,
+    /// recorded or reported.
+    pub fn constrain_function(&mut self) {
+        let (true, Some(points)) = (self.function, self.points()) else {
+            return;
+        };
+        let [a, b, c, d] = CubicBezier::from(points).to_x_monotonic().points();
+        (self.a, self.b, self.c, self.d) = (Some(a), Some(b), Some(c), Some(d));
+    }
+}
+
+/// Pulls function curves edited other than by dragging, e.g. by
+/// `BezierCommands`, back to being functions of x. The correction sends
+/// `BezierModified` and replaces the edit's points in its `BezierHistory`
+/// step.
+pub fn constrain_function_beziers(
+    mut commands: Commands,
+    mut events: EventReader<BezierModified>,
+    functions: Query<&BezierId, With<BezierFunction>>,
+    mut query: Query<(Entity, &mut Shape, &mut ShapeType)>,
+    mut document: Option<ResMut<BezierDocument>>,
+    mut history: ResMut<BezierHistory>,
+    style: Res<BezierStyle>,
+) {
+    for event in events.read() {
+        if !functions.iter().any(|id| id.0 == event.id) {
+            continue;
+        }
+        let points = CubicBezier::from(event.points).to_x_monotonic().points();
+        if points != event.points {
+            update_bezier_in_place(&style, &mut query, event.id, points);
+            // sent through commands, as this system is still reading the events
+            commands.send_event(BezierModified {
+                id: event.id,
+                points,
+            });
+            if let Some(document) = document.as_mut() {
+                document.write_back(event.id, points);
+            }
+            let edit = BezierEdit {
+                id: event.id,
+                before: event.points,
+                after: points,
+            };
+            history.amend(&HashSet::from([event.id]), vec![edit]);
+        }
+    }
+}
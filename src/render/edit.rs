@@ -0,0 +1,253 @@
+use super::*;
+
+/// Settings for deleting, duplicating and smoothing the selected curve,
+/// whose keys are in `BezierInputMap`, and the modifiers that constrain
+/// handle drags.
+#[derive(Resource, Clone, Debug)]
+pub struct BezierEditSettings {
+    pub keybindings: bool,
+    /// How far a duplicate is moved from the original.
+    pub duplicate_offset: Vec2,
+    /// Held while dragging a handle to swing it around its anchor at a
+    /// fixed length.
+    pub rotate_handle_keys: Vec<KeyCode>,
+    /// Held while dragging a handle to lengthen or shorten it along its
+    /// current direction.
+    pub extend_handle_keys: Vec<KeyCode>,
+    /// Keeps a dragged curve's arc length what it was when the drag
+    /// started, as for ropes and cables, by lengthening or shortening the
+    /// handle at the other end. Moving the whole curve is unaffected.
+    pub hold_length: bool,
+    /// How far the held length may drift, in world units.
+    pub length_tolerance: f32,
+    /// Dragging the curve body bends the curve through the pointer, its
+    /// anchors staying put, instead of moving it. Moving a selected group
+    /// and the whole-curve keys of `BezierInputMap` still move curves.
+    pub bend_curves: bool,
+    /// Key smoothing the spline of the selected curve, if any, see
+    /// `BezierCommand::SmoothSpline`. Ignored with
+    /// `BezierInputMap::shortcut_keys` held. None by default, so apps
+    /// binding S for their own tools aren't surprised.
+    pub smooth_key: Option<KeyCode>,
+    /// How far one press of `smooth_key` relaxes the handles, in `[0, 1]`.
+    pub smooth_strength: f32,
+}
+
+impl Default for BezierEditSettings {
+    fn default() -> Self {
+        Self {
+            keybindings: true,
+            duplicate_offset: Vec2::new(20.0, -20.0),
+            rotate_handle_keys: vec![KeyCode::AltLeft, KeyCode::AltRight],
+            extend_handle_keys: vec![KeyCode::ControlLeft, KeyCode::ControlRight],
+            hold_length: false,
+            length_tolerance: 0.5,
+            bend_curves: false,
+            smooth_key: None,
+            smooth_strength: 0.5,
+        }
+    }
+}
+
+impl BezierEditSettings {
+    /// The handle constraint selected by the modifiers held in `keys`.
+    pub fn handle_drag_mode(&self, keys: &ButtonInput<KeyCode>) -> HandleDragMode {
+        if keys.any_pressed(self.rotate_handle_keys.iter().copied()) {
+            HandleDragMode::Rotate
+        } else if keys.any_pressed(self.extend_handle_keys.iter().copied()) {
+            HandleDragMode::Extend
+        } else {
+            HandleDragMode::Free
+        }
+    }
+    /// Adjusts the handle opposite the dragged part of `drag` so the curve
+    /// keeps the length it had when the drag started, if `hold_length`.
+    pub fn hold_drag_length(&self, drag: &mut BezierDragState) {
+        let (true, false, Some(start), Some(points)) = (
+            self.hold_length,
+            drag.quadratic,
+            drag.start_points,
+            drag.points(),
+        ) else {
+            return;
+        };
+        let at_start = match drag.dragging {
+            BezierShapeType::End | BezierShapeType::ControlEnd => true,
+            BezierShapeType::Start | BezierShapeType::ControlStart => false,
+            _ => return,
+        };
+        let length = CubicBezier::from(start).arc_length();
+        let CubicBezier { a, b, c, d } =
+            CubicBezier::from(points).with_arc_length(length, self.length_tolerance, at_start);
+        (drag.a, drag.b, drag.c, drag.d) = (Some(a), Some(b), Some(c), Some(d));
+    }
+    /// Bends the curve of `drag` so the point grabbed on its body follows
+    /// the pointer, `delta` from where it was grabbed, if `bend_curves`.
+    /// Returns whether it did; the drag moves the curve otherwise.
+    pub fn bend_drag(&self, drag: &mut BezierDragState, delta: Vec2) -> bool {
+        let (true, BezierShapeType::BezierLine, true, Some(grab), Some(start)) = (
+            self.bend_curves,
+            &drag.dragging,
+            drag.group.is_empty(),
+            drag.grab_point,
+            drag.start_points,
+        ) else {
+            return false;
+        };
+        let curve = CubicBezier::from(start);
+        let (t, _) = curve.distance_to_point(grab);
+        let target = curve.point_at(t) + delta;
+        let CubicBezier { a, b, c, d } = if drag.quadratic {
+            // the elevated control has to stay a quadratic's
+            let [a, b, c, d] = start;
+            let q = reduce_to_quadratic(a, b, c, d);
+            let weight = 2.0 * t * (1.0 - t);
+            if weight * weight <= 1e-2 {
+                curve
+            } else {
+                let q = q + (target - curve.point_at(t)) / weight;
+                let (b, c) = elevate_quadratic(a, q, d);
+                CubicBezier::new(a, b, c, d)
+            }
+        } else {
+            curve.bend(t, target)
+        };
+        (drag.a, drag.b, drag.c, drag.d) = (Some(a), Some(b), Some(c), Some(d));
+        true
+    }
+}
+
+/// Despawns the parts and `BezierId` roots of curves `ids`.
+pub(crate) fn despawn_bezier_entities(world: &mut World, ids: &HashSet<usize>) {
+    let mut query = world.query::<(Entity, &ShapeType)>();
+    let mut entities: Vec<Entity> = query
+        .iter(world)
+        .filter(|(_, shape_type)| {
+            matches!(shape_type, ShapeType::Bezier(bezier_shape) if ids.contains(&bezier_shape.id))
+        })
+        .map(|(entity, _)| entity)
+        .collect();
+    let mut roots = world.query::<(Entity, &BezierId)>();
+    entities.extend(
+        roots
+            .iter(world)
+            .filter(|(_, id)| ids.contains(&id.0))
+            .map(|(entity, _)| entity),
+    );
+    for entity in entities {
+        world.try_despawn(entity).ok();
+    }
+}
+
+/// Removes curve `id` and its label from the scene, and from the
+/// `BezierDocument` and selection if it is in them.
+pub fn despawn_bezier(commands: &mut Commands, id: usize) {
+    commands.queue(move |world: &mut World| {
+        despawn_bezier_entities(world, &HashSet::from([id]));
+        let mut labels = world.query::<(Entity, &BezierLabel)>();
+        let labels: Vec<Entity> = labels
+            .iter(world)
+            .filter(|(_, label)| label.id == id)
+            .map(|(entity, _)| entity)
+            .collect();
+        for entity in labels {
+            world.try_despawn(entity).ok();
+        }
+        if let Some(mut document) = world.get_resource_mut::<BezierDocument>() {
+            if document.contains(id) {
+                document.remove(id);
+            }
+        }
+        if world
+            .get_resource::<SelectedBezierPoint>()
+            .is_some_and(|selected| selected.id == id)
+        {
+            world.remove_resource::<SelectedBezierPoint>();
+        }
+    });
+}
+
+/// Spawns a copy of curve `id` moved by `offset` under a fresh id, keeping
+/// whether it is closed or quadratic and its `BezierUserData`, and selects
+/// the copy.
+pub fn duplicate_bezier(commands: &mut Commands, id: usize, offset: Vec2) {
+    commands.queue(move |world: &mut World| {
+        let mut query = world.query::<&ShapeType>();
+        let Some(points) = collect_bezier_points(query.iter(world)).get(&id).copied() else {
+            return;
+        };
+        let (mut closed, mut quadratic) = (false, false);
+        for shape_type in query.iter(world) {
+            if let ShapeType::Bezier(bezier_shape) = shape_type {
+                if bezier_shape.id == id {
+                    closed |= bezier_shape.closed;
+                    quadratic |= matches!(bezier_shape.shape_type, BezierShapeType::Control);
+                }
+            }
+        }
+        let [a, b, c, d] = points.map(|point| point + offset);
+        let style = world.get_resource_or_init::<BezierStyle>().clone();
+        let new_id = world.get_resource_or_init::<BezierIdAllocator>().next_id();
+        let shapes = if quadratic {
+            bezier_quadratic_shapes(
+                &style,
+                new_id,
+                a,
+                reduce_to_quadratic(a, b, c, d),
+                d,
+                closed,
+            )
+        } else {
+            bezier_shapes(&style, new_id, a, b, c, d, closed)
+        };
+        let part = world
+            .get_resource::<SelectedBezierPoint>()
+            .filter(|selected| selected.id == id)
+            .map_or(BezierShapeType::Start, |selected| selected.part.clone());
+        spawn_bezier_with_id(&mut world.commands(), &style, new_id, shapes);
+        world.insert_resource(SelectedBezierPoint { id: new_id, part });
+        copy_bezier_user_data(&mut world.commands(), id, new_id);
+        world.flush();
+    });
+}
+
+/// Deletes, duplicates or smooths the selected curve on the
+/// `BezierInputMap` keys and `BezierEditSettings::smooth_key`, if set.
+pub fn bezier_edit_keys(
+    mut bezier_commands: ResMut<BezierCommands>,
+    keys: Res<ButtonInput<KeyCode>>,
+    settings: Res<BezierEditSettings>,
+    input: Res<BezierInputMap>,
+    selected: Option<Res<SelectedBezierPoint>>,
+    locks: BezierLocks,
+) {
+    let Some(selected) = selected else {
+        return;
+    };
+    if !settings.keybindings {
+        return;
+    }
+    let shortcut = keys.any_pressed(input.shortcut_keys.iter().copied());
+    if keys.any_just_pressed(input.delete_curve_keys.iter().copied()) {
+        if locks.curve_locked(selected.id) {
+            return;
+        }
+        bezier_commands.push(BezierCommand::DeleteCurve { id: selected.id });
+    } else if shortcut
+        && input
+            .duplicate_key
+            .is_some_and(|key| keys.just_pressed(key))
+    {
+        bezier_commands.push(BezierCommand::DuplicateCurve {
+            id: selected.id,
+            offset: settings.duplicate_offset,
+        });
+    } else if settings
+        .smooth_key
+        .is_some_and(|key| keys.just_pressed(key))
+        && !shortcut
+        && !locks.curve_locked(selected.id)
+    {
+        bezier_commands.smooth_spline(selected.id, settings.smooth_strength);
+    }
+}
@@ -0,0 +1,49 @@
+use super::*;
+
+/// Whether curves show their editing scaffolding. In `Display` only the
+/// curve itself (and its caps) is drawn; anchors, controls and guide lines
+/// are hidden, can't be picked, and nothing can be dragged.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub enum BezierEditMode {
+    #[default]
+    Editing,
+    Display,
+}
+
+impl BezierEditMode {
+    pub fn toggle(&mut self) {
+        *self = match self {
+            BezierEditMode::Editing => BezierEditMode::Display,
+            BezierEditMode::Display => BezierEditMode::Editing,
+        };
+    }
+    /// Whether a part of the given type is shown in this mode.
+    pub fn shows(&self, shape_type: &BezierShapeType) -> bool {
+        *self == BezierEditMode::Editing || *shape_type == BezierShapeType::BezierLine
+    }
+}
+
+/// Hides or shows the scaffolding of every curve when the mode changes, and
+/// of newly spawned curves.
+pub fn apply_bezier_edit_mode(
+    mode: Res<BezierEditMode>,
+    mut parts: Query<(Ref<ShapeType>, &mut Visibility)>,
+) {
+    for (shape_type, mut visibility) in parts.iter_mut() {
+        if !mode.is_changed() && !shape_type.is_added() {
+            continue;
+        }
+        let ShapeType::Bezier(bezier_shape) = &*shape_type else {
+            continue;
+        };
+        let wanted = if mode.shows(&bezier_shape.shape_type) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+    }
+}
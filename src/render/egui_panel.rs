@@ -0,0 +1,175 @@
+use super::*;
+use bevy_egui::{EguiContexts, egui};
+
+/// A side panel for the selected curve, the one `SelectedBezierPoint` and
+/// so the edit keys act on: its id, its points as numbers to type or drag,
+/// the modes of its spline joints and its style override. Edits go through
+/// `BezierCommands`, so they can be undone; a point is moved once its field
+/// is released or loses focus, so one scrub is one undo step. Not added by
+/// `BezierPlugin`; add it to `Update` next to bevy_egui's `EguiPlugin`, and
+/// `clear_keys_typed_into_egui` to `PreUpdate`.
+pub fn bezier_egui_panel(
+    mut contexts: EguiContexts,
+    selected: Option<Res<SelectedBezierPoint>>,
+    registry: Res<BezierRegistry>,
+    splines: Query<&BezierSpline>,
+    style: Res<BezierStyle>,
+    mut bezier_commands: ResMut<BezierCommands>,
+    mut pending: Local<Option<(usize, [Vec2; 4])>>,
+) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+    egui::SidePanel::right("bezier_properties").show(ctx, |ui| {
+        let Some((id, entry)) = selected
+            .as_ref()
+            .and_then(|selected| Some((selected.id, registry.get(selected.id)?)))
+        else {
+            ui.label("No curve selected");
+            return;
+        };
+        ui.heading(format!("Curve {}", id));
+
+        // points being typed or dragged, shown until their field is let go
+        let mut points = match *pending {
+            Some((pending_id, points)) if pending_id == id => points,
+            _ => entry.points,
+        };
+        let mut changed = false;
+        let mut finished = false;
+        egui::Grid::new("bezier_points").show(ui, |ui| {
+            for (name, point) in ["a", "b", "c", "d"].into_iter().zip(points.iter_mut()) {
+                ui.label(name);
+                for (prefix, value) in [("x ", &mut point.x), ("y ", &mut point.y)] {
+                    let response = ui.add(egui::DragValue::new(value).prefix(prefix));
+                    changed |= response.changed();
+                    finished |= response.drag_stopped() || response.lost_focus();
+                }
+                ui.end_row();
+            }
+        });
+        if changed {
+            *pending = Some((id, points));
+        }
+        if finished && pending.take().is_some() {
+            // moved like a drag would, so spline neighbours follow
+            let parts = [
+                BezierShapeType::Start,
+                BezierShapeType::ControlStart,
+                BezierShapeType::ControlEnd,
+                BezierShapeType::End,
+            ];
+            for ((part, before), after) in parts.into_iter().zip(entry.points).zip(points) {
+                if after != before {
+                    bezier_commands.push(BezierCommand::MovePoint {
+                        id,
+                        part,
+                        delta: after - before,
+                    });
+                }
+            }
+        }
+
+        if let Some(spline) = splines.iter().find(|spline| spline.segments.contains(&id)) {
+            let index = spline
+                .segments
+                .iter()
+                .position(|&segment| segment == id)
+                .unwrap();
+            ui.separator();
+            let joints = [
+                ("Start", spline.previous_index(index)),
+                ("End", spline.next_index(index).map(|_| index)),
+            ];
+            for (name, joint) in joints {
+                let Some(joint) = joint else {
+                    continue;
+                };
+                let current = spline.handle_mode(joint);
+                let mut mode = current;
+                egui::ComboBox::from_label(format!("{} joint", name))
+                    .selected_text(format!("{:?}", current))
+                    .show_ui(ui, |ui| {
+                        for option in [
+                            HandleMode::Free,
+                            HandleMode::Aligned,
+                            HandleMode::Mirrored,
+                            HandleMode::Curvature,
+                        ] {
+                            ui.selectable_value(&mut mode, option, format!("{:?}", option));
+                        }
+                    });
+                if mode != current {
+                    bezier_commands.push(BezierCommand::SetJointMode { id, joint, mode });
+                }
+            }
+        }
+
+        ui.separator();
+        let current = style.overrides.get(&id).cloned();
+        let mut style_override = current.clone().unwrap_or_default();
+        let curve_style = style.for_curve(id);
+        let mut changed = false;
+        let mut width = curve_style.bezier_stroke_width;
+        ui.horizontal(|ui| {
+            ui.label("Stroke width");
+            if ui
+                .add(
+                    egui::DragValue::new(&mut width)
+                        .range(0.0..=f32::MAX)
+                        .speed(0.1),
+                )
+                .changed()
+            {
+                style_override.bezier_stroke_width = Some(width);
+                changed = true;
+            }
+        });
+        for (name, color, field) in [
+            (
+                "Line color",
+                curve_style.bezier_line_color,
+                &mut style_override.bezier_line_color,
+            ),
+            (
+                "Fill color",
+                curve_style.fill_color,
+                &mut style_override.fill_color,
+            ),
+        ] {
+            let mut rgba = color.to_srgba().to_u8_array();
+            ui.horizontal(|ui| {
+                ui.label(name);
+                if ui.color_edit_button_srgba_unmultiplied(&mut rgba).changed() {
+                    *field = Some(Color::srgba_u8(rgba[0], rgba[1], rgba[2], rgba[3]));
+                    changed = true;
+                }
+            });
+        }
+        if changed {
+            bezier_commands.push(BezierCommand::SetStyle {
+                id,
+                style: Some(style_override),
+            });
+        }
+        if current.is_some() && ui.button("Reset style").clicked() {
+            bezier_commands.push(BezierCommand::SetStyle { id, style: None });
+        }
+    });
+}
+
+/// Clears `ButtonInput<KeyCode>` while an egui widget has keyboard focus,
+/// so Delete, Backspace and the arrows typed into `bezier_egui_panel` don't
+/// also delete or nudge the curve. Add it to `PreUpdate` after
+/// `InputSystem`.
+pub fn clear_keys_typed_into_egui(
+    mut contexts: EguiContexts,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+    if ctx.wants_keyboard_input() {
+        keys.reset_all();
+    }
+}
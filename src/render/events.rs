@@ -0,0 +1,34 @@
+use super::*;
+
+/// Curve `id` now has `points`, whether from a drag, undo, transform or
+/// any other edit made by this crate.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct BezierModified {
+    pub id: usize,
+    pub points: [Vec2; 4],
+}
+
+/// A pointer started dragging `part` of curve `id`.
+#[derive(Event, Clone, Debug)]
+pub struct BezierDragStarted {
+    pub id: usize,
+    pub part: BezierShapeType,
+    pub pointer_id: PointerId,
+}
+
+/// A pointer released curve `id`, leaving it at `points`.
+#[derive(Event, Clone, Debug)]
+pub struct BezierDragEnded {
+    pub id: usize,
+    pub points: [Vec2; 4],
+    pub pointer_id: PointerId,
+}
+
+/// A drag of curve `id` was cancelled, putting the curve back at `points`,
+/// where it was when the drag started.
+#[derive(Event, Clone, Debug)]
+pub struct BezierDragCancelled {
+    pub id: usize,
+    pub points: [Vec2; 4],
+    pub pointer_id: PointerId,
+}
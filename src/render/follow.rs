@@ -0,0 +1,79 @@
+use super::*;
+
+/// Moves the entity along curve `id` at `speed` world units per second,
+/// measured along the curve so the speed stays even through bends.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+#[require(Transform)]
+pub struct FollowBezier {
+    pub id: usize,
+    pub speed: f32,
+    /// Start over at the beginning after reaching the end, instead of
+    /// stopping there.
+    pub looping: bool,
+    /// Turn the entity so its local x axis points along the curve.
+    pub orient: bool,
+    /// Distance travelled along the curve so far.
+    pub distance: f32,
+}
+
+impl FollowBezier {
+    pub fn new(id: usize, speed: f32) -> Self {
+        Self {
+            id,
+            speed,
+            looping: false,
+            orient: false,
+            distance: 0.0,
+        }
+    }
+    pub fn looping(mut self) -> Self {
+        self.looping = true;
+        self
+    }
+    pub fn oriented(mut self) -> Self {
+        self.orient = true;
+        self
+    }
+}
+
+pub fn follow_beziers(
+    time: Res<Time>,
+    mut followers: Query<(&mut FollowBezier, &mut Transform)>,
+    shapes: Query<&ShapeType>,
+) {
+    if followers.is_empty() {
+        return;
+    }
+    let curves = collect_bezier_points(shapes.iter());
+    for (mut follow, mut transform) in followers.iter_mut() {
+        let Some(&points) = curves.get(&follow.id) else {
+            continue;
+        };
+        let curve = CubicBezier::from(points);
+        let length = curve.arc_length();
+        let mut distance = follow.distance + follow.speed * time.delta_secs();
+        distance = if follow.looping && length > 0.0 {
+            distance.rem_euclid(length)
+        } else {
+            distance.clamp(0.0, length)
+        };
+        follow.distance = distance;
+
+        let t = curve.t_at_distance(distance);
+        let oriented = bezier_transform_at(&curve, t, transform.translation.z);
+        transform.translation = oriented.translation;
+        if follow.orient && curve.frame_at(t).1 != Vec2::ZERO {
+            transform.rotation = oriented.rotation;
+        }
+    }
+}
+
+/// Transform at `t` on `curve`, at depth `z`, with its x axis along the
+/// curve and its y axis along the normal, e.g. to place fence posts or
+/// railway ties across a path.
+pub fn bezier_transform_at(curve: &CubicBezier, t: f32, z: f32) -> Transform {
+    let mut transform = curve.transform_at(t);
+    transform.translation.z = z;
+    transform
+}
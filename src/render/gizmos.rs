@@ -0,0 +1,124 @@
+use super::*;
+
+const GIZMO_FLATTEN_TOLERANCE: f32 = 0.5;
+
+/// How curves are drawn. `Gizmos` redraws every curve each frame with
+/// immediate-mode gizmos, which suits curves animated every frame anyway.
+/// Its lyon shapes stay spawned but transparent, since they are still what
+/// picking hits, so every editing interaction keeps working.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub enum BezierRenderBackend {
+    #[default]
+    Lyon,
+    Gizmos,
+}
+
+/// Makes curve shapes transparent under `BezierRenderBackend::Gizmos`, and
+/// restores them by restyling when switching back to `Lyon`.
+pub fn apply_bezier_render_backend(
+    backend: Res<BezierRenderBackend>,
+    mut style: ResMut<BezierStyle>,
+    mut shapes: Query<&mut Shape, With<ShapeType>>,
+) {
+    if backend.is_changed() && *backend == BezierRenderBackend::Lyon {
+        if !backend.is_added() {
+            style.set_changed();
+        }
+        return;
+    }
+    if *backend != BezierRenderBackend::Gizmos {
+        return;
+    }
+    for mut shape in shapes.iter_mut() {
+        if !backend.is_changed() && !shape.is_changed() {
+            continue;
+        }
+        let visible_fill = shape
+            .fill
+            .as_ref()
+            .is_some_and(|fill| fill.color != Color::NONE);
+        let visible_stroke = shape
+            .stroke
+            .as_ref()
+            .is_some_and(|stroke| stroke.color != Color::NONE);
+        if !visible_fill && !visible_stroke {
+            continue;
+        }
+        if let Some(fill) = shape.fill.as_mut() {
+            fill.color = Color::NONE;
+        }
+        if let Some(stroke) = shape.stroke.as_mut() {
+            stroke.color = Color::NONE;
+        }
+    }
+}
+
+/// Draws every curve in `BezierRegistry` with gizmos under
+/// `BezierRenderBackend::Gizmos`, with its handles unless `BezierEditMode`
+/// hides them.
+pub fn draw_bezier_gizmos(
+    mut gizmos: Gizmos,
+    backend: Res<BezierRenderBackend>,
+    registry: Res<BezierRegistry>,
+    style: Res<BezierStyle>,
+    mode: Res<BezierEditMode>,
+    hovered: Query<&ShapeType, With<BezierHovered>>,
+) {
+    if *backend != BezierRenderBackend::Gizmos {
+        return;
+    }
+    let hovered: HashSet<(usize, BezierShapeType)> = hovered
+        .iter()
+        .filter_map(|shape_type| match shape_type {
+            ShapeType::Bezier(bezier_shape) => {
+                Some((bezier_shape.id, bezier_shape.shape_type.clone()))
+            }
+            _ => None,
+        })
+        .collect();
+    for (id, entry) in registry.iter() {
+        let curve_style = style.for_curve(id);
+        let [a, b, c, d] = entry.points;
+        let curve_color = if hovered.contains(&(id, BezierShapeType::BezierLine)) {
+            style.hover_color
+        } else {
+            curve_style.bezier_line_color
+        };
+        let mut points = CubicBezier::from(entry.points).flatten(GIZMO_FLATTEN_TOLERANCE);
+        if entry.closed {
+            points.push(a);
+        }
+        gizmos.linestrip_2d(points, curve_color);
+
+        if !mode.shows(&BezierShapeType::Start) {
+            continue;
+        }
+        let handles = if entry.quadratic {
+            let q = reduce_to_quadratic(a, b, c, d);
+            gizmos.linestrip_2d([a, q, d], curve_style.sketch_color);
+            vec![
+                (BezierShapeType::Start, a),
+                (BezierShapeType::Control, q),
+                (BezierShapeType::End, d),
+            ]
+        } else {
+            gizmos.linestrip_2d([a, b, c, d], curve_style.sketch_color);
+            vec![
+                (BezierShapeType::Start, a),
+                (BezierShapeType::ControlStart, b),
+                (BezierShapeType::ControlEnd, c),
+                (BezierShapeType::End, d),
+            ]
+        };
+        let radius = curve_style.intersection_radius - 1.0;
+        for (part, point) in handles {
+            let color = if hovered.contains(&(id, part)) {
+                style.hover_color
+            } else {
+                curve_style.intersection_color
+            };
+            gizmos.circle_2d(Isometry2d::from_translation(point), radius, color);
+        }
+    }
+}
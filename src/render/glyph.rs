@@ -0,0 +1,30 @@
+use super::*;
+
+/// Spawns the outline of `c` from the font `font_bytes` as closed splines,
+/// one per contour, `scale` world units per font unit with the glyph origin
+/// at `origin`. Returns the spline entities. Each contour gets its own
+/// fill, so holes such as the counter of an `o` are filled over rather than
+/// cut out; set `BezierStyle::fill_color` transparent for lettering.
+pub fn spawn_glyph(
+    commands: &mut Commands,
+    style: &BezierStyle,
+    ids: &mut BezierIdAllocator,
+    font_bytes: &[u8],
+    c: char,
+    origin: Vec2,
+    scale: f32,
+) -> Result<Vec<Entity>, GlyphError> {
+    let contours = glyph_to_curves(font_bytes, c)?;
+    Ok(contours
+        .iter()
+        .filter_map(|contour| {
+            let points: Vec<Vec2> = contour
+                .segments
+                .iter()
+                .flat_map(|segment| [segment.a, segment.b, segment.c])
+                .map(|point| origin + point * scale)
+                .collect();
+            spawn_closed_bezier_spline(commands, style, ids, &points)
+        })
+        .collect())
+}
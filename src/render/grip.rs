@@ -0,0 +1,84 @@
+use super::*;
+
+/// Where a curve's move grip sits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum BezierGripPlacement {
+    /// On the curve, halfway along it.
+    #[default]
+    Midpoint,
+    /// Just past the top right corner of the curve's bounding box, clear of
+    /// the curve itself.
+    BoundsCorner,
+}
+
+/// A small square drawn with each curve whose drag moves the whole curve.
+/// While a curve has one, dragging its body no longer moves it, leaving
+/// the body to adding anchors and selecting; the whole-curve keys of
+/// `BezierInputMap` still do.
+#[derive(Clone, Debug, Reflect)]
+pub struct BezierMoveGrip {
+    pub placement: BezierGripPlacement,
+    /// Side of the square, in world units.
+    pub size: f32,
+    pub color: Color,
+}
+
+impl Default for BezierMoveGrip {
+    fn default() -> Self {
+        Self {
+            placement: BezierGripPlacement::Midpoint,
+            size: 10.0,
+            color: Color::srgb(0.3, 0.6, 1.0),
+        }
+    }
+}
+
+impl BezierMoveGrip {
+    /// Center of the grip of the curve through `points`.
+    pub fn position(&self, points: [Vec2; 4]) -> Vec2 {
+        let curve = CubicBezier::from(points);
+        match self.placement {
+            BezierGripPlacement::Midpoint => curve.point_at(0.5),
+            BezierGripPlacement::BoundsCorner => curve.bounding_box().max + Vec2::splat(self.size),
+        }
+    }
+}
+
+/// The move grip of curve `id`. Always built, empty when
+/// `BezierStyle::move_grip` is `None`, so toggling it restyles curves in
+/// place.
+pub fn move_grip_shape(
+    style: &BezierStyle,
+    id: usize,
+    closed: bool,
+    points: [Vec2; 4],
+) -> (Shape, ShapeType) {
+    let shape_type = ShapeType::Bezier(BezierShape {
+        shape_type: BezierShapeType::MoveGrip,
+        id,
+        point: None,
+        closed,
+        index: 0,
+        handles: None,
+    });
+    let Some(grip) = &style.move_grip else {
+        let empty = ShapeBuilder::with(&ShapePath::new())
+            .stroke((Color::NONE, 1.0))
+            .build();
+        return (empty, shape_type);
+    };
+    let center = grip.position(points);
+    let half = grip.size * 0.5;
+    let shape = ShapeBuilder::with(&shapes::Polygon {
+        points: vec![
+            center + Vec2::new(half, half),
+            center + Vec2::new(-half, half),
+            center + Vec2::new(-half, -half),
+            center + Vec2::new(half, -half),
+        ],
+        closed: true,
+    })
+    .fill(grip.color)
+    .build();
+    (shape, shape_type)
+}
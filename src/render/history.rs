@@ -0,0 +1,507 @@
+use super::*;
+use bevy::ecs::system::SystemState;
+use std::collections::BTreeSet;
+
+/// One committed edit: the points of curve `id` before and after it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BezierEdit {
+    pub id: usize,
+    pub before: [Vec2; 4],
+    pub after: [Vec2; 4],
+}
+
+impl BezierEdit {
+    pub fn inverse(&self) -> Self {
+        Self {
+            id: self.id,
+            before: self.after,
+            after: self.before,
+        }
+    }
+}
+
+/// A curve as a structural edit found or left it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BezierCurveState {
+    pub points: [Vec2; 4],
+    pub closed: bool,
+    pub quadratic: bool,
+    pub style: Option<BezierStyleOverride>,
+}
+
+/// Curve `id` before and after a structural edit, `None` where it didn't
+/// exist.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BezierCurveChange {
+    pub id: usize,
+    pub before: Option<BezierCurveState>,
+    pub after: Option<BezierCurveState>,
+}
+
+/// A spline before and after a structural edit, `None` where it didn't
+/// exist. Splines have no id, so restoring finds the one to change by its
+/// `before` contents.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BezierSplineChange {
+    pub before: Option<BezierSpline>,
+    pub after: Option<BezierSpline>,
+}
+
+/// The curves and splines an edit spawned, removed, split or restyled.
+/// Only what changed is kept.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BezierStructureEdit {
+    pub curves: Vec<BezierCurveChange>,
+    pub splines: Vec<BezierSplineChange>,
+    /// Moves of curves not in `curves` made along with the edit, e.g. by
+    /// `enforce_spline_continuity`, see `BezierHistory::amend`.
+    pub edits: Vec<BezierEdit>,
+}
+
+impl BezierStructureEdit {
+    /// What changed from `before` to `after`. Splines are matched by the
+    /// segments they share, so one that was split or merged is a change
+    /// rather than a removal and an addition.
+    pub fn between(before: &BezierScene, after: &BezierScene) -> Self {
+        let ids: BTreeSet<usize> = before
+            .curves
+            .keys()
+            .chain(after.curves.keys())
+            .copied()
+            .collect();
+        let curves = ids
+            .into_iter()
+            .filter_map(|id| {
+                let (old, new) = (before.curves.get(&id), after.curves.get(&id));
+                (old != new).then(|| BezierCurveChange {
+                    id,
+                    before: old.cloned(),
+                    after: new.cloned(),
+                })
+            })
+            .collect();
+        let mut unmatched: Vec<&BezierSpline> = before.splines.iter().collect();
+        let mut splines = Vec::new();
+        for spline in &after.splines {
+            let old = unmatched
+                .iter()
+                .position(|old| {
+                    old.segments
+                        .iter()
+                        .any(|segment| spline.segments.contains(segment))
+                })
+                .map(|index| unmatched.remove(index));
+            if old != Some(spline) {
+                splines.push(BezierSplineChange {
+                    before: old.cloned(),
+                    after: Some(spline.clone()),
+                });
+            }
+        }
+        splines.extend(unmatched.into_iter().map(|old| BezierSplineChange {
+            before: Some(old.clone()),
+            after: None,
+        }));
+        Self {
+            curves,
+            splines,
+            edits: Vec::new(),
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.curves.is_empty() && self.splines.is_empty() && self.edits.is_empty()
+    }
+    /// Whether the edit changed curve `id`.
+    pub fn touches(&self, id: usize) -> bool {
+        self.curves.iter().any(|change| change.id == id)
+            || self.edits.iter().any(|edit| edit.id == id)
+    }
+    pub fn inverse(&self) -> Self {
+        Self {
+            curves: self
+                .curves
+                .iter()
+                .map(|change| BezierCurveChange {
+                    id: change.id,
+                    before: change.after.clone(),
+                    after: change.before.clone(),
+                })
+                .collect(),
+            splines: self
+                .splines
+                .iter()
+                .map(|change| BezierSplineChange {
+                    before: change.after.clone(),
+                    after: change.before.clone(),
+                })
+                .collect(),
+            edits: self.edits.iter().rev().map(BezierEdit::inverse).collect(),
+        }
+    }
+}
+
+/// The curves and splines in the world, captured on either side of a
+/// structural edit to find what it changed.
+#[derive(Clone, Debug, Default)]
+pub struct BezierScene {
+    pub curves: BTreeMap<usize, BezierCurveState>,
+    pub splines: Vec<BezierSpline>,
+}
+
+impl BezierScene {
+    pub fn capture<'a>(
+        shape_types: impl IntoIterator<Item = &'a ShapeType>,
+        roots: impl IntoIterator<Item = (&'a BezierId, Option<&'a BezierStyleOverride>)>,
+        splines: impl IntoIterator<Item = &'a BezierSpline>,
+    ) -> Self {
+        let shape_types: Vec<&ShapeType> = shape_types.into_iter().collect();
+        let styles: HashMap<usize, &BezierStyleOverride> = roots
+            .into_iter()
+            .filter_map(|(root_id, style)| Some((root_id.0, style?)))
+            .collect();
+        let mut curves: BTreeMap<usize, BezierCurveState> =
+            collect_bezier_points(shape_types.iter().copied())
+                .into_iter()
+                .map(|(id, points)| {
+                    let state = BezierCurveState {
+                        points,
+                        closed: false,
+                        quadratic: false,
+                        style: styles.get(&id).map(|&style| style.clone()),
+                    };
+                    (id, state)
+                })
+                .collect();
+        for shape_type in shape_types {
+            if let ShapeType::Bezier(bezier_shape) = shape_type {
+                if let Some(curve) = curves.get_mut(&bezier_shape.id) {
+                    curve.closed |= bezier_shape.closed;
+                    curve.quadratic |= matches!(bezier_shape.shape_type, BezierShapeType::Control);
+                }
+            }
+        }
+        Self {
+            curves,
+            splines: splines.into_iter().cloned().collect(),
+        }
+    }
+    pub fn of_world(world: &mut World) -> Self {
+        let mut shape_types = world.query::<&ShapeType>();
+        let mut roots = world.query::<(&BezierId, Option<&BezierStyleOverride>)>();
+        let mut splines = world.query::<&BezierSpline>();
+        Self::capture(
+            shape_types.iter(world),
+            roots.iter(world),
+            splines.iter(world),
+        )
+    }
+}
+
+/// One undo step.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BezierStep {
+    /// Point edits made together, e.g. a curve and the spline neighbours
+    /// that followed it.
+    Edits(Vec<BezierEdit>),
+    Structure(BezierStructureEdit),
+}
+
+impl BezierStep {
+    pub fn inverse(&self) -> Self {
+        match self {
+            BezierStep::Edits(edits) => {
+                BezierStep::Edits(edits.iter().rev().map(BezierEdit::inverse).collect())
+            }
+            BezierStep::Structure(edit) => BezierStep::Structure(edit.inverse()),
+        }
+    }
+}
+
+/// Undo/redo stacks of edits, filled in by `drag_end` and
+/// `apply_bezier_commands`.
+#[derive(Resource)]
+pub struct BezierHistory {
+    undo: Vec<BezierStep>,
+    redo: Vec<BezierStep>,
+    /// Handle the `BezierInputMap` undo and redo keys in
+    /// `bezier_history_keys`.
+    pub keybindings: bool,
+}
+
+impl Default for BezierHistory {
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            keybindings: true,
+        }
+    }
+}
+
+impl BezierHistory {
+    pub fn record(&mut self, edit: BezierEdit) {
+        self.record_edits(vec![edit]);
+    }
+    /// Records `edits` as one step, skipping those that changed nothing.
+    pub fn record_edits(&mut self, mut edits: Vec<BezierEdit>) {
+        edits.retain(|edit| edit.before != edit.after);
+        if !edits.is_empty() {
+            self.push(BezierStep::Edits(edits));
+        }
+    }
+    pub fn record_structure(&mut self, edit: BezierStructureEdit) {
+        if !edit.is_empty() {
+            self.push(BezierStep::Structure(edit));
+        }
+    }
+    /// Adds `edits`, made in response to edits of the curves in `sources`,
+    /// to the latest step if it edited one of them, so one undo reverts
+    /// both. Otherwise they are dropped, as what they followed wasn't
+    /// recorded either.
+    pub fn amend(&mut self, sources: &HashSet<usize>, edits: Vec<BezierEdit>) {
+        match self.undo.last_mut() {
+            Some(BezierStep::Edits(step)) => {
+                if !step.iter().any(|edit| sources.contains(&edit.id)) {
+                    return;
+                }
+                for edit in edits {
+                    match step.iter_mut().find(|recorded| recorded.id == edit.id) {
+                        Some(recorded) => recorded.after = edit.after,
+                        None => step.push(edit),
+                    }
+                }
+            }
+            Some(BezierStep::Structure(step)) => {
+                if !sources.iter().any(|&id| step.touches(id)) {
+                    return;
+                }
+                for edit in edits {
+                    let change = step
+                        .curves
+                        .iter_mut()
+                        .find(|change| change.id == edit.id)
+                        .and_then(|change| change.after.as_mut());
+                    match change {
+                        Some(after) => after.points = edit.after,
+                        None => match step
+                            .edits
+                            .iter_mut()
+                            .find(|recorded| recorded.id == edit.id)
+                        {
+                            Some(recorded) => recorded.after = edit.after,
+                            None => step.edits.push(edit),
+                        },
+                    }
+                }
+            }
+            None => {}
+        }
+    }
+    fn push(&mut self, step: BezierStep) {
+        self.undo.push(step);
+        self.redo.clear();
+    }
+    /// Takes the latest step off the undo stack, returning the step that
+    /// reverts it, for `restore_bezier_step`.
+    pub fn undo(&mut self) -> Option<BezierStep> {
+        let step = self.undo.pop()?;
+        let inverse = step.inverse();
+        self.redo.push(step);
+        Some(inverse)
+    }
+    /// Re-applies the latest undone step.
+    pub fn redo(&mut self) -> Option<BezierStep> {
+        let step = self.redo.pop()?;
+        self.undo.push(step.clone());
+        Some(step)
+    }
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+/// Records `edits` as one step once the commands queued so far have run,
+/// so steps stay in the order their commands were applied.
+pub(crate) fn record_bezier_edits(commands: &mut Commands, edits: Vec<BezierEdit>) {
+    if edits.is_empty() {
+        return;
+    }
+    commands.queue(move |world: &mut World| {
+        if let Some(mut history) = world.get_resource_mut::<BezierHistory>() {
+            history.record_edits(edits);
+        }
+    });
+}
+
+/// Records the structural edit made since `before` was captured, once the
+/// commands queued so far have run.
+pub(crate) fn record_bezier_structure(commands: &mut Commands, before: BezierScene) {
+    commands.queue(move |world: &mut World| {
+        world.flush();
+        let edit = BezierStructureEdit::between(&before, &BezierScene::of_world(world));
+        if let Some(mut history) = world.get_resource_mut::<BezierHistory>() {
+            history.record_structure(edit);
+        }
+    });
+}
+
+/// Puts the curves and splines of `step` in their `after` state, sending
+/// `BezierModified` for the curves that moved or came back and writing
+/// moves back to the `BezierDocument`.
+pub fn restore_bezier_step(commands: &mut Commands, step: BezierStep) {
+    commands.queue(move |world: &mut World| match step {
+        BezierStep::Edits(edits) => {
+            for edit in edits {
+                move_bezier(world, edit.id, edit.after);
+            }
+        }
+        BezierStep::Structure(edit) => restore_bezier_structure(world, edit),
+    });
+}
+
+/// Rebuilds curve `id` at `points` in place, as a command would.
+pub(crate) fn move_bezier(world: &mut World, id: usize, points: [Vec2; 4]) {
+    let mut state = SystemState::<(
+        Res<BezierStyle>,
+        Query<(Entity, &mut Shape, &mut ShapeType)>,
+    )>::new(world);
+    let (style, mut query) = state.get_mut(world);
+    update_bezier_in_place(&style, &mut query, id, points);
+    world.send_event(BezierModified { id, points });
+    if let Some(mut document) = world.get_resource_mut::<BezierDocument>() {
+        document.write_back(id, points);
+    }
+}
+
+fn restore_bezier_structure(world: &mut World, edit: BezierStructureEdit) {
+    let current = BezierScene::of_world(world);
+    for BezierCurveChange { id, after, .. } in edit.curves {
+        match (current.curves.get(&id), after) {
+            (Some(_), None) => {
+                despawn_bezier(&mut world.commands(), id);
+                world.flush();
+            }
+            (Some(now), Some(after))
+                if now.closed == after.closed && now.quadratic == after.quadratic =>
+            {
+                if now.points != after.points {
+                    move_bezier(world, id, after.points);
+                }
+                if now.style != after.style {
+                    set_bezier_style_override(world, id, after.style);
+                }
+            }
+            (now, Some(after)) => {
+                if now.is_some() {
+                    despawn_bezier_entities(world, &HashSet::from([id]));
+                }
+                let style = world.get_resource_or_init::<BezierStyle>().clone();
+                let [a, b, c, d] = after.points;
+                let shapes = if after.quadratic {
+                    bezier_quadratic_shapes(
+                        &style,
+                        id,
+                        a,
+                        reduce_to_quadratic(a, b, c, d),
+                        d,
+                        after.closed,
+                    )
+                } else {
+                    bezier_shapes(&style, id, a, b, c, d, after.closed)
+                };
+                spawn_bezier_with_id(&mut world.commands(), &style, id, shapes);
+                world.flush();
+                set_bezier_style_override(world, id, after.style);
+                world.send_event(BezierModified {
+                    id,
+                    points: after.points,
+                });
+            }
+            (None, None) => {}
+        }
+    }
+    for BezierSplineChange { before, after } in edit.splines {
+        let mut splines = world.query::<(Entity, &BezierSpline)>();
+        let entity = before.and_then(|before| {
+            splines
+                .iter(world)
+                .find(|(_, spline)| **spline == before)
+                .map(|(entity, _)| entity)
+        });
+        let closed = entity
+            .and_then(|entity| world.get::<BezierSpline>(entity))
+            .is_some_and(|spline| spline.closed);
+        match (entity, after) {
+            (Some(entity), Some(after)) if after.closed == closed => {
+                world.entity_mut(entity).insert(after);
+            }
+            (entity, Some(after)) => {
+                if let Some(entity) = entity {
+                    world.try_despawn(entity).ok();
+                }
+                // through the spawn helper, so closed splines get their fill
+                let style = world.get_resource_or_init::<BezierStyle>().clone();
+                let mut shape_types = world.query::<&ShapeType>();
+                let curves = collect_bezier_points(shape_types.iter(world));
+                let cubics = after.cubics(&curves).unwrap_or_default();
+                spawn_spline_entity(&mut world.commands(), &style, after, &cubics);
+                world.flush();
+            }
+            (Some(entity), None) => {
+                world.try_despawn(entity).ok();
+            }
+            (None, None) => {}
+        }
+    }
+    // curves the structural change left alone, so their order doesn't matter
+    for edit in edit.edits {
+        move_bezier(world, edit.id, edit.after);
+    }
+}
+
+fn set_bezier_style_override(world: &mut World, id: usize, style: Option<BezierStyleOverride>) {
+    let mut roots = world.query::<(Entity, &BezierId)>();
+    let Some(root) = roots
+        .iter(world)
+        .find_map(|(entity, root_id)| (root_id.0 == id).then_some(entity))
+    else {
+        return;
+    };
+    match style {
+        Some(style) => {
+            world.entity_mut(root).insert(style);
+        }
+        None => {
+            world.entity_mut(root).remove::<BezierStyleOverride>();
+        }
+    }
+}
+
+pub fn bezier_history_keys(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    input: Res<BezierInputMap>,
+    mut history: ResMut<BezierHistory>,
+) {
+    if !history.keybindings || !input.undo_key.is_some_and(|key| keys.just_pressed(key)) {
+        return;
+    }
+    if !keys.any_pressed(input.shortcut_keys.iter().copied()) {
+        return;
+    }
+    let restore = if keys.any_pressed(input.redo_keys.iter().copied()) {
+        history.redo()
+    } else {
+        history.undo()
+    };
+    if let Some(step) = restore {
+        restore_bezier_step(&mut commands, step);
+    }
+}
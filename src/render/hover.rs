@@ -0,0 +1,91 @@
+use super::*;
+
+/// Marks a part of a curve the pointer is over.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct BezierHovered;
+
+pub fn bezier_hover_over(over: Trigger<Pointer<Over>>, mut commands: Commands) {
+    if let Ok(mut entity) = commands.get_entity(over.target) {
+        entity.insert(BezierHovered);
+    }
+}
+
+pub fn bezier_hover_out(out: Trigger<Pointer<Out>>, mut commands: Commands) {
+    if let Ok(mut entity) = commands.get_entity(out.target) {
+        entity.remove::<BezierHovered>();
+    }
+}
+
+/// Draws hovered handles, and the curve when its body is hovered, in
+/// `BezierStyle::hover_color`, and restores them once the pointer leaves.
+/// Runs every frame so shapes rebuilt by an edit get the color back.
+pub fn apply_bezier_hover(
+    style: Res<BezierStyle>,
+    hovered: Query<&ShapeType, With<BezierHovered>>,
+    mut shapes: Query<(&ShapeType, &mut Shape)>,
+    selected: Option<Res<SelectedBezierPoint>>,
+    mut previous: Local<HashSet<usize>>,
+) {
+    let mut ids = HashSet::new();
+    // the body is usually hovered through its invisible hit stroke
+    let mut curves = HashSet::new();
+    // and a handle through its `pick_radius` hit circle
+    let mut parts = HashSet::new();
+    for shape_type in hovered.iter() {
+        if let ShapeType::Bezier(bezier_shape) = shape_type {
+            ids.insert(bezier_shape.id);
+            parts.insert((bezier_shape.id, bezier_shape.shape_type.clone()));
+            if matches!(bezier_shape.shape_type, BezierShapeType::BezierLine) {
+                curves.insert(bezier_shape.id);
+            }
+        }
+    }
+    if ids.is_empty() && previous.is_empty() {
+        return;
+    }
+
+    for (shape_type, mut shape) in shapes.iter_mut() {
+        let ShapeType::Bezier(bezier_shape) = shape_type else {
+            continue;
+        };
+        let is_hovered = parts.contains(&(bezier_shape.id, bezier_shape.shape_type.clone()));
+        if !ids.contains(&bezier_shape.id) && !previous.contains(&bezier_shape.id) {
+            continue;
+        }
+        let curve_style = style.for_curve(bezier_shape.id);
+        if bezier_shape.point.is_some() || bezier_shape.handles.is_some() {
+            let is_selected = selected.as_ref().is_some_and(|selected| {
+                selected.id == bezier_shape.id && selected.part == bezier_shape.shape_type
+            });
+            let wanted = if is_hovered {
+                style.hover_color
+            } else {
+                curve_style
+                    .handle_marker(&bezier_shape.shape_type, is_selected)
+                    .1
+            };
+            if shape.fill.as_ref().is_some_and(|fill| fill.color != wanted) {
+                shape.fill.as_mut().unwrap().color = wanted;
+            }
+        } else if matches!(bezier_shape.shape_type, BezierShapeType::BezierLine) {
+            let wanted = if curves.contains(&bezier_shape.id) {
+                style.hover_color
+            } else {
+                curve_style.bezier_line_color
+            };
+            // leave the invisible hit strokes invisible
+            if let Some(stroke) = shape
+                .stroke
+                .as_ref()
+                .filter(|stroke| stroke.color != Color::NONE && stroke.color != wanted)
+            {
+                let stroke = Stroke {
+                    color: wanted,
+                    ..*stroke
+                };
+                shape.stroke = Some(stroke);
+            }
+        }
+    }
+    *previous = ids;
+}
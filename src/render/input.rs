@@ -0,0 +1,60 @@
+use super::*;
+
+/// The keys and buttons behind the editing gestures, so hosts can line
+/// them up with their own control scheme. Handle drag modifiers live in
+/// `BezierEditSettings` and angle snapping keys in `BezierSnapSettings`.
+#[derive(Resource, Clone, Debug)]
+pub struct BezierInputMap {
+    /// Pointer button that drags and clicks curve parts; other buttons are
+    /// left to the app, e.g. middle-drag for panning.
+    pub drag_button: PointerButton,
+    /// Held when a drag starts to move the whole curve, whichever part was
+    /// grabbed. Empty by default, so only the curve body moves it.
+    pub whole_curve_keys: Vec<KeyCode>,
+    /// Held while clicking a curve to insert an anchor where it was clicked.
+    /// Ctrl by default, which otherwise only constrains drags.
+    pub add_anchor_keys: Vec<KeyCode>,
+    /// Held while clicking an interior spline anchor to remove it. Alt by
+    /// default; dragging with it held rotates handles instead.
+    pub delete_anchor_keys: Vec<KeyCode>,
+    /// Key toggling `BezierSnapSettings::grid_enabled`, if any.
+    pub snap_toggle_key: Option<KeyCode>,
+    /// Held while dragging to keep the movement on the axis it first went
+    /// furthest along.
+    pub constrain_axis_keys: Vec<KeyCode>,
+    /// Pressed during a drag to put the dragged curves back where they
+    /// were when it started.
+    pub cancel_drag_keys: Vec<KeyCode>,
+    /// Mouse button sending a `BezierContextMenuEvent`, if any.
+    pub context_menu_button: Option<MouseButton>,
+    /// Pressed to delete the selected curve.
+    pub delete_curve_keys: Vec<KeyCode>,
+    /// Held for the shortcuts below and undo/redo. Ctrl by default.
+    pub shortcut_keys: Vec<KeyCode>,
+    /// Pressed with `shortcut_keys` to duplicate the selected curve, if any.
+    pub duplicate_key: Option<KeyCode>,
+    /// Pressed with `shortcut_keys` to undo, if any.
+    pub undo_key: Option<KeyCode>,
+    /// Held along with `shortcut_keys` and `undo_key` to redo instead.
+    pub redo_keys: Vec<KeyCode>,
+}
+
+impl Default for BezierInputMap {
+    fn default() -> Self {
+        Self {
+            drag_button: PointerButton::Primary,
+            whole_curve_keys: Vec::new(),
+            add_anchor_keys: vec![KeyCode::ControlLeft, KeyCode::ControlRight],
+            delete_anchor_keys: vec![KeyCode::AltLeft, KeyCode::AltRight],
+            snap_toggle_key: Some(KeyCode::KeyG),
+            constrain_axis_keys: vec![KeyCode::ShiftLeft, KeyCode::ShiftRight],
+            cancel_drag_keys: vec![KeyCode::Escape],
+            context_menu_button: Some(MouseButton::Right),
+            delete_curve_keys: vec![KeyCode::Delete, KeyCode::Backspace],
+            shortcut_keys: vec![KeyCode::ControlLeft, KeyCode::ControlRight],
+            duplicate_key: Some(KeyCode::KeyD),
+            undo_key: Some(KeyCode::KeyZ),
+            redo_keys: vec![KeyCode::ShiftLeft, KeyCode::ShiftRight],
+        }
+    }
+}
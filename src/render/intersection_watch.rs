@@ -0,0 +1,118 @@
+use super::*;
+
+/// Pairs of curve ids whose crossings are kept marked with
+/// `ShapeType::Intersection` shapes, recomputed whenever either curve
+/// changes. Markers of pairs removed from the watch, or whose curves were
+/// despawned, are despawned.
+#[derive(Resource, Clone, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct IntersectionWatch {
+    pub pairs: Vec<(usize, usize)>,
+}
+
+impl IntersectionWatch {
+    /// Starts marking the crossings of curves `a` and `b`.
+    pub fn watch(&mut self, a: usize, b: usize) {
+        if !self.is_watched(a, b) {
+            self.pairs.push((a, b));
+        }
+    }
+    /// Stops marking the crossings of curves `a` and `b`.
+    pub fn unwatch(&mut self, a: usize, b: usize) {
+        self.pairs
+            .retain(|&(p, q)| (p, q) != (a, b) && (p, q) != (b, a));
+    }
+    pub fn is_watched(&self, a: usize, b: usize) -> bool {
+        self.pairs
+            .iter()
+            .any(|&(p, q)| (p, q) == (a, b) || (p, q) == (b, a))
+    }
+}
+
+/// On each marker spawned for an `IntersectionWatch` pair: the pair, as
+/// listed in the watch, and which of its crossings the marker sits on.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct BezierIntersectionMarker {
+    pub pair: (usize, usize),
+    pub index: usize,
+}
+
+/// Spawns, moves and despawns the markers of `IntersectionWatch`, redoing a
+/// pair only when one of its curves' `BezierRegistry` versions changed.
+pub fn sync_intersection_markers(
+    mut commands: Commands,
+    watch: Res<IntersectionWatch>,
+    registry: Res<BezierRegistry>,
+    style: Res<BezierStyle>,
+    mut markers: Query<(Entity, &BezierIntersectionMarker, &mut Shape)>,
+    mut versions: Local<HashMap<(usize, usize), (u64, u64)>>,
+) {
+    if versions.is_empty() && watch.pairs.is_empty() {
+        return;
+    }
+    let mut current = HashMap::new();
+    for &(a, b) in &watch.pairs {
+        let (Some(entry_a), Some(entry_b)) = (registry.get(a), registry.get(b)) else {
+            continue;
+        };
+        current.insert((a, b), (entry_a, entry_b));
+    }
+
+    let mut existing: HashMap<(usize, usize), Vec<(usize, Entity)>> = HashMap::new();
+    for (entity, marker, _) in markers.iter() {
+        if current.contains_key(&marker.pair) {
+            existing
+                .entry(marker.pair)
+                .or_default()
+                .push((marker.index, entity));
+        } else {
+            commands.entity(entity).despawn();
+        }
+    }
+    versions.retain(|pair, _| current.contains_key(pair));
+
+    let z = style.layering.base_z + style.layering.label;
+    for (pair, (entry_a, entry_b)) in current {
+        let version = (entry_a.version, entry_b.version);
+        if versions.get(&pair) == Some(&version) {
+            continue;
+        }
+        versions.insert(pair, version);
+        let points: Vec<Vec2> = intersections(
+            &CubicBezier::from(entry_a.points),
+            &CubicBezier::from(entry_b.points),
+        )
+        .into_iter()
+        .map(|(_, _, point)| point)
+        .collect();
+        let mut shapes = intersection_markers(&style, &points).into_iter();
+        let mut existing = existing.remove(&pair).unwrap_or_default();
+        existing.sort_by_key(|&(index, _)| index);
+        // markers are numbered from 0 without gaps, so they line up with
+        // the first crossings
+        for (_, entity) in existing {
+            match shapes.next() {
+                Some((shape, _)) => {
+                    if let Ok((_, _, mut marker_shape)) = markers.get_mut(entity) {
+                        *marker_shape = shape;
+                    }
+                }
+                None => commands.entity(entity).despawn(),
+            }
+        }
+        let first_new = points.len() - shapes.len();
+        for (offset, (shape, shape_type)) in shapes.enumerate() {
+            commands.spawn((
+                shape,
+                shape_type,
+                BezierIntersectionMarker {
+                    pair,
+                    index: first_new + offset,
+                },
+                Pickable::IGNORE,
+                Transform::from_xyz(0.0, 0.0, z),
+            ));
+        }
+    }
+}
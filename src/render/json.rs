@@ -0,0 +1,191 @@
+use super::*;
+use serde::{Deserialize, Serialize};
+
+/// One curve as `export_beziers` and `save_curves` write it. The fields are
+/// spelled out rather than flattening a `BezierCurve`, which RON can't read
+/// back.
+#[derive(Serialize, Deserialize)]
+struct BezierRecord {
+    id: usize,
+    a: Vec2,
+    b: Vec2,
+    c: Vec2,
+    d: Vec2,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    closed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    data: BTreeMap<String, String>,
+}
+
+impl BezierRecord {
+    /// Spawns the curve under `id` with its label and user data.
+    fn spawn(&self, commands: &mut Commands, style: &BezierStyle, id: usize) {
+        let build = if self.closed {
+            bezier_closed
+        } else {
+            bezier_open
+        };
+        let (a, b, c, d) = (self.a, self.b, self.c, self.d);
+        spawn_bezier_with_id(commands, style, id, build(style, id, a, b, c, d));
+        if let Some(label) = &self.label {
+            set_bezier_label(commands, id, label.clone());
+        }
+        if !self.data.is_empty() {
+            let data = self.data.clone().into_iter().collect();
+            replace_bezier_user_data(commands, id, BezierUserData(data));
+        }
+    }
+}
+
+/// Records of the curves in `shape_types` that `keep` accepts, ordered by
+/// id, with their labels and user data.
+fn bezier_records<'a>(
+    shape_types: &[&'a ShapeType],
+    labels: impl IntoIterator<Item = &'a BezierLabel>,
+    user_data: impl IntoIterator<Item = (&'a BezierId, &'a BezierUserData)>,
+    keep: impl Fn(usize) -> bool,
+) -> Vec<BezierRecord> {
+    let closed: HashSet<usize> = shape_types
+        .iter()
+        .filter_map(|shape_type| match shape_type {
+            ShapeType::Bezier(bezier_shape) if bezier_shape.closed => Some(bezier_shape.id),
+            _ => None,
+        })
+        .collect();
+    let labels: HashMap<usize, &String> = labels
+        .into_iter()
+        .map(|label| (label.id, &label.text))
+        .collect();
+    let user_data: HashMap<usize, &BezierUserData> = user_data
+        .into_iter()
+        .map(|(root_id, data)| (root_id.0, data))
+        .collect();
+    collect_bezier_points(shape_types.iter().copied())
+        .into_iter()
+        .filter(|(id, _)| keep(*id))
+        .map(|(id, [a, b, c, d])| BezierRecord {
+            id,
+            a,
+            b,
+            c,
+            d,
+            closed: closed.contains(&id),
+            label: labels.get(&id).map(|text| (*text).clone()),
+            data: user_data
+                .get(&id)
+                .map(|data| data.0.clone().into_iter().collect())
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// Writes every curve in the world as a JSON array of `{id, a, b, c, d}`
+/// objects, ordered by id so repeated exports of the same scene match.
+/// Closed curves, labels and `BezierUserData` are written along.
+pub fn export_beziers(
+    query: &Query<(Entity, &ShapeType)>,
+    labels: &Query<&BezierLabel>,
+    user_data: &Query<(&BezierId, &BezierUserData)>,
+) -> String {
+    export_beziers_where(query, labels, user_data, |_| true)
+}
+
+/// Like `export_beziers`, but only the curves `ids`, e.g. to copy them.
+pub fn export_bezier_ids(
+    query: &Query<(Entity, &ShapeType)>,
+    labels: &Query<&BezierLabel>,
+    user_data: &Query<(&BezierId, &BezierUserData)>,
+    ids: &[usize],
+) -> String {
+    export_beziers_where(query, labels, user_data, |id| ids.contains(&id))
+}
+
+fn export_beziers_where(
+    query: &Query<(Entity, &ShapeType)>,
+    labels: &Query<&BezierLabel>,
+    user_data: &Query<(&BezierId, &BezierUserData)>,
+    keep: impl Fn(usize) -> bool,
+) -> String {
+    let shape_types: Vec<&ShapeType> = query.iter().map(|(_, shape_type)| shape_type).collect();
+    let records = bezier_records(&shape_types, labels.iter(), user_data.iter(), keep);
+    serde_json::to_string(&records).expect("bezier records serialize to JSON")
+}
+
+/// Spawns the curves from a document written by `export_beziers`, keeping
+/// their ids and moving the `BezierIdAllocator` past them.
+pub fn import_beziers(
+    commands: &mut Commands,
+    style: &BezierStyle,
+    json: &str,
+) -> Result<(), serde_json::Error> {
+    let records: Vec<BezierRecord> = serde_json::from_str(json)?;
+    for record in &records {
+        record.spawn(commands, style, record.id);
+    }
+    if let Some(max_id) = records.iter().map(|record| record.id).max() {
+        reserve_ids_through(commands, max_id);
+    }
+    Ok(())
+}
+
+/// Spawns the curves from a document written by `export_beziers` under
+/// fresh ids, so they can't clash with curves already in the world, e.g.
+/// when pasting. Returns the new ids in document order.
+pub fn import_beziers_as_new(
+    commands: &mut Commands,
+    style: &BezierStyle,
+    ids: &mut BezierIdAllocator,
+    json: &str,
+) -> Result<Vec<usize>, serde_json::Error> {
+    let records: Vec<BezierRecord> = serde_json::from_str(json)?;
+    let mut spawned = Vec::with_capacity(records.len());
+    for record in &records {
+        let id = ids.next_id();
+        record.spawn(commands, style, id);
+        spawned.push(id);
+    }
+    Ok(spawned)
+}
+
+/// Every curve in `world` as RON, ordered by id, in the same records as
+/// `export_beziers`.
+pub fn save_curves(world: &World) -> String {
+    let records = match world.try_query::<&ShapeType>() {
+        Some(mut query) => {
+            let mut labels = world.try_query::<&BezierLabel>();
+            let mut user_data = world.try_query::<(&BezierId, &BezierUserData)>();
+            bezier_records(
+                &query.iter(world).collect::<Vec<_>>(),
+                labels.iter_mut().flat_map(|labels| labels.iter(world)),
+                user_data.iter_mut().flat_map(|data| data.iter(world)),
+                |_| true,
+            )
+        }
+        None => Vec::new(),
+    };
+    ron::ser::to_string_pretty(&records, ron::ser::PrettyConfig::default())
+        .expect("bezier records serialize to RON")
+}
+
+/// Spawns the curves saved by `save_curves` with the default style.
+pub fn load_curves(commands: &mut Commands, ron: &str) -> Result<(), ron::error::SpannedError> {
+    let records: Vec<BezierRecord> = ron::from_str(ron)?;
+    let style = BezierStyle::default();
+    for record in &records {
+        record.spawn(commands, &style, record.id);
+    }
+    if let Some(max_id) = records.iter().map(|record| record.id).max() {
+        reserve_ids_through(commands, max_id);
+    }
+    Ok(())
+}
+
+pub(crate) fn reserve_ids_through(commands: &mut Commands, max_id: usize) {
+    commands.queue(move |world: &mut World| {
+        world
+            .get_resource_or_init::<BezierIdAllocator>()
+            .set_next_id(max_id + 1);
+    });
+}
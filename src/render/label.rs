@@ -0,0 +1,142 @@
+use super::*;
+
+/// Name shown next to curve `id`, drawn near the middle of the curve.
+#[derive(Component, Clone, Debug)]
+#[require(Text2d, Transform)]
+pub struct BezierLabel {
+    pub id: usize,
+    pub text: String,
+}
+
+/// Sets the label of curve `id`, spawning the label entity if it has none.
+pub fn set_bezier_label(commands: &mut Commands, id: usize, text: impl Into<String>) {
+    let text = text.into();
+    commands.queue(move |world: &mut World| {
+        let mut labels = world.query::<&mut BezierLabel>();
+        if let Some(mut label) = labels.iter_mut(world).find(|label| label.id == id) {
+            label.text = text;
+            return;
+        }
+        world.spawn(BezierLabel { id, text });
+    });
+}
+
+/// Keeps label text and position in step with the curves they name.
+pub fn update_bezier_labels(
+    mut labels: Query<(
+        &BezierLabel,
+        &mut Text2d,
+        &mut TextFont,
+        &mut TextColor,
+        &mut Transform,
+    )>,
+    query: Query<&ShapeType>,
+    style: Res<BezierStyle>,
+) {
+    if labels.is_empty() {
+        return;
+    }
+    let curves = collect_bezier_points(query.iter());
+    for (label, mut text, mut font, mut color, mut transform) in labels.iter_mut() {
+        if text.0 != label.text {
+            text.0.clone_from(&label.text);
+        }
+        font.font_size = style.label_font_size;
+        color.0 = style.label_color;
+        let Some(&[a, b, c, d]) = curves.get(&label.id) else {
+            continue;
+        };
+        let offset = Vec2::Y * (style.bezier_stroke_width + style.label_font_size);
+        let position = evaluate_bezier(a, b, c, d, 0.5) + offset;
+        transform.translation =
+            position.extend(style.layering.layer_z(label.id) + style.layering.label);
+    }
+}
+
+/// Annotation showing a curve's id, or the name of one of its points, while
+/// `BezierStyle::show_labels` or `show_point_labels` is on. Managed by
+/// `update_bezier_id_labels`; `part` is `None` for the id label.
+#[derive(Component, Clone, Debug)]
+#[require(Text2d, Transform)]
+pub struct BezierIdLabel {
+    pub id: usize,
+    pub part: Option<BezierShapeType>,
+}
+
+/// Spawns, moves and despawns `BezierIdLabel`s to match the curves in
+/// `BezierRegistry`: `#id` at the start of each curve and `a`/`b`/`c`/`d`
+/// beside its points (`q` for a quadratic's control).
+pub fn update_bezier_id_labels(
+    mut commands: Commands,
+    registry: Res<BezierRegistry>,
+    style: Res<BezierStyle>,
+    mut labels: Query<(
+        Entity,
+        &BezierIdLabel,
+        &mut Text2d,
+        &mut TextFont,
+        &mut TextColor,
+        &mut Transform,
+    )>,
+) {
+    if !registry.is_changed() && !style.is_changed() {
+        return;
+    }
+    let mut wanted: HashMap<(usize, Option<BezierShapeType>), (String, Vec2)> = HashMap::new();
+    let offset = Vec2::splat(style.intersection_radius + style.label_font_size * 0.5);
+    for (id, entry) in registry.iter() {
+        let [a, b, c, d] = entry.points;
+        if style.show_labels {
+            wanted.insert((id, None), (format!("#{id}"), a - offset));
+        }
+        if !style.show_point_labels {
+            continue;
+        }
+        let points = if entry.quadratic {
+            vec![
+                (BezierShapeType::Start, "a", a),
+                (
+                    BezierShapeType::Control,
+                    "q",
+                    reduce_to_quadratic(a, b, c, d),
+                ),
+                (BezierShapeType::End, "d", d),
+            ]
+        } else {
+            vec![
+                (BezierShapeType::Start, "a", a),
+                (BezierShapeType::ControlStart, "b", b),
+                (BezierShapeType::ControlEnd, "c", c),
+                (BezierShapeType::End, "d", d),
+            ]
+        };
+        for (part, name, point) in points {
+            wanted.insert((id, Some(part)), (name.to_string(), point + offset));
+        }
+    }
+
+    for (entity, label, mut text, mut font, mut color, mut transform) in labels.iter_mut() {
+        let Some((name, position)) = wanted.remove(&(label.id, label.part.clone())) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        if text.0 != name {
+            text.0 = name;
+        }
+        font.font_size = style.label_font_size;
+        color.0 = style.label_color;
+        transform.translation =
+            position.extend(style.layering.layer_z(label.id) + style.layering.label);
+    }
+    for ((id, part), (name, position)) in wanted {
+        commands.spawn((
+            BezierIdLabel { id, part },
+            Text2d::new(name),
+            TextFont::from_font_size(style.label_font_size),
+            TextColor(style.label_color),
+            Transform::from_translation(
+                position.extend(style.layering.layer_z(id) + style.layering.label),
+            ),
+        ));
+    }
+}
@@ -0,0 +1,145 @@
+use super::*;
+
+/// Puts the curve on whose `BezierId` root it sits into a named layer of
+/// `BezierLayers`, to hide, lock or recolor it along with the rest of the
+/// layer. Unrelated to the depth layers of `BezierLayering`.
+#[derive(Component, Clone, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct BezierLayer(pub String);
+
+/// How the curves of one named layer are shown and edited.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+pub struct BezierLayerSettings {
+    pub visible: bool,
+    /// Locks every curve in the layer, as if its root were `Locked`.
+    pub locked: bool,
+    /// Stroke color for the layer's curves, over the global style and any
+    /// `BezierStyleOverride` color.
+    pub color: Option<Color>,
+}
+
+impl Default for BezierLayerSettings {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            locked: false,
+            color: None,
+        }
+    }
+}
+
+/// Settings of the named layers curves are put into with `BezierLayer`.
+/// Layers nobody configured are visible, unlocked and keep their colors.
+#[derive(Resource, Clone, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct BezierLayers {
+    pub layers: HashMap<String, BezierLayerSettings>,
+}
+
+impl BezierLayers {
+    /// Settings of layer `name`.
+    pub fn layer(&self, name: &str) -> BezierLayerSettings {
+        self.layers.get(name).cloned().unwrap_or_default()
+    }
+    fn layer_mut(&mut self, name: &str) -> &mut BezierLayerSettings {
+        self.layers.entry(name.to_string()).or_default()
+    }
+    pub fn set_layer_visibility(&mut self, name: &str, visible: bool) {
+        self.layer_mut(name).visible = visible;
+    }
+    pub fn set_layer_locked(&mut self, name: &str, locked: bool) {
+        self.layer_mut(name).locked = locked;
+    }
+    /// Recolors layer `name`'s curves, or gives them back their own colors
+    /// with `None`.
+    pub fn set_layer_color(&mut self, name: &str, color: Option<Color>) {
+        self.layer_mut(name).color = color;
+    }
+    /// Whether layer `name` is locked.
+    pub fn is_locked(&self, name: &str) -> bool {
+        self.layers.get(name).is_some_and(|layer| layer.locked)
+    }
+}
+
+/// Moves curve `id` into layer `name` by putting `BezierLayer` on its root.
+pub fn set_bezier_layer(commands: &mut Commands, id: usize, name: impl Into<String>) {
+    let layer = BezierLayer(name.into());
+    commands.queue(move |world: &mut World| {
+        let mut roots = world.query::<(Entity, &BezierId)>();
+        if let Some(root) = roots
+            .iter(world)
+            .find_map(|(entity, root_id)| (root_id.0 == id).then_some(entity))
+        {
+            world.entity_mut(root).insert(layer);
+        }
+    });
+}
+
+/// Shows, hides and recolors curves by their layer's settings when the
+/// settings or a curve's layer change. Recoloring goes through
+/// `BezierStyle::overrides`, so runs after `sync_bezier_style_overrides`.
+#[allow(clippy::type_complexity)]
+pub fn apply_bezier_layers(
+    layers: Res<BezierLayers>,
+    mut roots: Query<(
+        Ref<BezierLayer>,
+        &BezierId,
+        &mut Visibility,
+        Option<Ref<BezierStyleOverride>>,
+    )>,
+    mut removed: RemovedComponents<BezierLayer>,
+    mut unlayered: Query<
+        (&BezierId, &mut Visibility, Option<&BezierStyleOverride>),
+        Without<BezierLayer>,
+    >,
+    mut style: ResMut<BezierStyle>,
+    mut recolored: Local<HashSet<usize>>,
+) {
+    for entity in removed.read() {
+        let Ok((id, mut visibility, style_override)) = unlayered.get_mut(entity) else {
+            continue;
+        };
+        *visibility = Visibility::Inherited;
+        if recolored.remove(&id.0) {
+            match style_override {
+                Some(style_override) => style.overrides.insert(id.0, style_override.clone()),
+                None => style.overrides.remove(&id.0),
+            };
+        }
+    }
+    for (layer, id, mut visibility, style_override) in roots.iter_mut() {
+        let override_changed = style_override
+            .as_ref()
+            .is_some_and(|style_override| style_override.is_changed());
+        if !layers.is_changed() && !layer.is_changed() && !override_changed {
+            continue;
+        }
+        let settings = layers.layer(&layer.0);
+        let wanted = if settings.visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+        // only the overrides this system wrote are put back, so ones set on
+        // `BezierStyle::overrides` directly survive layer changes
+        let own = style_override.map(|style_override| (*style_override).clone());
+        match (settings.color, own) {
+            (Some(color), own) => {
+                let mut style_override = own.unwrap_or_default();
+                style_override.bezier_line_color = Some(color);
+                style.overrides.insert(id.0, style_override);
+                recolored.insert(id.0);
+            }
+            (None, own) if recolored.remove(&id.0) => {
+                match own {
+                    Some(own) => style.overrides.insert(id.0, own),
+                    None => style.overrides.remove(&id.0),
+                };
+            }
+            (None, _) => {}
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use super::*;
+use bevy::ecs::system::SystemParam;
+
+/// Keeps a curve or one of its points from being edited: on a curve's
+/// `BezierId` root it locks the whole curve, on an anchor or control entity
+/// just that point. Locked parts can't be picked, dragged, nudged or
+/// deleted, so reference curves stay put.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct Locked;
+
+/// Locks curve `id` by marking its root `Locked`.
+pub fn lock_bezier(commands: &mut Commands, id: usize) {
+    set_bezier_locked(commands, id, true);
+}
+
+/// Unlocks curve `id`. Points locked on their own stay locked.
+pub fn unlock_bezier(commands: &mut Commands, id: usize) {
+    set_bezier_locked(commands, id, false);
+}
+
+fn set_bezier_locked(commands: &mut Commands, id: usize, locked: bool) {
+    commands.queue(move |world: &mut World| {
+        let mut roots = world.query::<(Entity, &BezierId)>();
+        let Some(root) = roots
+            .iter(world)
+            .find_map(|(entity, root_id)| (root_id.0 == id).then_some(entity))
+        else {
+            return;
+        };
+        if locked {
+            world.entity_mut(root).insert(Locked);
+        } else {
+            world.entity_mut(root).remove::<Locked>();
+        }
+    });
+}
+
+/// Which curves and points are `Locked`, or in a locked `BezierLayer`.
+/// Only visits locked entities and layered roots, so it is cheap to ask
+/// about every part.
+#[derive(SystemParam)]
+pub struct BezierLocks<'w, 's> {
+    locked: Query<'w, 's, (Option<&'static BezierId>, Option<&'static ShapeType>), With<Locked>>,
+    layered: Query<'w, 's, (&'static BezierId, &'static BezierLayer)>,
+    layers: Res<'w, BezierLayers>,
+}
+
+impl BezierLocks<'_, '_> {
+    /// Whether the whole curve `id` is locked.
+    pub fn curve_locked(&self, id: usize) -> bool {
+        self.locked
+            .iter()
+            .any(|(root, _)| root.is_some_and(|root| root.0 == id))
+            || self
+                .layered
+                .iter()
+                .any(|(root, layer)| root.0 == id && self.layers.is_locked(&layer.0))
+    }
+    /// Whether editing `part` of curve `id` is blocked. Moving the curve
+    /// body would drag its locked points along, so it is blocked by any.
+    pub fn part_locked(&self, id: usize, part: &BezierShapeType) -> bool {
+        self.curve_locked(id)
+            || self.locked.iter().any(|(_, shape_type)| match shape_type {
+                Some(ShapeType::Bezier(bezier_shape)) => {
+                    bezier_shape.id == id
+                        && (bezier_shape.shape_type == *part
+                            || *part == BezierShapeType::BezierLine)
+                }
+                _ => false,
+            })
+    }
+}
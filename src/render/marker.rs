@@ -0,0 +1,148 @@
+use super::*;
+
+/// Outline of an anchor or control point marker.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum BezierMarkerShape {
+    #[default]
+    Circle,
+    Square,
+    /// A square standing on one corner.
+    Diamond,
+}
+
+impl BezierMarkerShape {
+    /// The marker around `center`, filled with `color`. `radius` is that of
+    /// the circle; the square is drawn around it and the diamond is the
+    /// square turned by 45 degrees.
+    pub fn build(self, center: Vec2, radius: f32, color: Color) -> Shape {
+        let corners = match self {
+            BezierMarkerShape::Circle => {
+                return ShapeBuilder::with(&shapes::Circle { radius, center })
+                    .fill(color)
+                    .build();
+            }
+            BezierMarkerShape::Square => [
+                Vec2::new(radius, radius),
+                Vec2::new(-radius, radius),
+                Vec2::new(-radius, -radius),
+                Vec2::new(radius, -radius),
+            ],
+            BezierMarkerShape::Diamond => {
+                let radius = radius * std::f32::consts::SQRT_2;
+                [
+                    Vec2::new(radius, 0.0),
+                    Vec2::new(0.0, radius),
+                    Vec2::new(-radius, 0.0),
+                    Vec2::new(0.0, -radius),
+                ]
+            }
+        };
+        ShapeBuilder::with(&shapes::Polygon {
+            points: corners.iter().map(|&corner| center + corner).collect(),
+            closed: true,
+        })
+        .fill(color)
+        .build()
+    }
+}
+
+/// How one kind of point is drawn.
+#[derive(Clone, Debug, Default, PartialEq, Reflect)]
+pub struct BezierMarker {
+    pub shape: BezierMarkerShape,
+    /// Fill color, `BezierStyle::intersection_color` when `None`.
+    pub color: Option<Color>,
+}
+
+/// Markers for each kind of point, e.g. squares for anchors and circles for
+/// controls as most vector editors draw them. Curves drawn with
+/// `BezierStyle::batch_handles` keep plain circles.
+#[derive(Clone, Debug, Default, PartialEq, Reflect)]
+pub struct BezierHandleMarkers {
+    /// `Start` and `End` points.
+    pub anchor: BezierMarker,
+    /// `ControlStart`, `ControlEnd` and the `Control` of quadratics.
+    pub control: BezierMarker,
+    /// The `SelectedBezierPoint`, drawn like its kind when `None`.
+    pub selected: Option<BezierMarker>,
+}
+
+impl BezierStyle {
+    /// Shape and color of the marker of `part`.
+    pub fn handle_marker(
+        &self,
+        part: &BezierShapeType,
+        selected: bool,
+    ) -> (BezierMarkerShape, Color) {
+        let markers = &self.handle_markers;
+        let marker = match part {
+            BezierShapeType::Start | BezierShapeType::End => &markers.anchor,
+            BezierShapeType::ControlStart
+            | BezierShapeType::ControlEnd
+            | BezierShapeType::Control => &markers.control,
+            _ => return (BezierMarkerShape::Circle, self.intersection_color),
+        };
+        let marker = match &markers.selected {
+            Some(selected_marker) if selected => selected_marker,
+            _ => marker,
+        };
+        (
+            marker.shape,
+            marker.color.unwrap_or(self.intersection_color),
+        )
+    }
+
+    /// The marker of `part` at `center`.
+    pub fn handle_marker_shape(
+        &self,
+        part: &BezierShapeType,
+        center: Vec2,
+        selected: bool,
+    ) -> Shape {
+        let (shape, color) = self.handle_marker(part, selected);
+        shape.build(center, self.intersection_radius - 1.0, color)
+    }
+}
+
+/// Draws the `SelectedBezierPoint` with `BezierHandleMarkers::selected`
+/// and the point it replaced with its usual marker. Runs every frame so
+/// shapes rebuilt by an edit get the selected marker back.
+pub fn apply_selected_bezier_marker(
+    style: Res<BezierStyle>,
+    selected: Option<Res<SelectedBezierPoint>>,
+    mut shapes: Query<(&ShapeType, &mut Shape, Has<BezierHovered>)>,
+    mut previous: Local<Option<(usize, BezierShapeType)>>,
+) {
+    let current = selected.map(|selected| (selected.id, selected.part.clone()));
+    if style.handle_markers.selected.is_none() && previous.is_none() {
+        return;
+    }
+    let selection_changed = *previous != current;
+    for (shape_type, mut shape, is_hovered) in shapes.iter_mut() {
+        let ShapeType::Bezier(bezier_shape) = shape_type else {
+            continue;
+        };
+        let Some(point) = bezier_shape.point else {
+            continue;
+        };
+        let part = Some((bezier_shape.id, bezier_shape.shape_type.clone()));
+        let is_selected = part == current;
+        let rebuild = if is_selected {
+            selection_changed || shape.is_changed() || style.is_changed()
+        } else {
+            selection_changed && part == *previous
+        };
+        if !rebuild {
+            continue;
+        }
+        let curve_style = style.for_curve(bezier_shape.id);
+        let mut marker =
+            curve_style.handle_marker_shape(&bezier_shape.shape_type, point, is_selected);
+        // keep the hover color `apply_bezier_hover` gave it
+        if let Some(fill) = marker.fill.as_mut().filter(|_| is_hovered) {
+            fill.color = style.hover_color;
+        }
+        *shape = marker;
+    }
+    *previous = current.filter(|_| style.handle_markers.selected.is_some());
+}
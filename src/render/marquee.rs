@@ -0,0 +1,131 @@
+use super::*;
+use bevy::window::PrimaryWindow;
+
+/// Rectangle selection. While `enabled`, dragging from empty space draws a
+/// `ShapeType::Marquee` rectangle, and releasing selects every anchor and
+/// control inside it with `BezierSelection::select_indexed`, added to the
+/// selection with Shift held. Stays out of the way of the pen and sketch
+/// tools while they are enabled, and of `BezierTransformGizmo` handles.
+#[derive(Resource, Clone, Debug)]
+pub struct BezierMarqueeMode {
+    pub enabled: bool,
+    pub fill_color: Color,
+    pub stroke_color: Color,
+    start: Option<Vec2>,
+    end: Vec2,
+}
+
+impl Default for BezierMarqueeMode {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            fill_color: Color::srgba(0.3, 0.5, 1.0, 0.15),
+            stroke_color: Color::srgba(0.3, 0.5, 1.0, 0.8),
+            start: None,
+            end: Vec2::ZERO,
+        }
+    }
+}
+
+impl BezierMarqueeMode {
+    /// The rectangle being dragged out, if any.
+    pub fn rect(&self) -> Option<Rect> {
+        self.start.map(|start| Rect::from_corners(start, self.end))
+    }
+}
+
+/// Drags out and applies the `BezierMarqueeMode` rectangle.
+#[allow(clippy::too_many_arguments)]
+pub fn bezier_marquee_tool(
+    mut mode: ResMut<BezierMarqueeMode>,
+    mut selection: ResMut<BezierSelection>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    hovered: Query<(), With<BezierHovered>>,
+    index: Res<BezierPointIndex>,
+    pen: Res<BezierCreateMode>,
+    sketch: Res<BezierSketchMode>,
+    gizmo: Res<BezierTransformGizmo>,
+) {
+    if !mode.enabled || pen.enabled || sketch.enabled || gizmo.is_dragging() {
+        if mode.start.is_some() {
+            mode.start = None;
+        }
+        return;
+    }
+    if mouse.just_released(MouseButton::Left) {
+        if let Some(rect) = mode.rect() {
+            let additive = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+            selection.select_indexed(&index, rect, additive);
+            mode.start = None;
+        }
+        return;
+    }
+
+    let cursor = windows.single().ok().and_then(Window::cursor_position);
+    let Some((camera, camera_transform)) = cameras.iter().find(|(camera, _)| camera.is_active)
+    else {
+        return;
+    };
+    let Some(position) =
+        cursor.and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+    else {
+        return;
+    };
+
+    if mouse.just_pressed(MouseButton::Left) && hovered.is_empty() {
+        mode.start = Some(position);
+        mode.end = position;
+    } else if mode.start.is_some() && mouse.pressed(MouseButton::Left) && mode.end != position {
+        mode.end = position;
+    }
+}
+
+/// Draws the marquee being dragged as a `ShapeType::Marquee` shape.
+pub fn update_bezier_marquee_rect(
+    mut commands: Commands,
+    mode: Res<BezierMarqueeMode>,
+    mut rects: Query<(Entity, &mut Shape, &ShapeType)>,
+    style: Res<BezierStyle>,
+) {
+    if !mode.is_changed() {
+        return;
+    }
+    let mut existing = rects
+        .iter_mut()
+        .filter(|(_, _, shape_type)| matches!(shape_type, ShapeType::Marquee));
+    let Some(rect) = mode.rect() else {
+        for (entity, _, _) in existing {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+
+    let shape = ShapeBuilder::with(&shapes::Polygon {
+        points: vec![
+            rect.min,
+            Vec2::new(rect.max.x, rect.min.y),
+            rect.max,
+            Vec2::new(rect.min.x, rect.max.y),
+        ],
+        closed: true,
+    })
+    .fill(mode.fill_color)
+    .stroke((mode.stroke_color, 1.0))
+    .build();
+
+    if let Some((_, mut marquee, _)) = existing.next() {
+        *marquee = shape;
+    } else {
+        // above the curves so the selection area stays visible
+        let z = style.layering.base_z + style.layering.label;
+        commands.spawn((
+            ShapeType::Marquee,
+            shape,
+            Transform::from_xyz(0.0, 0.0, z),
+            Pickable::IGNORE,
+        ));
+    }
+}
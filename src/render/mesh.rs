@@ -0,0 +1,62 @@
+use super::*;
+use bevy::asset::RenderAssetUsages;
+use bevy::render::mesh::{Indices, PrimitiveTopology};
+
+/// Triangulated ribbon `stroke_width` wide along `curve`, flattened to
+/// within `tolerance`. UVs run 0..1 along the curve in `u` and across it in
+/// `v`, so the mesh can carry a texture or a custom material.
+pub fn build_mesh(curve: &CubicBezier, stroke_width: f32, tolerance: f32) -> Mesh {
+    let points = curve.flatten(tolerance);
+    let half_width = stroke_width * 0.5;
+    let mut lengths = Vec::with_capacity(points.len());
+    let mut length = 0.0;
+    for (i, point) in points.iter().enumerate() {
+        if i > 0 {
+            length += point.distance(points[i - 1]);
+        }
+        lengths.push(length);
+    }
+    let mut positions = Vec::with_capacity(points.len() * 2);
+    let mut uvs = Vec::with_capacity(points.len() * 2);
+    for (i, point) in points.iter().enumerate() {
+        let previous = points[i.saturating_sub(1)];
+        let next = points[(i + 1).min(points.len() - 1)];
+        let normal = (next - previous).normalize_or_zero().perp();
+        let u = if length > 0.0 {
+            lengths[i] / length
+        } else {
+            0.0
+        };
+        for (side, v) in [(1.0, 0.0), (-1.0, 1.0)] {
+            positions.push((*point + normal * half_width * side).extend(0.0).to_array());
+            uvs.push([u, v]);
+        }
+    }
+    let mut indices = Vec::with_capacity(points.len().saturating_sub(1) * 6);
+    for i in 0..points.len().saturating_sub(1) as u32 {
+        let (left, right) = (i * 2, i * 2 + 1);
+        let (next_left, next_right) = (left + 2, right + 2);
+        indices.extend([left, right, next_left, right, next_right, next_left]);
+    }
+    let normals = vec![[0.0, 0.0, 1.0]; positions.len()];
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    .with_inserted_indices(Indices::U32(indices))
+}
+
+/// Polyline collider along `curve`, flattened to within `tolerance`.
+#[cfg(feature = "avian2d")]
+pub fn build_collider(curve: &CubicBezier, tolerance: f32) -> avian2d::prelude::Collider {
+    avian2d::prelude::Collider::polyline(curve.flatten(tolerance), None)
+}
+
+/// Polyline collider along `curve`, flattened to within `tolerance`.
+#[cfg(all(feature = "rapier2d", not(feature = "avian2d")))]
+pub fn build_collider(curve: &CubicBezier, tolerance: f32) -> bevy_rapier2d::prelude::Collider {
+    bevy_rapier2d::prelude::Collider::polyline(curve.flatten(tolerance), None)
+}
@@ -0,0 +1,2305 @@
+//! The Bevy side of the crate: curves as pickable, draggable lyon shapes,
+//! and the plugin and tools built on them.
+use crate::math::*;
+// explicit so these win over the types of the same name in bevy's prelude
+use crate::math::{CubicBezier, EasingCurve};
+use bevy::picking::PickSet;
+use bevy::picking::pointer::{PointerId, PointerLocation};
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Mutex;
+
+mod attach;
+pub use attach::*;
+mod batch;
+pub use batch::*;
+mod bezier3d;
+pub use bezier3d::*;
+mod bounds;
+pub use bounds::*;
+mod caps;
+pub use caps::*;
+mod comb;
+pub use comb::*;
+mod command;
+pub use command::*;
+mod context_menu;
+pub use context_menu::*;
+mod continuity;
+pub use continuity::*;
+mod culling;
+pub use culling::*;
+mod dash;
+pub use dash::*;
+mod diff;
+pub use diff::*;
+mod document;
+pub use document::*;
+mod easing;
+pub use easing::*;
+mod edit;
+pub use edit::*;
+mod edit_mode;
+pub use edit_mode::*;
+mod events;
+pub use events::*;
+mod follow;
+pub use follow::*;
+mod gizmos;
+pub use gizmos::*;
+mod grip;
+pub use grip::*;
+mod history;
+pub use history::*;
+mod hover;
+pub use hover::*;
+mod input;
+pub use input::*;
+mod intersection_watch;
+pub use intersection_watch::*;
+mod label;
+pub use label::*;
+mod layer;
+pub use layer::*;
+mod lock;
+pub use lock::*;
+mod marker;
+pub use marker::*;
+mod marquee;
+pub use marquee::*;
+mod mesh;
+pub use mesh::*;
+mod morph;
+pub use morph::*;
+mod pen;
+pub use pen::*;
+mod pick;
+pub use pick::*;
+mod point_index;
+pub use point_index::*;
+mod probe;
+pub use probe::*;
+mod query;
+pub use query::*;
+mod reference;
+pub use reference::*;
+mod registry;
+pub use registry::*;
+mod rope;
+pub use rope::*;
+mod selection;
+pub use selection::*;
+mod self_intersect;
+pub use self_intersect::*;
+mod sketch;
+pub use sketch::*;
+mod snap;
+pub use snap::*;
+mod space;
+pub use space::*;
+mod spline;
+pub use spline::*;
+mod style_override;
+pub use style_override::*;
+mod svg;
+pub use svg::*;
+mod symmetry;
+pub use symmetry::*;
+mod touch;
+pub use touch::*;
+mod track;
+pub use track::*;
+mod transform_ops;
+pub use transform_ops::*;
+mod trim;
+pub use trim::*;
+mod user_data;
+pub use user_data::*;
+mod weld;
+pub use weld::*;
+mod z_order;
+pub use z_order::*;
+mod zoom;
+pub use zoom::*;
+#[cfg(feature = "image")]
+mod snapshot;
+#[cfg(feature = "image")]
+pub use snapshot::*;
+#[cfg(feature = "serde")]
+mod json;
+#[cfg(feature = "serde")]
+pub use json::*;
+#[cfg(feature = "serde")]
+mod asset;
+#[cfg(feature = "serde")]
+pub use asset::*;
+#[cfg(feature = "egui")]
+mod egui_panel;
+#[cfg(feature = "egui")]
+pub use egui_panel::*;
+#[cfg(feature = "ttf")]
+mod glyph;
+#[cfg(feature = "ttf")]
+pub use glyph::*;
+#[cfg(feature = "clipboard")]
+mod clipboard;
+#[cfg(feature = "clipboard")]
+pub use clipboard::*;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+
+static BEZIER_ID: Mutex<usize> = Mutex::new(0);
+
+#[deprecated(note = "use the `BezierIdAllocator` resource, which is per-world")]
+pub fn new_id() -> usize {
+    let mut bezier_id = BEZIER_ID.lock().unwrap();
+    *bezier_id += 1;
+    *bezier_id
+}
+
+/// Makes `new_id` return at least `next` from now on, e.g. after loading
+/// curves with existing ids. Never moves the counter backwards.
+#[deprecated(note = "use `BezierIdAllocator::set_next_id`")]
+pub fn set_next_id(next: usize) {
+    let mut bezier_id = BEZIER_ID.lock().unwrap();
+    *bezier_id = (*bezier_id).max(next.saturating_sub(1));
+}
+
+/// Hands out curve ids. Each `App` gets its own sequence starting at 1, so
+/// separate worlds (and headless tests) don't share or race on ids.
+#[derive(Resource, Debug, Default)]
+pub struct BezierIdAllocator {
+    last: usize,
+}
+
+impl BezierIdAllocator {
+    pub fn next_id(&mut self) -> usize {
+        self.last += 1;
+        self.last
+    }
+    /// Makes `next_id` return at least `next` from now on, e.g. after loading
+    /// curves with existing ids. Never moves the counter backwards.
+    pub fn set_next_id(&mut self, next: usize) {
+        self.last = self.last.max(next.saturating_sub(1));
+    }
+}
+
+/// Plain value form of one curve, detached from its entities.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BezierCurve {
+    pub id: usize,
+    pub a: Vec2,
+    pub b: Vec2,
+    pub c: Vec2,
+    pub d: Vec2,
+}
+
+impl BezierCurve {
+    pub fn points(&self) -> [Vec2; 4] {
+        [self.a, self.b, self.c, self.d]
+    }
+    pub fn cubic(&self) -> CubicBezier {
+        CubicBezier::from(self.points())
+    }
+}
+
+#[derive(Clone, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BezierShape {
+    pub shape_type: BezierShapeType,
+    pub id: usize,
+    pub point: Option<Vec2>,
+    pub closed: bool,
+    /// Position of this part in the list built by `bezier_shapes`, used to
+    /// rebuild the part in place.
+    pub index: usize,
+    /// Points `a`, `b`, `c` and `d` of a `Handles` part, which draws all of
+    /// them at once.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub handles: Option<[Vec2; 4]>,
+}
+
+impl BezierShape {
+    /// The part a pointer at `position` is on: for a `Handles` part the
+    /// point nearest to it, otherwise this part's own type.
+    pub fn part_at(&self, position: Vec2) -> BezierShapeType {
+        let Some(handles) = self.handles else {
+            return self.shape_type.clone();
+        };
+        [
+            BezierShapeType::Start,
+            BezierShapeType::ControlStart,
+            BezierShapeType::ControlEnd,
+            BezierShapeType::End,
+        ]
+        .into_iter()
+        .zip(handles)
+        .min_by(|(_, p), (_, q)| {
+            p.distance_squared(position)
+                .total_cmp(&q.distance_squared(position))
+        })
+        .map_or(BezierShapeType::Start, |(part, _)| part)
+    }
+    /// The point of `part` on this shape, looking inside `handles` for a
+    /// `Handles` part.
+    pub fn point_of(&self, part: &BezierShapeType) -> Option<Vec2> {
+        let Some([a, b, c, d]) = self.handles else {
+            return self.point;
+        };
+        match part {
+            BezierShapeType::Start => Some(a),
+            BezierShapeType::ControlStart => Some(b),
+            BezierShapeType::ControlEnd => Some(c),
+            BezierShapeType::End => Some(d),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BezierShapeType {
+    #[default]
+    Start,
+    ControlStart,
+    ControlEnd,
+    End,
+    Line,
+    BezierLine,
+    /// The single control point of a quadratic curve.
+    Control,
+    /// All four points of a curve drawn as one entity, see
+    /// `BezierStyle::batch_handles`. Which point a pointer grabbed is
+    /// resolved by distance, see `BezierShape::part_at`.
+    Handles,
+    /// Square whose drag moves the whole curve, see `BezierMoveGrip`.
+    MoveGrip,
+}
+
+impl std::fmt::Display for BezierShapeType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BezierShapeType::Start => write!(f, "Start"),
+            BezierShapeType::ControlStart => write!(f, "ControlStart"),
+            BezierShapeType::ControlEnd => write!(f, "ControlEnd"),
+            BezierShapeType::End => write!(f, "End"),
+            BezierShapeType::Line => write!(f, "Line"),
+            BezierShapeType::BezierLine => write!(f, "BezierLine"),
+            BezierShapeType::Control => write!(f, "Control"),
+            BezierShapeType::Handles => write!(f, "Handles"),
+            BezierShapeType::MoveGrip => write!(f, "MoveGrip"),
+        }
+    }
+}
+
+/// In-progress drags keyed by pointer, so each finger on a touch screen (or
+/// the mouse) drags its own point independently.
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource)]
+pub struct BezierDrag {
+    pub pointers: HashMap<PointerId, BezierDragState>,
+    /// Pointers whose press turned into a drag, from `Pointer<DragStart>`
+    /// to `Pointer<DragEnd>`, whether or not a curve was dragged. Bevy
+    /// still sends `Pointer<Click>` on release before ending the drag.
+    #[reflect(ignore)]
+    pub dragged: HashSet<PointerId>,
+}
+
+impl BezierDrag {
+    pub fn get(&self, pointer_id: &PointerId) -> Option<&BezierDragState> {
+        self.pointers.get(pointer_id)
+    }
+    pub fn get_mut(&mut self, pointer_id: &PointerId) -> Option<&mut BezierDragState> {
+        self.pointers.get_mut(pointer_id)
+    }
+    /// Whether a `Pointer<Click>` of `pointer_id` is the release of a drag,
+    /// which click gestures ignore.
+    pub fn click_ends_drag(&self, pointer_id: &PointerId) -> bool {
+        self.dragged.contains(pointer_id)
+    }
+    /// Whether any pointer is currently dragging part of the curve `bezier_id`.
+    pub fn is_dragging(&self, bezier_id: usize) -> bool {
+        self.pointers
+            .values()
+            .any(|state| state.bezier_id == bezier_id)
+    }
+}
+
+#[derive(Clone, Default, Reflect)]
+pub struct BezierDragState {
+    pub bezier_id: usize,
+    pub entity: Option<Entity>,
+    pub dragging: BezierShapeType,
+    pub start_click: Option<Vec2>,
+    /// Camera the drag started under, which converts pointer movement to
+    /// world space.
+    pub camera: Option<Entity>,
+    pub a: Option<Vec2>,
+    pub b: Option<Vec2>,
+    pub c: Option<Vec2>,
+    pub d: Option<Vec2>,
+    pub closed: bool,
+    /// Points of the curve when the drag started.
+    pub start_points: Option<[Vec2; 4]>,
+    /// World-space pointer movement accumulated since the drag started.
+    pub total_delta: Vec2,
+    /// Movement is held to the dominant axis of `total_delta`, e.g. while
+    /// Shift is held.
+    pub axis_constrained: bool,
+    /// Axis the drag is held to, picked when the constraint first applies
+    /// and kept until it is released.
+    pub locked_axis: Option<DragAxis>,
+    /// The curve is a quadratic; `b` and `c` are its elevated control.
+    pub quadratic: bool,
+    /// Other selected curves moving with this drag.
+    pub group: Vec<BezierGroupMember>,
+    /// Constraint on how a dragged control handle moves around its anchor.
+    pub handle_mode: HandleDragMode,
+    /// Another curve's anchor the dragged anchor currently sits on.
+    pub anchor_snap: Option<AnchorSnap>,
+    /// The curve's `BezierBounds`, which the dragged points stay inside.
+    pub bounds: Option<Rect>,
+    /// The curve is a `BezierFunction`, kept a function of x as it is
+    /// dragged, see `constrain_function`.
+    pub function: bool,
+    /// Where the curve body was grabbed, if it was grabbed by its body
+    /// rather than moved with `BezierInputMap::whole_curve_keys`.
+    pub grab_point: Option<Vec2>,
+    /// Other curves the drag moved, e.g. spline neighbours or segments
+    /// `enforce_spline_continuity` realigned, by id, recorded with the drag
+    /// when it ends.
+    #[reflect(ignore)]
+    pub followers: BTreeMap<usize, BezierEdit>,
+}
+
+/// How a dragged control handle may move relative to its anchor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum HandleDragMode {
+    #[default]
+    Free,
+    /// Swings around the anchor, keeping the handle's length.
+    Rotate,
+    /// Slides along the handle's direction, changing only its length.
+    Extend,
+}
+
+impl HandleDragMode {
+    /// Where `handle`, attached to `anchor`, ends up when dragged by `delta`.
+    pub fn moved(self, anchor: Vec2, handle: Vec2, delta: Vec2) -> Vec2 {
+        let target = handle + delta;
+        let Some(direction) = (handle - anchor).try_normalize() else {
+            return target;
+        };
+        match self {
+            HandleDragMode::Free => target,
+            HandleDragMode::Rotate => match (target - anchor).try_normalize() {
+                Some(swung) => anchor + swung * handle.distance(anchor),
+                None => handle,
+            },
+            HandleDragMode::Extend => {
+                anchor + direction * (target - anchor).dot(direction).max(0.0)
+            }
+        }
+    }
+}
+
+/// Axis a constrained drag is latched to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+pub enum DragAxis {
+    Horizontal,
+    Vertical,
+}
+
+impl BezierDragState {
+    pub fn points(&self) -> Option<[Vec2; 4]> {
+        Some([self.a?, self.b?, self.c?, self.d?])
+    }
+    pub fn clear_drag(&mut self) {
+        self.bezier_id = 0;
+        self.entity = None;
+        self.start_click = None;
+        self.a = None;
+        self.b = None;
+        self.c = None;
+        self.d = None;
+        self.closed = false;
+        self.start_points = None;
+        self.total_delta = Vec2::ZERO;
+        self.axis_constrained = false;
+        self.locked_axis = None;
+        self.quadratic = false;
+        self.group.clear();
+        self.handle_mode = HandleDragMode::Free;
+        self.anchor_snap = None;
+        self.bounds = None;
+        self.function = false;
+        self.grab_point = None;
+        self.followers.clear();
+    }
+    /// Adds curves that moved along with the drag to `followers`, keeping
+    /// the points each had before it first moved.
+    pub fn follow(&mut self, edits: impl IntoIterator<Item = BezierEdit>) {
+        for edit in edits {
+            self.followers
+                .entry(edit.id)
+                .and_modify(|follower| follower.after = edit.after)
+                .or_insert(edit);
+        }
+    }
+    /// The axis along which the pointer has moved furthest since the drag
+    /// started.
+    pub fn dominant_axis(&self) -> DragAxis {
+        if self.total_delta.x.abs() >= self.total_delta.y.abs() {
+            DragAxis::Horizontal
+        } else {
+            DragAxis::Vertical
+        }
+    }
+    /// Holds the drag to an axis or releases it. The axis is the dominant
+    /// one when the constraint first applies, and stays put afterwards so a
+    /// drag that turns can't jump from one axis to the other.
+    pub fn constrain_axis(&mut self, constrained: bool) {
+        self.axis_constrained = constrained;
+        if !constrained {
+            self.locked_axis = None;
+        } else if self.locked_axis.is_none() && self.total_delta != Vec2::ZERO {
+            self.locked_axis = Some(self.dominant_axis());
+        }
+    }
+    /// `delta` with the component off the locked axis dropped while
+    /// `axis_constrained`, otherwise unchanged. Without a locked axis, e.g.
+    /// for a `MovePoint` command, the dominant axis is used.
+    pub fn constrain_to_axis(&self, delta: Vec2) -> Vec2 {
+        if !self.axis_constrained {
+            return delta;
+        }
+        match self.locked_axis.unwrap_or_else(|| self.dominant_axis()) {
+            DragAxis::Horizontal => Vec2::new(delta.x, 0.0),
+            DragAxis::Vertical => Vec2::new(0.0, delta.y),
+        }
+    }
+    /// Moves the dragged part by `delta` in world units, held to the
+    /// locked axis while `axis_constrained`. A dragged handle moves under
+    /// `handle_mode` around its anchor.
+    pub fn add_delta(&mut self, delta: Vec2) {
+        let delta = self.constrain_to_axis(delta);
+        let control = match (self.quadratic, self.points()) {
+            (true, Some([a, b, c, d])) => Some(reduce_to_quadratic(a, b, c, d)),
+            _ => None,
+        };
+        let moved = |point: &mut Option<Vec2>| {
+            if let Some(point) = point {
+                *point += delta;
+            }
+        };
+        let mode = self.handle_mode;
+        let bounds = self.bounds;
+        let handle = |anchor: Option<Vec2>, point: &mut Option<Vec2>| {
+            if let (Some(anchor), Some(point)) = (anchor, point) {
+                *point = mode.moved(anchor, *point, delta);
+                // swinging or sliding a handle can carry it past a bounded
+                // delta
+                if let Some(bounds) = bounds {
+                    *point = point.max(bounds.min).min(bounds.max);
+                }
+            }
+        };
+        match self.dragging {
+            BezierShapeType::Start => moved(&mut self.a),
+            BezierShapeType::ControlStart => handle(self.a, &mut self.b),
+            BezierShapeType::ControlEnd => handle(self.d, &mut self.c),
+            BezierShapeType::End => moved(&mut self.d),
+            BezierShapeType::Line | BezierShapeType::Handles | BezierShapeType::MoveGrip => {}
+            BezierShapeType::BezierLine => {
+                moved(&mut self.a);
+                moved(&mut self.b);
+                moved(&mut self.c);
+                moved(&mut self.d);
+            }
+            BezierShapeType::Control => {}
+        }
+        // keep a quadratic quadratic: move its control, then re-elevate
+        if let (Some(q), Some(a), Some(d)) = (control, self.a, self.d) {
+            let q = match self.dragging {
+                BezierShapeType::Control | BezierShapeType::BezierLine => q + delta,
+                _ => q,
+            };
+            let (b, c) = elevate_quadratic(a, q, d);
+            self.b = Some(b);
+            self.c = Some(c);
+        }
+    }
+}
+
+#[derive(Clone, Component, Reflect)]
+#[reflect(Component)]
+pub enum ShapeType {
+    Intersection,
+    Main,
+    Sketch,
+    Marquee,
+    Bezier(BezierShape),
+}
+
+impl std::fmt::Display for ShapeType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShapeType::Intersection => write!(f, "Intersection"),
+            ShapeType::Main => write!(f, "Main"),
+            ShapeType::Sketch => write!(f, "Sketch"),
+            ShapeType::Marquee => write!(f, "Marquee"),
+            ShapeType::Bezier(_bezier_shape) => write!(f, "Bezier"),
+        }
+    }
+}
+
+#[derive(Resource, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct BezierStyle {
+    pub intersection_color: Color,
+    pub intersection_radius: f32,
+    pub sketch_color: Color,
+    pub bezier_stroke_width: f32,
+    pub sketch_stroke_width: f32,
+    pub bezier_line_color: Color,
+    pub fill_color: Color,
+    #[reflect(ignore, default = "default_fill_rule")]
+    pub fill_rule: FillRule,
+    /// Color of the handle or curve under the pointer.
+    pub hover_color: Color,
+    pub start_cap: BezierCap,
+    pub end_cap: BezierCap,
+    /// Length of an arrowhead, or width of a circle or square cap.
+    pub cap_size: f32,
+    pub cap_color: Color,
+    /// Dash pattern (alternating on and off lengths) of the guide lines
+    /// between anchors and controls, solid when `None`.
+    pub sketch_dash: Option<Vec<f32>>,
+    /// Dash pattern of open curves. Closed curves stay solid.
+    pub curve_dash: Option<Vec<f32>>,
+    pub layering: BezierLayering,
+    /// Extra distance around the curve and guide lines that still grabs
+    /// them, so thin strokes don't need pixel-perfect clicks.
+    pub pick_tolerance: f32,
+    /// Hit radius around curves for touch and pen pointers, in logical
+    /// pixels so it matches a fingertip at any DPI or zoom, see
+    /// `bezier_touch_picking`.
+    pub touch_hit_radius: f32,
+    pub label_color: Color,
+    pub label_font_size: f32,
+    /// Draw each curve's id next to its start, see `BezierIdLabel`.
+    pub show_labels: bool,
+    /// Draw the names (`a`, `b`, `c`, `d`) of each curve's points.
+    pub show_point_labels: bool,
+    /// Curvature comb drawn along each curve, hidden when `None`.
+    pub curvature_comb: Option<CurvatureComb>,
+    /// Per-curve style changes, see `BezierStyleOverride`.
+    pub overrides: HashMap<usize, BezierStyleOverride>,
+    /// Draw a cubic curve's guide lines as one entity and its four points
+    /// as one `Handles` entity, instead of an entity each, which adds up in
+    /// scenes with many curves. Set it before spawning: parts of batched
+    /// curves can't be locked, welded or attached to one by one.
+    pub batch_handles: bool,
+    /// Square drawn with each curve that drags the whole curve, hidden when
+    /// `None`, see `BezierMoveGrip`.
+    pub move_grip: Option<BezierMoveGrip>,
+    /// Shape and color of the anchor and control markers, see
+    /// `BezierHandleMarkers`.
+    pub handle_markers: BezierHandleMarkers,
+    /// How far from an anchor or control, in world units, the pointer
+    /// still grabs it, so small markers needn't be hit exactly. Only
+    /// widens the grab beyond the marker; not used by `batch_handles`
+    /// curves. The hit circles are only built for radii larger than the
+    /// marker, so raising it past the marker applies to curves spawned
+    /// afterwards.
+    pub pick_radius: f32,
+}
+
+/// Depths curves render at. Curve `id` sits at `base_z` plus its layer times
+/// `layer_spacing`, and its parts are stacked by role within that.
+#[derive(Clone, Debug, Reflect)]
+pub struct BezierLayering {
+    pub base_z: f32,
+    pub layer_spacing: f32,
+    pub fill: f32,
+    pub guide_line: f32,
+    pub curve: f32,
+    pub control: f32,
+    pub anchor: f32,
+    pub label: f32,
+    /// Layer per curve id; curves not listed are on layer 0.
+    pub layers: HashMap<usize, i32>,
+    /// Brings a curve to the front when a drag starts on it, see
+    /// `bring_to_front`.
+    pub raise_dragged: bool,
+}
+
+impl Default for BezierLayering {
+    fn default() -> Self {
+        Self {
+            base_z: 0.0,
+            layer_spacing: 0.1,
+            fill: -0.01,
+            guide_line: 0.0,
+            curve: 0.01,
+            control: 0.02,
+            anchor: 0.03,
+            label: 0.04,
+            layers: HashMap::new(),
+            raise_dragged: true,
+        }
+    }
+}
+
+impl BezierLayering {
+    pub fn set_layer(&mut self, id: usize, layer: i32) {
+        self.layers.insert(id, layer);
+    }
+    /// Z of curve `id`'s layer, before the per-part offsets.
+    pub fn layer_z(&self, id: usize) -> f32 {
+        let layer = self.layers.get(&id).copied().unwrap_or(0);
+        self.base_z + layer as f32 * self.layer_spacing
+    }
+    /// Z for a part of curve `id`, so stacking is decided by role rather
+    /// than by the order `bezier_open` happens to build the shapes in.
+    pub fn z_for(&self, id: usize, shape_type: &BezierShapeType) -> f32 {
+        self.layer_z(id)
+            + match shape_type {
+                BezierShapeType::Line => self.guide_line,
+                BezierShapeType::BezierLine => self.curve,
+                BezierShapeType::ControlStart
+                | BezierShapeType::ControlEnd
+                | BezierShapeType::Control => self.control,
+                BezierShapeType::Start
+                | BezierShapeType::End
+                | BezierShapeType::Handles
+                | BezierShapeType::MoveGrip => self.anchor,
+            }
+    }
+}
+
+/// Restyles everything already spawned when `BezierStyle` is edited, e.g.
+/// from an inspector or to keep stroke widths constant on screen while
+/// zooming: curves are rebuilt and intersection markers recolored.
+pub fn restyle_beziers(
+    style: Res<BezierStyle>,
+    mut query: Query<(Entity, &mut Shape, &mut ShapeType)>,
+) {
+    if !style.is_changed() || style.is_added() {
+        return;
+    }
+    let curves = collect_bezier_points(query.iter().map(|(_, _, shape_type)| shape_type));
+    for (id, points) in curves {
+        update_bezier_in_place(&style, &mut query, id, points);
+    }
+    for (_entity, mut shape, shape_type) in query.iter_mut() {
+        if !matches!(*shape_type, ShapeType::Intersection) {
+            continue;
+        }
+        if let Some(fill) = shape.fill.as_mut() {
+            fill.color = style.intersection_color;
+        }
+    }
+}
+
+/// Moves already spawned curves to new depths when the layering changes.
+pub fn apply_bezier_layering(
+    style: Res<BezierStyle>,
+    mut shapes: Query<(&ShapeType, &mut Transform)>,
+    mut fills: Query<(&BezierSpline, &mut Transform), Without<ShapeType>>,
+) {
+    if !style.is_changed() {
+        return;
+    }
+    let layering = &style.layering;
+    for (shape_type, mut transform) in shapes.iter_mut() {
+        if let ShapeType::Bezier(bezier_shape) = shape_type {
+            transform.translation.z = layering.z_for(bezier_shape.id, &bezier_shape.shape_type);
+        }
+    }
+    for (spline, mut transform) in fills.iter_mut() {
+        if let Some(&id) = spline.segments.first() {
+            transform.translation.z = layering.layer_z(id) + layering.fill;
+        }
+    }
+}
+
+fn default_fill_rule() -> FillRule {
+    FillRule::NonZero
+}
+
+impl Default for BezierStyle {
+    fn default() -> Self {
+        Self {
+            intersection_color: Color::srgba(1.0, 0.0, 0.0, 1.0),
+            sketch_color: Color::srgba(0.5, 0.5, 0.5, 1.0),
+            intersection_radius: 6.0,
+            bezier_stroke_width: 4.0,
+            sketch_stroke_width: 1.0,
+            bezier_line_color: Color::srgba_u8(200, 172, 110, 255),
+            fill_color: Color::srgba_u8(200, 172, 110, 80),
+            fill_rule: default_fill_rule(),
+            hover_color: Color::srgba(1.0, 0.9, 0.3, 1.0),
+            start_cap: BezierCap::None,
+            end_cap: BezierCap::None,
+            cap_size: 12.0,
+            cap_color: Color::srgba_u8(200, 172, 110, 255),
+            sketch_dash: None,
+            curve_dash: None,
+            layering: BezierLayering::default(),
+            pick_tolerance: 6.0,
+            touch_hit_radius: 22.0,
+            label_color: Color::srgba(0.9, 0.9, 0.9, 1.0),
+            label_font_size: 14.0,
+            show_labels: false,
+            show_point_labels: false,
+            curvature_comb: None,
+            overrides: HashMap::new(),
+            batch_handles: false,
+            move_grip: None,
+            handle_markers: BezierHandleMarkers::default(),
+            pick_radius: 0.0,
+        }
+    }
+}
+
+/// Adds the curve editing systems and resources, starting them from its
+/// fields so hosts configure the crate here rather than overwriting the
+/// resources after the plugin inserted its defaults.
+#[derive(Clone)]
+pub struct BezierPlugin {
+    /// Initial `BezierStyle`.
+    pub style: BezierStyle,
+    /// Initial `BezierInputMap`. Set its `drag_button` to keep the other
+    /// pointer buttons free for e.g. camera controls.
+    pub input_map: BezierInputMap,
+    /// Let handles win over anchors and anchors over the curve body when
+    /// drags grab overlapping parts, see `BezierPickPriority`.
+    pub pick_priority: bool,
+    /// Attach the editing observers to `ShapeType::Bezier` entities the
+    /// host spawned without them, see `observe_new_bezier_parts`. Curves
+    /// spawned by the crate get them either way.
+    pub auto_observers: bool,
+    /// The editing and undo shortcuts of `BezierEditSettings` and
+    /// `BezierHistory`.
+    pub keybindings: bool,
+}
+
+impl Default for BezierPlugin {
+    fn default() -> Self {
+        Self {
+            style: BezierStyle::default(),
+            input_map: BezierInputMap::default(),
+            pick_priority: true,
+            auto_observers: true,
+            keybindings: true,
+        }
+    }
+}
+
+impl BezierPlugin {
+    pub fn with_style(mut self, style: BezierStyle) -> Self {
+        self.style = style;
+        self
+    }
+    pub fn with_input_map(mut self, input_map: BezierInputMap) -> Self {
+        self.input_map = input_map;
+        self
+    }
+    pub fn with_drag_button(mut self, button: PointerButton) -> Self {
+        self.input_map.drag_button = button;
+        self
+    }
+    pub fn with_pick_priority(mut self, enabled: bool) -> Self {
+        self.pick_priority = enabled;
+        self
+    }
+    pub fn with_auto_observers(mut self, enabled: bool) -> Self {
+        self.auto_observers = enabled;
+        self
+    }
+    pub fn with_keybindings(mut self, enabled: bool) -> Self {
+        self.keybindings = enabled;
+        self
+    }
+    /// Sets the depth curves are drawn at, `BezierLayering::base_z`.
+    pub fn with_layer_z(mut self, z: f32) -> Self {
+        self.style.layering.base_z = z;
+        self
+    }
+}
+
+impl Plugin for BezierPlugin {
+    fn build(&self, app: &mut App) {
+        let auto_observers = self.auto_observers;
+        let mut history = BezierHistory::default();
+        history.keybindings = self.keybindings;
+        app.insert_resource(self.style.clone())
+            .insert_resource(BezierDrag::default())
+            .insert_resource(self.input_map.clone())
+            .insert_resource(BezierPickPriority {
+                enabled: self.pick_priority,
+                ..default()
+            })
+            .insert_resource(BezierEditSettings {
+                keybindings: self.keybindings,
+                ..default()
+            })
+            .insert_resource(history)
+            .init_resource::<BezierIdAllocator>()
+            .init_resource::<BezierDocument>()
+            .init_resource::<BezierRegistry>()
+            .init_resource::<BezierPointIndex>()
+            .init_resource::<BezierLayers>()
+            .init_resource::<BezierScreenSizes>()
+            .init_resource::<BezierSelfIntersectionWarnings>()
+            .init_resource::<IntersectionWatch>()
+            .init_resource::<BezierReference>()
+            .init_resource::<BezierContinuitySettings>()
+            .init_resource::<BezierHandleCulling>()
+            .init_resource::<BezierCommands>()
+            .init_resource::<BezierClickTracker>()
+            .init_resource::<BezierNudgeSettings>()
+            .init_resource::<BezierSelection>()
+            .init_resource::<BezierSnapSettings>()
+            .init_resource::<BezierCreateMode>()
+            .init_resource::<BezierSketchMode>()
+            .init_resource::<BezierMarqueeMode>()
+            .init_resource::<BezierTransformGizmo>()
+            .init_resource::<BezierTrimMode>()
+            .init_resource::<BezierEditMode>()
+            .init_resource::<BezierRenderBackend>()
+            .init_resource::<Bezier3dSettings>()
+            .init_resource::<Bezier3dDrag>()
+            .register_type::<BezierShape>()
+            .register_type::<BezierShapeType>()
+            .register_type::<ShapeType>()
+            .register_type::<BezierStyle>()
+            .register_type::<BezierStyleOverride>()
+            .register_type::<BezierDrag>()
+            .register_type::<FollowBezier>()
+            .register_type::<BezierEasing>()
+            .register_type::<BezierFunction>()
+            .register_type::<AnchorAttachedTo>()
+            .register_type::<Locked>()
+            .register_type::<BezierBounds>()
+            .register_type::<BezierReference>()
+            .register_type::<BezierContinuitySettings>()
+            .register_type::<BezierHandleCulling>()
+            .register_type::<BezierPickPriority>()
+            .register_type::<BezierMoveGrip>()
+            .register_type::<BezierSymmetry>()
+            .register_type::<BezierLayer>()
+            .register_type::<BezierUserData>()
+            .register_type::<BezierLayers>()
+            .register_type::<BezierScreenSizes>()
+            .register_type::<BezierSelfIntersectionWarnings>()
+            .register_type::<IntersectionWatch>()
+            .register_type::<BezierIntersectionMarker>()
+            .register_type::<WeldedTo>()
+            .register_type::<BezierRope>()
+            .register_type::<BezierProbe>()
+            .register_type::<BezierMorph>()
+            .register_type::<BezierTrack>()
+            .register_type::<BezierSpace>()
+            .register_type::<Bezier3d>()
+            .register_type::<Bezier3dSettings>()
+            .register_type::<BezierEditMode>()
+            .register_type::<BezierRenderBackend>()
+            .add_event::<TransformBezier>()
+            .add_event::<BezierModified>()
+            .add_event::<BezierDragStarted>()
+            .add_event::<BezierDragEnded>()
+            .add_event::<BezierDragCancelled>()
+            .add_event::<BezierCommandApplied>()
+            .add_event::<BezierAnchorSnapped>()
+            .add_event::<BezierContextMenuEvent>()
+            .add_systems(
+                Update,
+                (
+                    sync_bezier_document,
+                    bezier_history_keys,
+                    apply_bezier_transforms,
+                    update_bezier_labels,
+                    update_closed_spline_fills,
+                    apply_bezier_layering,
+                    (sync_bezier_style_overrides, restyle_beziers).chain(),
+                    nudge_selected_bezier_point,
+                    toggle_bezier_grid_snap,
+                    sync_ui_bezier_roots,
+                    sync_bezier_reference,
+                    (drag_bezier3d_points, draw_bezier3d_gizmos).chain(),
+                    bezier_edit_keys,
+                    follow_beziers,
+                    constrain_easing_beziers,
+                    follow_attached_anchors,
+                    apply_bezier_edit_mode,
+                    (bezier_pen_tool, update_bezier_pen_preview).chain(),
+                    (bezier_sketch_tool, update_bezier_sketch_stroke).chain(),
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    apply_bezier_commands
+                        .after(nudge_selected_bezier_point)
+                        .after(bezier_edit_keys),
+                    draw_bezier_anchor_snaps,
+                    apply_bezier_layers
+                        .after(sync_bezier_style_overrides)
+                        .before(restyle_beziers),
+                    apply_bezier_screen_sizes.before(restyle_beziers),
+                    weld_snapped_anchors,
+                    sag_bezier_ropes,
+                    animate_bezier_morphs,
+                    play_bezier_tracks,
+                    cancel_bezier_drags,
+                    enforce_spline_continuity.after(apply_bezier_commands),
+                    mirror_symmetric_beziers
+                        .after(apply_bezier_commands)
+                        .after(apply_bezier_transforms)
+                        .after(bezier_history_keys),
+                    (
+                        bezier_transform_gizmo_tool,
+                        bezier_marquee_tool,
+                        update_bezier_marquee_rect,
+                    )
+                        .chain(),
+                    draw_bezier_transform_gizmo,
+                    bezier_context_menu,
+                    bring_dragged_beziers_to_front,
+                    constrain_function_beziers.after(apply_bezier_commands),
+                    cull_bezier_handles.after(apply_bezier_edit_mode),
+                ),
+            )
+            .add_systems(
+                Update,
+                (apply_selected_bezier_marker, apply_bezier_hover)
+                    .chain()
+                    .after(apply_bezier_commands)
+                    .after(bezier_history_keys)
+                    .after(apply_bezier_transforms)
+                    .after(restyle_beziers)
+                    .after(nudge_selected_bezier_point)
+                    .run_if(resource_equals(BezierRenderBackend::Lyon)),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    update_bezier_registry,
+                    update_bezier_point_index.after(update_bezier_registry),
+                    apply_bezier_render_backend,
+                    draw_bezier_gizmos.after(update_bezier_registry),
+                    draw_bezier_probes.after(update_bezier_registry),
+                    draw_bezier_self_intersections.after(update_bezier_registry),
+                    sync_intersection_markers.after(update_bezier_registry),
+                    draw_bezier_continuity_violations.after(update_bezier_registry),
+                    update_bezier_id_labels
+                        .after(update_bezier_registry)
+                        .before(TransformSystem::TransformPropagate),
+                ),
+            )
+            .add_systems(
+                PreUpdate,
+                (
+                    observe_new_bezier_parts.run_if(move || auto_observers),
+                    update_bezier_aabbs,
+                    bezier_pick_prefilter,
+                )
+                    .chain()
+                    .before(PickSet::Backend),
+            )
+            .add_systems(PreUpdate, bezier_touch_picking.in_set(PickSet::Backend));
+        #[cfg(feature = "serde")]
+        app.init_asset::<BezierAsset>()
+            .init_asset_loader::<BezierAssetLoader>()
+            .add_systems(Update, sync_bezier_assets);
+        #[cfg(feature = "clipboard")]
+        app.add_systems(Update, bezier_clipboard_keys);
+    }
+}
+
+/// Bounding box of the whole curve (handles included), stored on every entity
+/// sharing the curve id so picking can skip curves far from the pointer.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct BezierAabb(pub Rect);
+
+/// Recomputes `BezierAabb` for curves whose entities were spawned or edited.
+pub fn update_bezier_aabbs(
+    mut commands: Commands,
+    added: Query<&ShapeType, Changed<ShapeType>>,
+    query: Query<(Entity, &ShapeType)>,
+    style: Res<BezierStyle>,
+) {
+    let ids: HashSet<usize> = added
+        .iter()
+        .filter_map(|shape_type| match shape_type {
+            ShapeType::Bezier(bezier_shape) => Some(bezier_shape.id),
+            _ => None,
+        })
+        .collect();
+    if ids.is_empty() {
+        return;
+    }
+
+    let mut points: HashMap<usize, Vec<Vec2>> = HashMap::new();
+    for (_entity, shape_type) in query.iter() {
+        if let ShapeType::Bezier(bezier_shape) = shape_type {
+            if !ids.contains(&bezier_shape.id) {
+                continue;
+            }
+            if let Some(point) = bezier_shape.point {
+                points.entry(bezier_shape.id).or_default().push(point);
+            }
+            if let Some(handles) = bezier_shape.handles {
+                points.entry(bezier_shape.id).or_default().extend(handles);
+            }
+        }
+    }
+
+    let grip_curves = match style.move_grip {
+        Some(_) => collect_bezier_points(query.iter().map(|(_, shape_type)| shape_type)),
+        None => BTreeMap::new(),
+    };
+    let margin = style.intersection_radius.max(style.pick_radius)
+        + style.bezier_stroke_width
+        + style.pick_tolerance;
+    for (entity, shape_type) in query.iter() {
+        let ShapeType::Bezier(bezier_shape) = shape_type else {
+            continue;
+        };
+        let bounds = match points.get(&bezier_shape.id).map(Vec::as_slice) {
+            Some(&[a, b, c, d]) => bezier_bounds(a, b, c, d),
+            Some(&[a, q, d]) => Rect::from_corners(a, d).union_point(q),
+            _ => continue,
+        };
+        let mut bounds = bounds.inflate(margin);
+        // a grip past the corner of the bounding box sits outside the box
+        if let (BezierShapeType::MoveGrip, Some(grip), Some(&curve)) = (
+            &bezier_shape.shape_type,
+            &style.move_grip,
+            grip_curves.get(&bezier_shape.id),
+        ) {
+            let square = Rect::from_center_size(grip.position(curve), Vec2::splat(grip.size));
+            bounds = bounds.union(square.inflate(margin));
+        }
+        commands.entity(entity).insert(BezierAabb(bounds));
+    }
+}
+
+/// Cheap rejection test run before the picking backends: curves whose box
+/// contains no pointer, and hidden or locked parts, are made non-pickable so
+/// lyon meshes aren't hit-tested. Boxes are grown by each pointer's
+/// `pointer_hit_radius`, so touches beside a curve still reach it.
+pub fn bezier_pick_prefilter(
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut shapes: Query<(
+        &BezierAabb,
+        &ShapeType,
+        &mut Pickable,
+        Option<&Visibility>,
+        Option<&BezierSpace>,
+    )>,
+    locks: BezierLocks,
+    style: Res<BezierStyle>,
+) {
+    let Some((camera, camera_transform)) = cameras.iter().find(|(camera, _)| camera.is_active)
+    else {
+        return;
+    };
+    let located: Vec<(&PointerId, Vec2)> = pointers
+        .iter()
+        .filter_map(|(pointer_id, pointer)| Some((pointer_id, pointer.location()?.position)))
+        .collect();
+    // UI curves are laid out in logical pixels, so their radius is too
+    let ui_pointers: Vec<(Vec2, f32)> = located
+        .iter()
+        .map(|&(pointer_id, position)| {
+            let radius = if is_touch_or_pen(pointer_id) {
+                style.touch_hit_radius
+            } else {
+                0.0
+            };
+            (position, radius)
+        })
+        .collect();
+    let world_pointers: Vec<(Vec2, f32)> = located
+        .iter()
+        .filter_map(|&(pointer_id, position)| {
+            let world = camera
+                .viewport_to_world_2d(camera_transform, position)
+                .ok()?;
+            let radius = pointer_hit_radius(&style, pointer_id, camera, camera_transform, position);
+            Some((world, radius))
+        })
+        .collect();
+
+    for (aabb, shape_type, mut pickable, visibility, space) in shapes.iter_mut() {
+        let pointers = match space {
+            Some(BezierSpace::Ui) => &ui_pointers,
+            _ => &world_pointers,
+        };
+        let near_pointer = pointers
+            .iter()
+            .any(|&(position, radius)| aabb.0.inflate(radius).contains(position));
+        let hidden = visibility == Some(&Visibility::Hidden);
+        let locked = match shape_type {
+            ShapeType::Bezier(bezier_shape) => {
+                locks.part_locked(bezier_shape.id, &bezier_shape.shape_type)
+            }
+            _ => false,
+        };
+        let wanted = if near_pointer && !hidden && !locked {
+            Pickable::default()
+        } else {
+            Pickable::IGNORE
+        };
+        if *pickable != wanted {
+            *pickable = wanted;
+        }
+    }
+}
+
+pub fn bezier_open(
+    style: &BezierStyle,
+    id: usize,
+    a: Vec2,
+    b: Vec2,
+    c: Vec2,
+    d: Vec2,
+) -> Vec<(Shape, ShapeType)> {
+    bezier_shapes(style, id, a, b, c, d, false)
+}
+
+/// Like `bezier_open` but the `End` point joins back to `Start` and the
+/// enclosed region is filled with `BezierStyle::fill_color`.
+pub fn bezier_closed(
+    style: &BezierStyle,
+    id: usize,
+    a: Vec2,
+    b: Vec2,
+    c: Vec2,
+    d: Vec2,
+) -> Vec<(Shape, ShapeType)> {
+    bezier_shapes(style, id, a, b, c, d, true)
+}
+
+/// Builds the shapes of an open or closed curve.
+pub fn bezier_shapes(
+    style: &BezierStyle,
+    id: usize,
+    a: Vec2,
+    b: Vec2,
+    c: Vec2,
+    d: Vec2,
+    closed: bool,
+) -> Vec<(Shape, ShapeType)> {
+    let style = &*style.for_curve(id);
+    let stroke = style.sketch_stroke_width;
+    let thick_stroke_width = style.bezier_stroke_width;
+    let mut shapes = Vec::new();
+    let bezier_color = style.bezier_line_color;
+
+    let path = ShapePath::new().move_to(a).cubic_bezier_to(b, c, d);
+    let curve = if closed {
+        ShapeBuilder::with(&path.close())
+            .fill(Fill {
+                options: FillOptions::default().with_fill_rule(style.fill_rule),
+                color: style.fill_color,
+            })
+            .stroke((bezier_color, thick_stroke_width))
+            .build()
+    } else {
+        dashed_curve_shape(style, [a, b, c, d]).unwrap_or_else(|| {
+            ShapeBuilder::with(&path)
+                .stroke((bezier_color, thick_stroke_width))
+                .build()
+        })
+    };
+    if style.batch_handles {
+        return batched_bezier_shapes(style, id, [a, b, c, d], closed, curve);
+    }
+
+    shapes.push((
+        style.handle_marker_shape(&BezierShapeType::Start, a, false),
+        ShapeType::Bezier(BezierShape {
+            shape_type: BezierShapeType::Start,
+            id,
+            closed,
+            index: 0,
+            point: Some(a),
+            handles: None,
+        }),
+    ));
+
+    shapes.push((
+        curve,
+        ShapeType::Bezier(BezierShape {
+            shape_type: BezierShapeType::BezierLine,
+            id,
+            closed,
+            index: 0,
+            point: None,
+            handles: None,
+        }),
+    ));
+
+    shapes.push((
+        guide_line_shape(style, a, b),
+        ShapeType::Bezier(BezierShape {
+            shape_type: BezierShapeType::Line,
+            id,
+            closed,
+            index: 0,
+            point: None,
+            handles: None,
+        }),
+    ));
+    shapes.push((
+        style.handle_marker_shape(&BezierShapeType::ControlStart, b, false),
+        ShapeType::Bezier(BezierShape {
+            shape_type: BezierShapeType::ControlStart,
+            id,
+            closed,
+            index: 0,
+            point: Some(b),
+            handles: None,
+        }),
+    ));
+    shapes.push((
+        guide_line_shape(style, b, c),
+        ShapeType::Bezier(BezierShape {
+            shape_type: BezierShapeType::Line,
+            id,
+            closed,
+            index: 0,
+            point: None,
+            handles: None,
+        }),
+    ));
+    shapes.push((
+        style.handle_marker_shape(&BezierShapeType::ControlEnd, c, false),
+        ShapeType::Bezier(BezierShape {
+            shape_type: BezierShapeType::ControlEnd,
+            id,
+            closed,
+            index: 0,
+            point: Some(c),
+            handles: None,
+        }),
+    ));
+
+    shapes.push((
+        guide_line_shape(style, c, d),
+        ShapeType::Bezier(BezierShape {
+            shape_type: BezierShapeType::Line,
+            id,
+            closed,
+            index: 0,
+            point: None,
+            handles: None,
+        }),
+    ));
+
+    shapes.push((
+        style.handle_marker_shape(&BezierShapeType::End, d, false),
+        ShapeType::Bezier(BezierShape {
+            shape_type: BezierShapeType::End,
+            id,
+            closed,
+            index: 0,
+            point: Some(d),
+            handles: None,
+        }),
+    ));
+
+    // invisible, wider copies of the thin strokes used only for picking
+    let hit_width = 2.0 * style.pick_tolerance;
+    if hit_width > 0.0 {
+        let hit_path = ShapePath::new().move_to(a).cubic_bezier_to(b, c, d);
+        shapes.push((
+            ShapeBuilder::with(&hit_path)
+                .stroke((Color::NONE, thick_stroke_width + hit_width))
+                .build(),
+            ShapeType::Bezier(BezierShape {
+                shape_type: BezierShapeType::BezierLine,
+                id,
+                point: None,
+                closed,
+                index: 0,
+                handles: None,
+            }),
+        ));
+        for (start, end) in [(a, b), (b, c), (c, d)] {
+            shapes.push((
+                ShapeBuilder::new()
+                    .add(&shapes::Line(start, end))
+                    .stroke((Color::NONE, stroke + hit_width))
+                    .build(),
+                ShapeType::Bezier(BezierShape {
+                    shape_type: BezierShapeType::Line,
+                    id,
+                    point: None,
+                    closed,
+                    index: 0,
+                    handles: None,
+                }),
+            ));
+        }
+    }
+
+    shapes.extend(cap_shapes(style, id, closed, [a, b, c, d]));
+    shapes.push(comb_shape(style, id, closed, [a, b, c, d]));
+    shapes.push(move_grip_shape(style, id, closed, [a, b, c, d]));
+    // last, so the parts before keep their index with or without them
+    shapes.extend(handle_hit_shapes(
+        style,
+        id,
+        closed,
+        &[
+            (BezierShapeType::Start, a),
+            (BezierShapeType::ControlStart, b),
+            (BezierShapeType::ControlEnd, c),
+            (BezierShapeType::End, d),
+        ],
+    ));
+
+    for (index, (_shape, shape_type)) in shapes.iter_mut().enumerate() {
+        if let ShapeType::Bezier(bezier_shape) = shape_type {
+            bezier_shape.index = index;
+        }
+    }
+    shapes
+}
+
+/// Builds the shapes of an open quadratic curve: anchors `a` and `d` and a
+/// single `Control` handle `q`.
+pub fn bezier_quadratic(
+    style: &BezierStyle,
+    id: usize,
+    a: Vec2,
+    q: Vec2,
+    d: Vec2,
+) -> Vec<(Shape, ShapeType)> {
+    bezier_quadratic_shapes(style, id, a, q, d, false)
+}
+
+/// Builds the shapes of an open or closed quadratic curve.
+pub fn bezier_quadratic_shapes(
+    style: &BezierStyle,
+    id: usize,
+    a: Vec2,
+    q: Vec2,
+    d: Vec2,
+    closed: bool,
+) -> Vec<(Shape, ShapeType)> {
+    let style = &*style.for_curve(id);
+    let stroke = style.sketch_stroke_width;
+    let thick_stroke_width = style.bezier_stroke_width;
+    let part = |shape_type: BezierShapeType, point: Option<Vec2>| {
+        ShapeType::Bezier(BezierShape {
+            shape_type,
+            id,
+            point,
+            closed,
+            index: 0,
+            handles: None,
+        })
+    };
+    let marker =
+        |part: BezierShapeType, center: Vec2| style.handle_marker_shape(&part, center, false);
+    let line = |start: Vec2, end: Vec2, color: Color, width: f32| {
+        ShapeBuilder::new()
+            .add(&shapes::Line(start, end))
+            .stroke((color, width))
+            .build()
+    };
+
+    let path = ShapePath::new().move_to(a).quadratic_bezier_to(q, d);
+    let curve = if closed {
+        ShapeBuilder::with(&path.close())
+            .fill(Fill {
+                options: FillOptions::default().with_fill_rule(style.fill_rule),
+                color: style.fill_color,
+            })
+            .stroke((style.bezier_line_color, thick_stroke_width))
+            .build()
+    } else {
+        let (b, c) = elevate_quadratic(a, q, d);
+        dashed_curve_shape(style, [a, b, c, d]).unwrap_or_else(|| {
+            ShapeBuilder::with(&path)
+                .stroke((style.bezier_line_color, thick_stroke_width))
+                .build()
+        })
+    };
+
+    let mut shapes = vec![
+        (
+            marker(BezierShapeType::Start, a),
+            part(BezierShapeType::Start, Some(a)),
+        ),
+        (curve, part(BezierShapeType::BezierLine, None)),
+        (
+            guide_line_shape(style, a, q),
+            part(BezierShapeType::Line, None),
+        ),
+        (
+            marker(BezierShapeType::Control, q),
+            part(BezierShapeType::Control, Some(q)),
+        ),
+        (
+            guide_line_shape(style, q, d),
+            part(BezierShapeType::Line, None),
+        ),
+        (
+            marker(BezierShapeType::End, d),
+            part(BezierShapeType::End, Some(d)),
+        ),
+    ];
+
+    // invisible, wider copies of the thin strokes used only for picking
+    let hit_width = 2.0 * style.pick_tolerance;
+    if hit_width > 0.0 {
+        let hit_path = ShapePath::new().move_to(a).quadratic_bezier_to(q, d);
+        shapes.push((
+            ShapeBuilder::with(&hit_path)
+                .stroke((Color::NONE, thick_stroke_width + hit_width))
+                .build(),
+            part(BezierShapeType::BezierLine, None),
+        ));
+        for (start, end) in [(a, q), (q, d)] {
+            shapes.push((
+                line(start, end, Color::NONE, stroke + hit_width),
+                part(BezierShapeType::Line, None),
+            ));
+        }
+    }
+
+    shapes.extend(cap_shapes(style, id, closed, [a, q, q, d]));
+    let (b, c) = elevate_quadratic(a, q, d);
+    shapes.push(comb_shape(style, id, closed, [a, b, c, d]));
+    shapes.push(move_grip_shape(style, id, closed, [a, b, c, d]));
+    // last, so the parts before keep their index with or without them
+    shapes.extend(handle_hit_shapes(
+        style,
+        id,
+        closed,
+        &[
+            (BezierShapeType::Start, a),
+            (BezierShapeType::Control, q),
+            (BezierShapeType::End, d),
+        ],
+    ));
+
+    for (index, (_shape, shape_type)) in shapes.iter_mut().enumerate() {
+        if let ShapeType::Bezier(bezier_shape) = shape_type {
+            bezier_shape.index = index;
+        }
+    }
+    shapes
+}
+
+/// Spawns a new open quadratic curve with a fresh id under a `BezierId` root.
+pub fn spawn_quadratic_bezier(
+    commands: &mut Commands,
+    style: &BezierStyle,
+    ids: &mut BezierIdAllocator,
+    a: Vec2,
+    q: Vec2,
+    d: Vec2,
+) -> BezierId {
+    let id = ids.next_id();
+    spawn_bezier_with_id(commands, style, id, bezier_quadratic(style, id, a, q, d));
+    BezierId(id)
+}
+
+/// Id of a curve, stored on the root entity its parts are parented to.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[require(Transform, Visibility)]
+pub struct BezierId(pub usize);
+
+/// Spawns a new open curve with a fresh id: a `BezierId` root entity with
+/// every pickable, draggable part of the curve as its children.
+pub fn spawn_bezier(
+    commands: &mut Commands,
+    style: &BezierStyle,
+    ids: &mut BezierIdAllocator,
+    a: Vec2,
+    b: Vec2,
+    c: Vec2,
+    d: Vec2,
+) -> BezierId {
+    let id = ids.next_id();
+    spawn_bezier_with_id(commands, style, id, bezier_open(style, id, a, b, c, d));
+    BezierId(id)
+}
+
+/// Spawns already built shapes of curve `id` under a new `BezierId` root,
+/// returning the root entity.
+pub fn spawn_bezier_with_id(
+    commands: &mut Commands,
+    style: &BezierStyle,
+    id: usize,
+    shapes: Vec<(Shape, ShapeType)>,
+) -> Entity {
+    let children = spawn_bezier_shapes(commands, style, shapes);
+    let mut root = commands.spawn(BezierId(id));
+    root.add_children(&children);
+    root.id()
+}
+
+/// Spawns the shapes built by `bezier_open`/`bezier_closed` as pickable,
+/// draggable entities at their role's z offset.
+pub fn spawn_bezier_shapes(
+    commands: &mut Commands,
+    style: &BezierStyle,
+    shapes: Vec<(Shape, ShapeType)>,
+) -> Vec<Entity> {
+    shapes
+        .into_iter()
+        .map(|(shape, shape_type)| {
+            let z = match &shape_type {
+                ShapeType::Bezier(bezier_shape) => style
+                    .layering
+                    .z_for(bezier_shape.id, &bezier_shape.shape_type),
+                _ => 0.0,
+            };
+            let mut entity = commands.spawn((
+                shape,
+                shape_type,
+                Pickable::default(),
+                Transform::from_xyz(0.0, 0.0, z),
+            ));
+            attach_bezier_observers(&mut entity);
+            entity.id()
+        })
+        .collect()
+}
+
+/// Marks a curve part whose editing observers are attached, see
+/// `attach_bezier_observers`.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct BezierObserved;
+
+/// Attaches the observers that make a curve part draggable, selectable and
+/// editable, for parts spawned without `spawn_bezier_shapes`. The plugin
+/// also does this for new `ShapeType::Bezier` entities that lack them,
+/// unless `BezierPlugin::auto_observers` is off.
+pub fn attach_bezier_observers(entity: &mut EntityCommands) {
+    entity
+        .insert(BezierObserved)
+        .observe(drag_start)
+        .observe(bezier_drag)
+        .observe(drag_end)
+        .observe(bezier_double_click_split)
+        .observe(bezier_double_click_anchor)
+        .observe(bezier_click_remove_anchor)
+        .observe(bezier_trim_click)
+        .observe(bezier_rope_drag)
+        .observe(select_bezier_point)
+        .observe(bezier_hover_over)
+        .observe(bezier_hover_out);
+}
+
+/// Attaches the editing observers to curve parts a host spawned itself.
+pub fn observe_new_bezier_parts(
+    mut commands: Commands,
+    added: Query<(Entity, &ShapeType), (Added<ShapeType>, Without<BezierObserved>)>,
+) {
+    for (entity, shape_type) in added.iter() {
+        if matches!(shape_type, ShapeType::Bezier(_)) {
+            attach_bezier_observers(&mut commands.entity(entity));
+        }
+    }
+}
+
+/// Gathers the `[Start, ControlStart, ControlEnd, End]` points of every curve
+/// from its anchor and control entities, keyed by id. Quadratics are elevated
+/// to cubics. Curves with a missing point are left out.
+pub fn collect_bezier_points<'a>(
+    shape_types: impl IntoIterator<Item = &'a ShapeType>,
+) -> BTreeMap<usize, [Vec2; 4]> {
+    let mut found: BTreeMap<usize, [Option<Vec2>; 5]> = BTreeMap::new();
+    for shape_type in shape_types {
+        let ShapeType::Bezier(bezier_shape) = shape_type else {
+            continue;
+        };
+        let index = match bezier_shape.shape_type {
+            BezierShapeType::Start => 0,
+            BezierShapeType::ControlStart => 1,
+            BezierShapeType::ControlEnd => 2,
+            BezierShapeType::End => 3,
+            BezierShapeType::Control => 4,
+            BezierShapeType::Handles => {
+                if let Some(handles) = bezier_shape.handles {
+                    let found = found.entry(bezier_shape.id).or_default();
+                    for (slot, point) in found.iter_mut().zip(handles) {
+                        *slot = Some(point);
+                    }
+                }
+                continue;
+            }
+            BezierShapeType::Line | BezierShapeType::BezierLine | BezierShapeType::MoveGrip => {
+                continue;
+            }
+        };
+        found.entry(bezier_shape.id).or_default()[index] = bezier_shape.point;
+    }
+    found
+        .into_iter()
+        .filter_map(|(id, [a, b, c, d, q])| {
+            let (a, d) = (a?, d?);
+            let (b, c) = match (b, c, q) {
+                (Some(b), Some(c), _) => (b, c),
+                (_, _, Some(q)) => elevate_quadratic(a, q, d),
+                _ => return None,
+            };
+            Some((id, [a, b, c, d]))
+        })
+        .collect()
+}
+
+/// The id of the curve nearest to `p` and its distance. Ties resolve to the
+/// lowest id.
+pub fn nearest_bezier(query: &Query<(Entity, &ShapeType)>, p: Vec2) -> Option<(usize, f32)> {
+    let mut nearest: Option<(usize, f32)> = None;
+    for (id, [a, b, c, d]) in collect_bezier_points(query.iter().map(|(_, s)| s)) {
+        let distance = distance_to_bezier(a, b, c, d, p);
+        if nearest.is_none_or(|(_, best)| distance < best) {
+            nearest = Some((id, distance));
+        }
+    }
+    nearest
+}
+
+/// Markers at the inflection points of a curve, styled like intersections.
+pub fn inflection_markers(
+    style: &BezierStyle,
+    a: Vec2,
+    b: Vec2,
+    c: Vec2,
+    d: Vec2,
+) -> Vec<(Shape, ShapeType)> {
+    let radius = style.intersection_radius * 0.5;
+    bezier_inflections(a, b, c, d)
+        .into_iter()
+        .map(|t| {
+            let center = evaluate_bezier(a, b, c, d, t);
+            (
+                ShapeBuilder::new()
+                    .add(&shapes::Circle { radius, center })
+                    .fill(style.intersection_color)
+                    .build(),
+                ShapeType::Intersection,
+            )
+        })
+        .collect()
+}
+
+/// Markers at intersection points, e.g. those found by `intersections`.
+pub fn intersection_markers(style: &BezierStyle, points: &[Vec2]) -> Vec<(Shape, ShapeType)> {
+    points
+        .iter()
+        .map(|&center| {
+            (
+                ShapeBuilder::new()
+                    .add(&shapes::Circle {
+                        radius: style.intersection_radius,
+                        center,
+                    })
+                    .fill(style.intersection_color)
+                    .build(),
+                ShapeType::Intersection,
+            )
+        })
+        .collect()
+}
+
+/// Spawns a mirror image of curve `id` under a fresh id, keeping whether it
+/// is closed or quadratic and its `BezierUserData`, returning that id, or
+/// `None` when the curve isn't found.
+pub fn spawn_mirrored_bezier(
+    commands: &mut Commands,
+    style: &BezierStyle,
+    ids: &mut BezierIdAllocator,
+    query: &Query<(Entity, &ShapeType)>,
+    id: usize,
+    axis_point: Vec2,
+    axis_dir: Vec2,
+) -> Option<usize> {
+    let [a, b, c, d] = *collect_bezier_points(query.iter().map(|(_, s)| s)).get(&id)?;
+    let (a, b, c, d) = mirror_bezier(a, b, c, d, axis_point, axis_dir);
+    let (mut closed, mut quadratic) = (false, false);
+    for (_, shape_type) in query.iter() {
+        if let ShapeType::Bezier(bezier_shape) = shape_type {
+            if bezier_shape.id == id {
+                closed |= bezier_shape.closed;
+                quadratic |= matches!(bezier_shape.shape_type, BezierShapeType::Control);
+            }
+        }
+    }
+    let mirrored_id = ids.next_id();
+    let shapes = if quadratic {
+        bezier_quadratic_shapes(
+            style,
+            mirrored_id,
+            a,
+            reduce_to_quadratic(a, b, c, d),
+            d,
+            closed,
+        )
+    } else {
+        bezier_shapes(style, mirrored_id, a, b, c, d, closed)
+    };
+    spawn_bezier_with_id(commands, style, mirrored_id, shapes);
+    copy_bezier_user_data(commands, id, mirrored_id);
+    Some(mirrored_id)
+}
+
+/// Spawns the parallel curve `distance` to the left of curve `id` (right
+/// when negative) as a spline of fresh curves, returning the spline entity,
+/// or `None` when the curve isn't found.
+pub fn spawn_offset_bezier(
+    commands: &mut Commands,
+    style: &BezierStyle,
+    ids: &mut BezierIdAllocator,
+    query: &Query<(Entity, &ShapeType)>,
+    id: usize,
+    distance: f32,
+) -> Option<Entity> {
+    let points = *collect_bezier_points(query.iter().map(|(_, s)| s)).get(&id)?;
+    let pieces = CubicBezier::from(points).offset(distance);
+    let mut spline_points = vec![pieces.first()?.a];
+    for piece in &pieces {
+        spline_points.extend([piece.b, piece.c, piece.d]);
+    }
+    spawn_bezier_spline(commands, style, ids, &spline_points)
+}
+
+/// Request to rotate/scale/translate every point of curve `id` at once.
+#[derive(Event, Clone, Debug)]
+pub struct TransformBezier {
+    pub id: usize,
+    pub transform: Transform,
+}
+
+pub fn apply_bezier_transforms(
+    mut events: EventReader<TransformBezier>,
+    mut modified: EventWriter<BezierModified>,
+    mut query: Query<(Entity, &mut Shape, &mut ShapeType)>,
+    style: Res<BezierStyle>,
+) {
+    if events.is_empty() {
+        return;
+    }
+    let mut curves = collect_bezier_points(query.iter().map(|(_, _, s)| s));
+    let mut changed = HashSet::new();
+    for event in events.read() {
+        let Some(points) = curves.get_mut(&event.id) else {
+            continue;
+        };
+        let (a, b, c, d) = transform_bezier(
+            (points[0], points[1], points[2], points[3]),
+            &event.transform,
+        );
+        *points = [a, b, c, d];
+        changed.insert(event.id);
+    }
+
+    for id in changed {
+        update_bezier_in_place(&style, &mut query, id, curves[&id]);
+        modified.write(BezierModified {
+            id,
+            points: curves[&id],
+        });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn drag_start(
+    click: Trigger<Pointer<DragStart>>,
+    query: Query<(Entity, &mut Shape, &ShapeType)>,
+    mut drag: ResMut<BezierDrag>,
+    mut started: EventWriter<BezierDragStarted>,
+    selection: Res<BezierSelection>,
+    mode: Res<BezierEditMode>,
+    touches: Res<Touches>,
+    keys: Res<ButtonInput<KeyCode>>,
+    input: Res<BezierInputMap>,
+    ropes: Query<&BezierId, With<BezierRope>>,
+    locks: BezierLocks,
+    roots: Query<(&BezierId, Option<&BezierBounds>, Has<BezierFunction>)>,
+    priority: Res<BezierPickPriority>,
+    index: Res<BezierPointIndex>,
+    style: Res<BezierStyle>,
+) {
+    if click.button != input.drag_button {
+        return;
+    }
+    drag.dragged.insert(click.pointer_id);
+    if *mode == BezierEditMode::Display || is_multi_touch(&click.pointer_id, &touches) {
+        return;
+    }
+    let Ok((drag_entity, _shape, drag_shape_type)) = query.get(click.target) else {
+        return;
+    };
+
+    let (mut bezier_id, mut part_drag) = if let ShapeType::Bezier(bezier_shape) = drag_shape_type {
+        let part = match click.hit.position {
+            Some(position) => bezier_shape.part_at(position.truncate()),
+            None => bezier_shape.shape_type.clone(),
+        };
+        (bezier_shape.id, part)
+    } else {
+        return;
+    };
+    if keys.any_pressed(input.whole_curve_keys.iter().copied()) {
+        part_drag = BezierShapeType::BezierLine;
+    } else if let Some(position) = click.hit.position {
+        // a handle drawn over a thick stroke should win over the stroke
+        if let Some(resolved) =
+            priority.resolve(&index, &part_drag, position.truncate(), |id, part| {
+                locks.part_locked(id, part)
+            })
+        {
+            (bezier_id, part_drag) = resolved;
+        }
+    }
+    match part_drag {
+        BezierShapeType::MoveGrip => part_drag = BezierShapeType::BezierLine,
+        // the grip moves the curve, leaving the body to other gestures
+        BezierShapeType::BezierLine
+            if !keys.any_pressed(input.whole_curve_keys.iter().copied())
+                && style.for_curve(bezier_id).move_grip.is_some() =>
+        {
+            return;
+        }
+        _ => {}
+    }
+    // a rope's body changes its slack, see `bezier_rope_drag`
+    if part_drag == BezierShapeType::BezierLine && ropes.iter().any(|id| id.0 == bezier_id) {
+        return;
+    }
+    // a second finger on a curve that is already being dragged would fight
+    // the first one over the curve's points
+    if drag.is_dragging(bezier_id) || locks.part_locked(bezier_id, &part_drag) {
+        return;
+    }
+
+    let root = roots.iter().find(|(id, _, _)| id.0 == bezier_id);
+    let mut state = BezierDragState {
+        bezier_id,
+        entity: Some(drag_entity),
+        dragging: part_drag,
+        start_click: Some(click.event().pointer_location.position),
+        camera: Some(click.hit.camera),
+        closed: query.iter().any(|(_, _, shape_type)| {
+            matches!(shape_type, ShapeType::Bezier(bezier_shape)
+                if bezier_shape.id == bezier_id && bezier_shape.closed)
+        }),
+        bounds: root.and_then(|(_, bounds, _)| bounds.map(|bounds| bounds.0)),
+        function: root.is_some_and(|(_, _, function)| function),
+        grab_point: click
+            .hit
+            .position
+            .filter(|_| !keys.any_pressed(input.whole_curve_keys.iter().copied()))
+            .map(|position| position.truncate()),
+        ..default()
+    };
+
+    // find the bezier points with id
+    for (_entity, _shape, shape_type) in query.iter() {
+        let ShapeType::Bezier(bezier_shape) = shape_type else {
+            continue;
+        };
+        if bezier_id != bezier_shape.id {
+            continue;
+        }
+        if let Some([a, b, c, d]) = bezier_shape.handles {
+            (state.a, state.b, state.c, state.d) = (Some(a), Some(b), Some(c), Some(d));
+        }
+        let Some(point) = bezier_shape.point else {
+            continue;
+        };
+        match bezier_shape.shape_type {
+            BezierShapeType::Start => state.a = Some(point),
+            BezierShapeType::ControlStart => state.b = Some(point),
+            BezierShapeType::ControlEnd => state.c = Some(point),
+            BezierShapeType::End => state.d = Some(point),
+            BezierShapeType::Control => {
+                // elevated into `b` and `c` once `a` and `d` are known
+                state.b = Some(point);
+                state.c = Some(point);
+                state.quadratic = true;
+            }
+            BezierShapeType::Line
+            | BezierShapeType::BezierLine
+            | BezierShapeType::Handles
+            | BezierShapeType::MoveGrip => {}
+        }
+    }
+    if state.quadratic {
+        if let (Some(a), Some(q), Some(d)) = (state.a, state.b, state.d) {
+            let (b, c) = elevate_quadratic(a, q, d);
+            state.b = Some(b);
+            state.c = Some(c);
+        }
+    }
+    // a curve missing a part (e.g. despawned by hand) can't be dragged
+    // without losing points, so leave it alone
+    if state.points().is_none() {
+        warn!("not dragging bezier {bezier_id}: some of its points are missing");
+        return;
+    }
+    state.start_points = state.points();
+    state.group = group_drag_members(
+        &selection,
+        query.iter().map(|(_, _, shape_type)| shape_type),
+        bezier_id,
+        &state.dragging,
+    );
+    state.group.retain(|member| {
+        !member
+            .parts
+            .iter()
+            .any(|part| locks.part_locked(member.id, part))
+    });
+    started.write(BezierDragStarted {
+        id: bezier_id,
+        part: state.dragging.clone(),
+        pointer_id: click.pointer_id,
+    });
+    drag.pointers.insert(click.pointer_id, state);
+}
+
+/// World-space movement of a pointer that moved by `delta` pixels to
+/// `position` in `camera`'s viewport. Unlike scaling the pixel delta this
+/// follows camera zoom, rotation and projection scale.
+pub fn pointer_delta_to_world(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    position: Vec2,
+    delta: Vec2,
+) -> Option<Vec2> {
+    let to = camera
+        .viewport_to_world_2d(camera_transform, position)
+        .ok()?;
+    let from = camera
+        .viewport_to_world_2d(camera_transform, position - delta)
+        .ok()?;
+    Some(to - from)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn bezier_drag(
+    click: Trigger<Pointer<Drag>>,
+    mut query: Query<(Entity, &mut Shape, &mut ShapeType)>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    keys: Res<ButtonInput<KeyCode>>,
+    splines: Query<&BezierSpline>,
+    mut drags: ResMut<BezierDrag>,
+    mut document: Option<ResMut<BezierDocument>>,
+    mut modified: EventWriter<BezierModified>,
+    style: Res<BezierStyle>,
+    snap: Res<BezierSnapSettings>,
+    edit: Res<BezierEditSettings>,
+    input: Res<BezierInputMap>,
+    touches: Res<Touches>,
+    spaces: Query<&BezierSpace>,
+    welds: Query<(Entity, &WeldedTo)>,
+    index: Res<BezierPointIndex>,
+) {
+    if !query.contains(click.target) || click.button != input.drag_button {
+        return;
+    }
+    // a second finger turns the gesture into the app's pan or zoom, so the
+    // curve stays where it was
+    if is_multi_touch(&click.pointer_id, &touches) {
+        return;
+    }
+    let Some(drag) = drags.get_mut(&click.pointer_id) else {
+        return;
+    };
+    let camera = drag
+        .camera
+        .and_then(|entity| cameras.get(entity).ok())
+        .or_else(|| cameras.iter().find(|(camera, _)| camera.is_active));
+    // UI space is logical pixels, which is what the pointer reports
+    let ui = matches!(spaces.get(click.target), Ok(BezierSpace::Ui));
+    let world_delta = if ui {
+        Some(click.delta)
+    } else {
+        camera.and_then(|(camera, camera_transform)| {
+            pointer_delta_to_world(
+                camera,
+                camera_transform,
+                click.pointer_location.position,
+                click.delta,
+            )
+        })
+    };
+    let Some(world_delta) = world_delta else {
+        return;
+    };
+    drag.total_delta += world_delta;
+    drag.constrain_axis(keys.any_pressed(input.constrain_axis_keys.iter().copied()));
+    drag.handle_mode = edit.handle_drag_mode(&keys);
+    // the handle's direction is already held while extending
+    let angle = drag.handle_mode != HandleDragMode::Extend
+        && keys.any_pressed(snap.angle_keys.iter().copied());
+    // re-apply the whole movement from the start points so snapping never
+    // accumulates rounding
+    let mut snapped = snap.snap_drag_delta(drag, drag.total_delta, angle);
+    drag.anchor_snap = None;
+    let dragged_anchor = match (&drag.dragging, drag.start_points) {
+        (BezierShapeType::Start, Some([a, _, _, _])) => Some(a),
+        (BezierShapeType::End, Some([_, _, _, d])) => Some(d),
+        _ => None,
+    };
+    if let (Some(radius), Some(start)) = (snap.anchor_radius, dragged_anchor) {
+        // curves moving with this one, including spline neighbours and
+        // welded curves sharing the anchor, would always be the nearest
+        let mut exclude: HashSet<usize> = drag.group.iter().map(|member| member.id).collect();
+        exclude.insert(drag.bezier_id);
+        for (entity, welded) in welds.iter() {
+            let Ok((_, _, ShapeType::Bezier(bezier_shape))) = query.get(entity) else {
+                continue;
+            };
+            if bezier_shape.id == drag.bezier_id {
+                exclude.insert(welded.id);
+            }
+        }
+        for spline in splines
+            .iter()
+            .filter(|spline| spline.segments.contains(&drag.bezier_id))
+        {
+            exclude.extend(spline.segments.iter().copied());
+        }
+        let target = start + drag.constrain_to_axis(snapped);
+        if let Some(anchor) = nearest_anchor(&index, &exclude, target, radius) {
+            snapped = anchor.position - start;
+            // landing on the anchor wins over staying on the axis
+            drag.axis_constrained = false;
+            drag.anchor_snap = Some(anchor);
+        }
+    }
+    // the group moves as far as the bounded curve does
+    let snapped = drag.bounded_delta(snapped);
+    if let Some([a, b, c, d]) = drag.start_points {
+        (drag.a, drag.b, drag.c, drag.d) = (Some(a), Some(b), Some(c), Some(d));
+    }
+    if !edit.bend_drag(drag, snapped) {
+        drag.add_delta(snapped);
+    }
+    let group_delta = drag.constrain_to_axis(snapped);
+    let mut group = std::mem::take(&mut drag.group);
+    for member in group.iter_mut() {
+        member.points = member.moved(group_delta);
+        if member.id == drag.bezier_id {
+            let [a, b, c, d] = member.points;
+            (drag.a, drag.b, drag.c, drag.d) = (Some(a), Some(b), Some(c), Some(d));
+        } else {
+            update_bezier_in_place(&style, &mut query, member.id, member.points);
+            modified.write(BezierModified {
+                id: member.id,
+                points: member.points,
+            });
+            if let Some(document) = document.as_mut() {
+                document.write_back(member.id, member.points);
+            }
+        }
+    }
+    drag.group = group;
+    edit.hold_drag_length(drag);
+    drag.constrain_function();
+    let Some(points) = drag.points() else {
+        warn!(
+            "aborting drag of bezier {}: its points went missing",
+            drag.bezier_id
+        );
+        drags.pointers.remove(&click.pointer_id);
+        return;
+    };
+    update_bezier_in_place(&style, &mut query, drag.bezier_id, points);
+    modified.write(BezierModified {
+        id: drag.bezier_id,
+        points,
+    });
+    let neighbours = follow_spline_neighbors(&style, &mut query, &splines, drag, &mut modified);
+    let welded = follow_welded_anchors(
+        &style,
+        &mut query,
+        &welds,
+        drag.bezier_id,
+        &mut document,
+        &mut modified,
+    );
+    drag.follow(neighbours.into_iter().chain(welded));
+    if let Some(document) = document.as_mut() {
+        document.write_back(drag.bezier_id, points);
+    }
+}
+
+/// Rebuilds the shapes of curve `id` at `points` on its existing entities,
+/// so edits don't despawn, respawn and re-observe the whole curve.
+pub fn update_bezier_in_place(
+    style: &BezierStyle,
+    query: &mut Query<(Entity, &mut Shape, &mut ShapeType)>,
+    id: usize,
+    points: [Vec2; 4],
+) {
+    let closed = query
+        .iter()
+        .find_map(|(_, _, shape_type)| match shape_type {
+            ShapeType::Bezier(bezier_shape) if bezier_shape.id == id => Some(bezier_shape.closed),
+            _ => None,
+        });
+    let Some(closed) = closed else {
+        return;
+    };
+    let quadratic = query.iter().any(|(_, _, shape_type)| {
+        matches!(shape_type, ShapeType::Bezier(bezier_shape)
+            if bezier_shape.id == id && matches!(bezier_shape.shape_type, BezierShapeType::Control))
+    });
+    let [a, b, c, d] = points;
+    let shapes = if quadratic {
+        bezier_quadratic_shapes(style, id, a, reduce_to_quadratic(a, b, c, d), d, closed)
+    } else {
+        bezier_shapes(style, id, a, b, c, d, closed)
+    };
+    let mut rebuilt: Vec<Option<(Shape, ShapeType)>> = shapes.into_iter().map(Some).collect();
+    for (_entity, mut shape, mut shape_type) in query.iter_mut() {
+        let index = match &*shape_type {
+            ShapeType::Bezier(bezier_shape) if bezier_shape.id == id => bezier_shape.index,
+            _ => continue,
+        };
+        match rebuilt.get_mut(index) {
+            Some(slot) => {
+                if let Some((new_shape, new_shape_type)) = slot.take() {
+                    *shape = new_shape;
+                    *shape_type = new_shape_type;
+                }
+            }
+            // a part the style no longer builds, e.g. the hit circles of a
+            // `pick_radius` that shrank
+            None => {
+                *shape = ShapeBuilder::with(&ShapePath::new())
+                    .stroke((Color::NONE, 1.0))
+                    .build();
+            }
+        }
+    }
+}
+
+pub fn drag_end(
+    click: Trigger<Pointer<DragEnd>>,
+    mut drag: ResMut<BezierDrag>,
+    mut history: ResMut<BezierHistory>,
+    mut ended: EventWriter<BezierDragEnded>,
+    mut snapped: EventWriter<BezierAnchorSnapped>,
+    input: Res<BezierInputMap>,
+) {
+    if click.button != input.drag_button {
+        return;
+    }
+    drag.dragged.remove(&click.pointer_id);
+    let Some(state) = drag.pointers.remove(&click.pointer_id) else {
+        return;
+    };
+    let mut edits: Vec<BezierEdit> = state
+        .group
+        .iter()
+        .filter(|member| member.id != state.bezier_id)
+        .map(|member| BezierEdit {
+            id: member.id,
+            before: member.start_points,
+            after: member.points,
+        })
+        .collect();
+    edits.extend(state.followers.values().copied());
+    if let (Some(before), Some(after)) = (state.start_points, state.points()) {
+        edits.push(BezierEdit {
+            id: state.bezier_id,
+            before,
+            after,
+        });
+        ended.write(BezierDragEnded {
+            id: state.bezier_id,
+            points: after,
+            pointer_id: click.pointer_id,
+        });
+    }
+    history.record_edits(edits);
+    if let Some(to) = state.anchor_snap {
+        snapped.write(BezierAnchorSnapped {
+            id: state.bezier_id,
+            part: state.dragging,
+            to,
+        });
+    }
+}
+
+/// Puts every curve being dragged, and the selected curves moving with it,
+/// back where it was when its drag started once a
+/// `BezierInputMap::cancel_drag_keys` key is pressed. The drags end without
+/// recording history; the rest of each gesture is ignored.
+#[allow(clippy::too_many_arguments)]
+pub fn cancel_bezier_drags(
+    keys: Res<ButtonInput<KeyCode>>,
+    input: Res<BezierInputMap>,
+    mut drags: ResMut<BezierDrag>,
+    mut query: Query<(Entity, &mut Shape, &mut ShapeType)>,
+    splines: Query<&BezierSpline>,
+    welds: Query<(Entity, &WeldedTo)>,
+    mut document: Option<ResMut<BezierDocument>>,
+    mut modified: EventWriter<BezierModified>,
+    mut cancelled: EventWriter<BezierDragCancelled>,
+    style: Res<BezierStyle>,
+) {
+    if drags.pointers.is_empty() || !keys.any_just_pressed(input.cancel_drag_keys.iter().copied()) {
+        return;
+    }
+    for (pointer_id, mut state) in std::mem::take(&mut drags.pointers) {
+        let Some(start) = state.start_points else {
+            continue;
+        };
+        let restored = state
+            .followers
+            .values()
+            .map(|follower| (follower.id, follower.before))
+            .chain(
+                state
+                    .group
+                    .iter()
+                    .filter(|member| member.id != state.bezier_id)
+                    .map(|member| (member.id, member.start_points)),
+            )
+            .chain([(state.bezier_id, start)]);
+        for (id, points) in restored {
+            update_bezier_in_place(&style, &mut query, id, points);
+            modified.write(BezierModified { id, points });
+            if let Some(document) = document.as_mut() {
+                document.write_back(id, points);
+            }
+        }
+        let [a, b, c, d] = start;
+        (state.a, state.b, state.c, state.d) = (Some(a), Some(b), Some(c), Some(d));
+        follow_spline_neighbors(&style, &mut query, &splines, &state, &mut modified);
+        follow_welded_anchors(
+            &style,
+            &mut query,
+            &welds,
+            state.bezier_id,
+            &mut document,
+            &mut modified,
+        );
+        cancelled.write(BezierDragCancelled {
+            id: state.bezier_id,
+            points: start,
+            pointer_id,
+        });
+    }
+}
@@ -0,0 +1,75 @@
+use super::*;
+
+/// Put on a curve's `BezierId` root to blend it through `shapes` in turn,
+/// `seconds_per_shape` for each step, e.g. for UI transitions. The curve
+/// is redrawn every frame while the morph runs.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct BezierMorph {
+    pub shapes: Vec<[Vec2; 4]>,
+    pub seconds_per_shape: f32,
+    /// Blend from the last shape back to the first and keep going, instead
+    /// of stopping on the last.
+    pub looping: bool,
+    /// Time since the morph started.
+    pub elapsed: f32,
+}
+
+impl BezierMorph {
+    pub fn new(shapes: Vec<[Vec2; 4]>, seconds_per_shape: f32) -> Self {
+        Self {
+            shapes,
+            seconds_per_shape,
+            looping: false,
+            elapsed: 0.0,
+        }
+    }
+    pub fn looping(mut self) -> Self {
+        self.looping = true;
+        self
+    }
+    /// The blended points `elapsed` seconds in.
+    pub fn points_at(&self, elapsed: f32) -> Option<[Vec2; 4]> {
+        let last = self.shapes.len().checked_sub(1)?;
+        if last == 0 || self.seconds_per_shape <= 0.0 {
+            return self.shapes.last().copied();
+        }
+        let steps = if self.looping { last + 1 } else { last };
+        let mut position = elapsed.max(0.0) / self.seconds_per_shape;
+        position = if self.looping {
+            position.rem_euclid(steps as f32)
+        } else {
+            position.min(steps as f32)
+        };
+        let step = (position.floor() as usize).min(steps - 1);
+        let from = CubicBezier::from(self.shapes[step]);
+        let to = CubicBezier::from(self.shapes[(step + 1) % self.shapes.len()]);
+        Some(lerp_curves(&from, &to, position - step as f32).points())
+    }
+    /// Whether a non-looping morph has reached its last shape.
+    pub fn finished(&self) -> bool {
+        !self.looping
+            && self.elapsed > self.shapes.len().saturating_sub(1) as f32 * self.seconds_per_shape
+    }
+}
+
+/// Advances every `BezierMorph` and redraws its curve.
+pub fn animate_bezier_morphs(
+    time: Res<Time>,
+    mut morphs: Query<(&mut BezierMorph, &BezierId)>,
+    mut query: Query<(Entity, &mut Shape, &mut ShapeType)>,
+    mut modified: EventWriter<BezierModified>,
+    style: Res<BezierStyle>,
+) {
+    for (mut morph, id) in morphs.iter_mut() {
+        if morph.finished() {
+            continue;
+        }
+        morph.elapsed += time.delta_secs();
+        let Some(points) = morph.points_at(morph.elapsed) else {
+            continue;
+        };
+        update_bezier_in_place(&style, &mut query, id.0, points);
+        modified.write(BezierModified { id: id.0, points });
+    }
+}
@@ -0,0 +1,137 @@
+use super::*;
+use bevy::window::PrimaryWindow;
+
+/// Pen tool. While `enabled`, clicking empty space places an anchor,
+/// dragging before releasing pulls out its handles, and Enter or Escape
+/// turns the anchors placed so far into a `BezierSpline`.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct BezierCreateMode {
+    pub enabled: bool,
+    /// Anchors placed so far with their outgoing handle. The incoming handle
+    /// mirrors it.
+    anchors: Vec<(Vec2, Vec2)>,
+    pulling: bool,
+}
+
+impl BezierCreateMode {
+    pub fn is_drawing(&self) -> bool {
+        !self.anchors.is_empty()
+    }
+    /// The spline points the anchors describe, laid out as
+    /// `spawn_bezier_spline` expects.
+    pub fn spline_points(&self) -> Vec<Vec2> {
+        let mut points = Vec::new();
+        for (index, &(anchor, handle)) in self.anchors.iter().enumerate() {
+            if index > 0 {
+                points.push(2.0 * anchor - handle);
+            }
+            points.push(anchor);
+            if index + 1 < self.anchors.len() {
+                points.push(handle);
+            }
+        }
+        points
+    }
+}
+
+/// The in-progress path of the pen tool.
+#[derive(Component, Clone, Copy, Debug, Default)]
+#[require(Transform)]
+pub struct BezierPenPreview;
+
+/// Places anchors and pulls handles for `BezierCreateMode`, and spawns the
+/// finished path. Presses over existing curve parts are left to dragging.
+#[allow(clippy::too_many_arguments)]
+pub fn bezier_pen_tool(
+    mut commands: Commands,
+    mut mode: ResMut<BezierCreateMode>,
+    mut ids: ResMut<BezierIdAllocator>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    hovered: Query<(), With<BezierHovered>>,
+    style: Res<BezierStyle>,
+) {
+    if !mode.enabled {
+        return;
+    }
+    if keys.any_just_pressed([KeyCode::Enter, KeyCode::Escape]) {
+        let points = mode.spline_points();
+        spawn_bezier_spline(&mut commands, &style, &mut ids, &points);
+        mode.anchors.clear();
+        mode.pulling = false;
+        return;
+    }
+
+    let cursor = windows.single().ok().and_then(Window::cursor_position);
+    let Some((camera, camera_transform)) = cameras.iter().find(|(camera, _)| camera.is_active)
+    else {
+        return;
+    };
+    let Some(position) =
+        cursor.and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+    else {
+        return;
+    };
+
+    if mouse.just_pressed(MouseButton::Left) && hovered.is_empty() {
+        mode.anchors.push((position, position));
+        mode.pulling = true;
+    } else if mode.pulling && mouse.pressed(MouseButton::Left) {
+        if let Some((_, handle)) = mode.anchors.last_mut() {
+            *handle = position;
+        }
+    }
+    if mouse.just_released(MouseButton::Left) {
+        mode.pulling = false;
+    }
+}
+
+/// Draws the path the pen tool has placed so far.
+pub fn update_bezier_pen_preview(
+    mut commands: Commands,
+    mode: Res<BezierCreateMode>,
+    mut previews: Query<(Entity, &mut Shape), With<BezierPenPreview>>,
+    style: Res<BezierStyle>,
+) {
+    if !mode.is_changed() && !style.is_changed() {
+        return;
+    }
+    if !mode.is_drawing() {
+        for (entity, _) in previews.iter() {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    let points = mode.spline_points();
+    let mut path = ShapePath::new().move_to(points[0]);
+    for segment in points[1..].chunks_exact(3) {
+        path = path.cubic_bezier_to(segment[0], segment[1], segment[2]);
+    }
+    let mut builder = ShapeBuilder::with(&path);
+    for &(anchor, handle) in &mode.anchors {
+        builder = builder
+            .add(&shapes::Line(2.0 * anchor - handle, handle))
+            .add(&shapes::Circle {
+                radius: style.intersection_radius - 1.0,
+                center: anchor,
+            });
+    }
+    let shape = builder
+        .stroke((style.bezier_line_color, style.sketch_stroke_width))
+        .build();
+
+    if let Ok((_, mut preview)) = previews.single_mut() {
+        *preview = shape;
+    } else {
+        let z = style.layering.base_z + style.layering.curve;
+        commands.spawn((
+            BezierPenPreview,
+            shape,
+            Transform::from_xyz(0.0, 0.0, z),
+            Pickable::IGNORE,
+        ));
+    }
+}
@@ -0,0 +1,102 @@
+use super::*;
+
+/// Which part a drag grabs when several overlap under the pointer. Without
+/// it the topmost pick wins, which on a thick stroke is often the curve
+/// body rather than the control handle drawn over it. With it, `drag_start`
+/// looks for points near where the pointer went down and prefers control
+/// handles over anchors over lines, whichever curve they belong to.
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct BezierPickPriority {
+    pub enabled: bool,
+    /// How close to the pointer, in world units, a handle or anchor must be
+    /// to win over the part that was picked.
+    pub radius: f32,
+}
+
+impl Default for BezierPickPriority {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            radius: 8.0,
+        }
+    }
+}
+
+impl BezierPickPriority {
+    /// Rank of `part`; higher ranks win.
+    pub fn rank(part: &BezierShapeType) -> u8 {
+        match part {
+            BezierShapeType::ControlStart
+            | BezierShapeType::ControlEnd
+            | BezierShapeType::Control => 2,
+            BezierShapeType::Start | BezierShapeType::End | BezierShapeType::MoveGrip => 1,
+            BezierShapeType::Line | BezierShapeType::BezierLine | BezierShapeType::Handles => 0,
+        }
+    }
+    /// The curve and part to drag instead of `part`, picked at
+    /// `position`: the highest ranked point within `radius` that outranks
+    /// `part` and isn't `locked`, the nearest among equals, or `None` when
+    /// nothing does.
+    pub fn resolve(
+        &self,
+        index: &BezierPointIndex,
+        part: &BezierShapeType,
+        position: Vec2,
+        locked: impl Fn(usize, &BezierShapeType) -> bool,
+    ) -> Option<(usize, BezierShapeType)> {
+        if !self.enabled {
+            return None;
+        }
+        let rank = Self::rank(part);
+        index
+            .within(position, self.radius)
+            .filter(|point| Self::rank(&point.part) > rank)
+            .filter(|point| !locked(point.id, &point.part))
+            .max_by(|p, q| {
+                Self::rank(&p.part).cmp(&Self::rank(&q.part)).then_with(|| {
+                    q.position
+                        .distance_squared(position)
+                        .total_cmp(&p.position.distance_squared(position))
+                })
+            })
+            .map(|point| (point.id, point.part.clone()))
+    }
+}
+
+/// Invisible circles `BezierStyle::pick_radius` wide under the anchors and
+/// controls of curve `id`, which grab the point like its marker does. None
+/// while the radius is no larger than the marker, so curves at the default
+/// radius carry no extra entities.
+pub fn handle_hit_shapes(
+    style: &BezierStyle,
+    id: usize,
+    closed: bool,
+    points: &[(BezierShapeType, Vec2)],
+) -> Vec<(Shape, ShapeType)> {
+    let radius = style.pick_radius;
+    if radius <= style.intersection_radius - 1.0 {
+        return Vec::new();
+    }
+    points
+        .iter()
+        .map(|(part, center)| {
+            let shape = ShapeBuilder::with(&shapes::Circle {
+                radius,
+                center: *center,
+            })
+            .fill(Color::NONE)
+            .build();
+            // no `point`, so only the marker counts as the point's entity
+            let shape_type = ShapeType::Bezier(BezierShape {
+                shape_type: part.clone(),
+                id,
+                point: None,
+                closed,
+                index: 0,
+                handles: None,
+            });
+            (shape, shape_type)
+        })
+        .collect()
+}
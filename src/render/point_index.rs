@@ -0,0 +1,213 @@
+use super::*;
+
+/// An anchor or control point in `BezierPointIndex`. A quadratic's single
+/// control is indexed as `Control` at its own position.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IndexedBezierPoint {
+    pub id: usize,
+    pub part: BezierShapeType,
+    pub position: Vec2,
+}
+
+/// Where a ray cast with `BezierPointIndex::raycast` first hit a curve.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BezierRayHit {
+    pub id: usize,
+    /// Parameter of the hit on the curve.
+    pub t: f32,
+    /// Distance from the ray's origin.
+    pub distance: f32,
+    pub point: Vec2,
+}
+
+/// Spatial hash over the anchors and controls of every spawned curve, so
+/// snapping, marquee selection and nearest point lookups only look at the
+/// cells around them instead of every point. Kept in step with
+/// `BezierRegistry` by `update_bezier_point_index`, which re-files only the
+/// curves whose points moved.
+#[derive(Resource, Clone, Debug)]
+pub struct BezierPointIndex {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<IndexedBezierPoint>>,
+    curves: HashMap<usize, ([Vec2; 4], bool)>,
+}
+
+impl Default for BezierPointIndex {
+    fn default() -> Self {
+        Self::new(64.0)
+    }
+}
+
+impl BezierPointIndex {
+    /// An empty index with square cells `cell_size` world units wide. Cells
+    /// around the usual snap or pick radius work best.
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.max(f32::EPSILON),
+            cells: HashMap::new(),
+            curves: HashMap::new(),
+        }
+    }
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+    fn cell(&self, position: Vec2) -> IVec2 {
+        (position / self.cell_size).floor().as_ivec2()
+    }
+    fn points_of(id: usize, points: [Vec2; 4], quadratic: bool) -> Vec<IndexedBezierPoint> {
+        let [a, b, c, d] = points;
+        let parts = if quadratic {
+            vec![
+                (BezierShapeType::Start, a),
+                (BezierShapeType::Control, reduce_to_quadratic(a, b, c, d)),
+                (BezierShapeType::End, d),
+            ]
+        } else {
+            vec![
+                (BezierShapeType::Start, a),
+                (BezierShapeType::ControlStart, b),
+                (BezierShapeType::ControlEnd, c),
+                (BezierShapeType::End, d),
+            ]
+        };
+        parts
+            .into_iter()
+            .map(|(part, position)| IndexedBezierPoint { id, part, position })
+            .collect()
+    }
+    /// Files curve `id`'s points, replacing any it had.
+    pub fn insert_curve(&mut self, id: usize, points: [Vec2; 4], quadratic: bool) {
+        self.remove_curve(id);
+        for point in Self::points_of(id, points, quadratic) {
+            let cell = self.cell(point.position);
+            self.cells.entry(cell).or_default().push(point);
+        }
+        self.curves.insert(id, (points, quadratic));
+    }
+    /// Drops curve `id`'s points.
+    pub fn remove_curve(&mut self, id: usize) {
+        let Some((points, quadratic)) = self.curves.remove(&id) else {
+            return;
+        };
+        for point in Self::points_of(id, points, quadratic) {
+            let cell = self.cell(point.position);
+            if let Some(entries) = self.cells.get_mut(&cell) {
+                entries.retain(|entry| entry.id != id);
+                if entries.is_empty() {
+                    self.cells.remove(&cell);
+                }
+            }
+        }
+    }
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.curves.clear();
+    }
+    /// Number of indexed curves.
+    pub fn len(&self) -> usize {
+        self.curves.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.curves.is_empty()
+    }
+    /// Points inside `rect`.
+    pub fn in_rect(&self, rect: Rect) -> impl Iterator<Item = &IndexedBezierPoint> + '_ {
+        let (min, max) = (self.cell(rect.min), self.cell(rect.max));
+        (min.x..=max.x)
+            .flat_map(move |x| (min.y..=max.y).map(move |y| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .filter(move |point| rect.contains(point.position))
+    }
+    /// Points within `radius` of `center`.
+    pub fn within(
+        &self,
+        center: Vec2,
+        radius: f32,
+    ) -> impl Iterator<Item = &IndexedBezierPoint> + '_ {
+        self.in_rect(Rect::from_center_half_size(center, Vec2::splat(radius)))
+            .filter(move |point| point.position.distance(center) <= radius)
+    }
+    /// The first indexed curve a ray from `origin` heading along `dir` hits,
+    /// e.g. for line of sight against drawn walls. Curves whose control box
+    /// the ray misses are skipped before the exact test.
+    pub fn raycast(&self, origin: Vec2, dir: Vec2) -> Option<BezierRayHit> {
+        let dir = dir.try_normalize()?;
+        self.curves
+            .iter()
+            .filter(|(_, (points, _))| {
+                let [a, b, c, d] = *points;
+                ray_hits_rect(origin, dir, bezier_bounds(a, b, c, d))
+            })
+            .filter_map(|(&id, (points, _))| {
+                let (t, distance, point) = raycast(&CubicBezier::from(*points), origin, dir)?;
+                Some(BezierRayHit {
+                    id,
+                    t,
+                    distance,
+                    point,
+                })
+            })
+            .min_by(|p, q| p.distance.total_cmp(&q.distance))
+    }
+    /// The point closest to `target` within `radius` that `keep` accepts.
+    pub fn nearest(
+        &self,
+        target: Vec2,
+        radius: f32,
+        keep: impl Fn(&IndexedBezierPoint) -> bool,
+    ) -> Option<&IndexedBezierPoint> {
+        self.within(target, radius)
+            .filter(|point| keep(point))
+            .min_by(|p, q| {
+                p.position
+                    .distance_squared(target)
+                    .total_cmp(&q.position.distance_squared(target))
+            })
+    }
+}
+
+/// Whether a ray from `origin` along unit `dir` passes through `rect`
+/// (slab test).
+fn ray_hits_rect(origin: Vec2, dir: Vec2, rect: Rect) -> bool {
+    let (mut near, mut far) = (0.0f32, f32::INFINITY);
+    for axis in 0..2 {
+        let (start, step) = (origin[axis], dir[axis]);
+        let (min, max) = (rect.min[axis], rect.max[axis]);
+        if step.abs() <= f32::EPSILON {
+            if start < min || start > max {
+                return false;
+            }
+            continue;
+        }
+        let (t0, t1) = ((min - start) / step, (max - start) / step);
+        near = near.max(t0.min(t1));
+        far = far.min(t0.max(t1));
+    }
+    near <= far
+}
+
+/// Re-files the curves in `BezierPointIndex` whose points changed since
+/// the registry was last rebuilt, and drops despawned ones.
+pub fn update_bezier_point_index(
+    registry: Res<BezierRegistry>,
+    mut index: ResMut<BezierPointIndex>,
+) {
+    if !registry.is_changed() {
+        return;
+    }
+    let stale: Vec<usize> = index
+        .curves
+        .keys()
+        .copied()
+        .filter(|&id| !registry.contains(id))
+        .collect();
+    for id in stale {
+        index.remove_curve(id);
+    }
+    for (id, entry) in registry.iter() {
+        if index.curves.get(&id) != Some(&(entry.points, entry.quadratic)) {
+            index.insert_curve(id, entry.points, entry.quadratic);
+        }
+    }
+}
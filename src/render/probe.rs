@@ -0,0 +1,53 @@
+use super::*;
+
+/// Marks the point at parameter `t` on curve `id` with a circle, drawn
+/// with gizmos every frame so it tracks the curve while it is edited.
+/// Handy when checking easing or path-following against the curve.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct BezierProbe {
+    pub id: usize,
+    pub t: f32,
+    /// Also draw the tangent at `t`, this many world units long.
+    pub tangent_length: Option<f32>,
+    pub color: Color,
+}
+
+impl BezierProbe {
+    pub fn new(id: usize, t: f32) -> Self {
+        Self {
+            id,
+            t,
+            tangent_length: None,
+            color: Color::srgb(1.0, 0.2, 0.8),
+        }
+    }
+    pub fn with_tangent(mut self, length: f32) -> Self {
+        self.tangent_length = Some(length);
+        self
+    }
+}
+
+/// Draws every `BezierProbe`.
+pub fn draw_bezier_probes(
+    mut gizmos: Gizmos,
+    probes: Query<&BezierProbe>,
+    registry: Res<BezierRegistry>,
+    style: Res<BezierStyle>,
+) {
+    for probe in probes.iter() {
+        let Some(points) = registry.points(probe.id) else {
+            continue;
+        };
+        let (position, tangent, _) = CubicBezier::from(points).frame_at(probe.t.clamp(0.0, 1.0));
+        gizmos.circle_2d(
+            Isometry2d::from_translation(position),
+            style.intersection_radius,
+            probe.color,
+        );
+        if let Some(length) = probe.tangent_length {
+            let half = tangent * length * 0.5;
+            gizmos.line_2d(position - half, position + half, probe.color);
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use super::*;
+use bevy::ecs::system::SystemParam;
+
+/// Every curve among `shape_types` as a plain `BezierCurve`, ordered by id.
+/// Quadratics are elevated to cubics; curves with a missing point are left
+/// out, as in `collect_bezier_points`.
+pub fn collect_curves<'a>(
+    shape_types: impl IntoIterator<Item = &'a ShapeType>,
+) -> Vec<BezierCurve> {
+    collect_bezier_points(shape_types)
+        .into_iter()
+        .map(|(id, [a, b, c, d])| BezierCurve { id, a, b, c, d })
+        .collect()
+}
+
+/// Reads the curves currently spawned as plain values, straight from their
+/// parts, so exporters and gameplay systems needn't know how a curve is
+/// split across entities. Unlike `BezierRegistry`, which is rebuilt in
+/// `PostUpdate`, it sees edits made earlier in the same frame.
+#[derive(SystemParam)]
+pub struct BezierQuery<'w, 's> {
+    shapes: Query<'w, 's, &'static ShapeType>,
+}
+
+impl BezierQuery<'_, '_> {
+    /// Curve `id`, or `None` when it isn't spawned or is missing a point.
+    pub fn get(&self, id: usize) -> Option<BezierCurve> {
+        collect_curves(self.parts(id)).pop()
+    }
+    /// Every spawned curve, ordered by id.
+    pub fn iter(&self) -> std::vec::IntoIter<BezierCurve> {
+        collect_curves(self.shapes.iter()).into_iter()
+    }
+    /// Whether curve `id` is closed.
+    pub fn is_closed(&self, id: usize) -> bool {
+        self.parts(id).any(|shape_type| {
+            matches!(shape_type, ShapeType::Bezier(bezier_shape) if bezier_shape.closed)
+        })
+    }
+    fn parts(&self, id: usize) -> impl Iterator<Item = &ShapeType> + '_ {
+        self.shapes.iter().filter(move |shape_type| {
+            matches!(shape_type, ShapeType::Bezier(bezier_shape) if bezier_shape.id == id)
+        })
+    }
+}
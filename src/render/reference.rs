@@ -0,0 +1,118 @@
+use super::*;
+
+/// An image shown behind the curves to trace, e.g. a sketch or a logo to
+/// vectorize. Set `path` to show one; edits to the resource restyle the
+/// image in place.
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct BezierReference {
+    /// Asset path of the image, nothing shown when `None`.
+    pub path: Option<String>,
+    pub opacity: f32,
+    /// A locked image ignores the pointer. An unlocked one can be dragged
+    /// with `BezierInputMap::drag_button` to line it up.
+    pub locked: bool,
+    /// Center of the image in world units.
+    pub position: Vec2,
+    pub scale: f32,
+    /// Depth of the image, below the curves by default.
+    pub z: f32,
+}
+
+impl Default for BezierReference {
+    fn default() -> Self {
+        Self {
+            path: None,
+            opacity: 0.5,
+            locked: true,
+            position: Vec2::ZERO,
+            scale: 1.0,
+            z: -1.0,
+        }
+    }
+}
+
+/// The sprite showing `BezierReference`, and the path it was loaded from.
+#[derive(Component, Clone, Debug)]
+pub struct BezierReferenceImage {
+    pub path: String,
+}
+
+/// Spawns, restyles and despawns the `BezierReference` sprite.
+pub fn sync_bezier_reference(
+    mut commands: Commands,
+    reference: Res<BezierReference>,
+    asset_server: Res<AssetServer>,
+    mut images: Query<(
+        Entity,
+        &BezierReferenceImage,
+        &mut Sprite,
+        &mut Transform,
+        &mut Pickable,
+    )>,
+) {
+    if !reference.is_changed() {
+        return;
+    }
+    let color = Color::WHITE.with_alpha(reference.opacity);
+    let transform = Transform::from_translation(reference.position.extend(reference.z))
+        .with_scale(Vec3::new(reference.scale, reference.scale, 1.0));
+    let pickable = if reference.locked {
+        Pickable::IGNORE
+    } else {
+        Pickable::default()
+    };
+    let mut shown = false;
+    for (entity, image, mut sprite, mut image_transform, mut image_pickable) in images.iter_mut() {
+        if reference.path.as_ref() != Some(&image.path) {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        sprite.color = color;
+        *image_transform = transform;
+        *image_pickable = pickable.clone();
+        shown = true;
+    }
+    let Some(path) = reference.path.clone().filter(|_| !shown) else {
+        return;
+    };
+    commands
+        .spawn((
+            Sprite {
+                image: asset_server.load(path.clone()),
+                color,
+                ..default()
+            },
+            transform,
+            pickable,
+            BezierReferenceImage { path },
+        ))
+        .observe(drag_bezier_reference);
+}
+
+/// Moves an unlocked `BezierReference` image with the pointer.
+pub fn drag_bezier_reference(
+    click: Trigger<Pointer<Drag>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    input: Res<BezierInputMap>,
+    mut reference: ResMut<BezierReference>,
+) {
+    if click.button != input.drag_button || reference.locked {
+        return;
+    }
+    let Some(delta) = cameras
+        .get(click.hit.camera)
+        .ok()
+        .and_then(|(camera, transform)| {
+            pointer_delta_to_world(
+                camera,
+                transform,
+                click.pointer_location.position,
+                click.delta,
+            )
+        })
+    else {
+        return;
+    };
+    reference.position += delta;
+}
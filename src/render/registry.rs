@@ -0,0 +1,114 @@
+use super::*;
+
+/// What the registry knows about one spawned curve.
+#[derive(Clone, Debug, Default)]
+pub struct BezierRegistryEntry {
+    /// The `BezierId` root its parts are parented to, if it has one.
+    pub root: Option<Entity>,
+    /// Every part entity of the curve.
+    pub entities: Vec<Entity>,
+    /// Current points; a quadratic's control is elevated into `b` and `c`.
+    pub points: [Vec2; 4],
+    pub closed: bool,
+    pub quadratic: bool,
+    /// Changes every time the curve's points do, so caches built from them
+    /// (meshes, colliders) can tell whether they are stale. Unique across
+    /// curves and never reused, even for a respawned id.
+    pub version: u64,
+}
+
+/// Index of the spawned curves by id, so looking up a curve's points or
+/// entities doesn't mean scanning every `ShapeType`. Rebuilt by
+/// `update_bezier_registry` at the end of any frame that spawned, edited or
+/// despawned curve parts.
+#[derive(Resource, Default)]
+pub struct BezierRegistry {
+    curves: BTreeMap<usize, BezierRegistryEntry>,
+    last_version: u64,
+}
+
+impl BezierRegistry {
+    pub fn get(&self, id: usize) -> Option<&BezierRegistryEntry> {
+        self.curves.get(&id)
+    }
+    pub fn points(&self, id: usize) -> Option<[Vec2; 4]> {
+        self.curves.get(&id).map(|entry| entry.points)
+    }
+    pub fn root_entity(&self, id: usize) -> Option<Entity> {
+        self.curves.get(&id).and_then(|entry| entry.root)
+    }
+    pub fn entities(&self, id: usize) -> &[Entity] {
+        self.curves
+            .get(&id)
+            .map_or(&[], |entry| entry.entities.as_slice())
+    }
+    /// `BezierRegistryEntry::version` of curve `id`.
+    pub fn version(&self, id: usize) -> Option<u64> {
+        self.curves.get(&id).map(|entry| entry.version)
+    }
+    pub fn contains(&self, id: usize) -> bool {
+        self.curves.contains_key(&id)
+    }
+    pub fn len(&self) -> usize {
+        self.curves.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.curves.is_empty()
+    }
+    /// Ids and entries in ascending id order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &BezierRegistryEntry)> + '_ {
+        self.curves.iter().map(|(&id, entry)| (id, entry))
+    }
+}
+
+/// Rebuilds `BezierRegistry` when curve parts or roots were added, changed
+/// or removed.
+pub fn update_bezier_registry(
+    mut registry: ResMut<BezierRegistry>,
+    parts: Query<(Entity, &ShapeType)>,
+    roots: Query<(Entity, &BezierId)>,
+    changed: Query<(), Or<(Changed<ShapeType>, Added<BezierId>)>>,
+    mut removed_parts: RemovedComponents<ShapeType>,
+    mut removed_roots: RemovedComponents<BezierId>,
+) {
+    let removed = removed_parts.read().count() + removed_roots.read().count() > 0;
+    if changed.is_empty() && !removed {
+        return;
+    }
+    let registry = &mut *registry;
+    let points = collect_bezier_points(parts.iter().map(|(_, shape_type)| shape_type));
+    let mut curves: BTreeMap<usize, BezierRegistryEntry> = points
+        .into_iter()
+        .map(|(id, points)| {
+            let version = match registry.curves.get(&id) {
+                Some(previous) if previous.points == points => previous.version,
+                _ => {
+                    registry.last_version += 1;
+                    registry.last_version
+                }
+            };
+            let entry = BezierRegistryEntry {
+                points,
+                version,
+                ..default()
+            };
+            (id, entry)
+        })
+        .collect();
+    for (entity, shape_type) in parts.iter() {
+        let ShapeType::Bezier(bezier_shape) = shape_type else {
+            continue;
+        };
+        if let Some(entry) = curves.get_mut(&bezier_shape.id) {
+            entry.entities.push(entity);
+            entry.closed = bezier_shape.closed;
+            entry.quadratic |= bezier_shape.shape_type == BezierShapeType::Control;
+        }
+    }
+    for (entity, id) in roots.iter() {
+        if let Some(entry) = curves.get_mut(&id.0) {
+            entry.root = Some(entity);
+        }
+    }
+    registry.curves = curves;
+}
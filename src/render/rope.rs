@@ -0,0 +1,98 @@
+use super::*;
+
+/// Put on a curve's `BezierId` root to draw it as a hanging rope: its
+/// handles are kept so the curve sags `slack` world units longer than the
+/// line between its anchors, wherever the anchors are dragged. Dragging the
+/// curve body up or down takes up or pays out slack instead of moving it.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component)]
+pub struct BezierRope {
+    pub slack: f32,
+}
+
+/// Re-hangs rope curves whose anchors moved or whose slack changed.
+pub fn sag_bezier_ropes(
+    ropes: Query<(Ref<BezierRope>, &BezierId)>,
+    mut events: EventReader<BezierModified>,
+    mut query: Query<(Entity, &mut Shape, &mut ShapeType)>,
+    mut modified: EventWriter<BezierModified>,
+    style: Res<BezierStyle>,
+) {
+    let moved: HashSet<usize> = events.read().map(|event| event.id).collect();
+    let hang: Vec<(usize, f32)> = ropes
+        .iter()
+        .filter(|(rope, id)| rope.is_changed() || moved.contains(&id.0))
+        .map(|(rope, id)| (id.0, rope.slack))
+        .collect();
+    if hang.is_empty() {
+        return;
+    }
+    let curves = collect_bezier_points(query.iter().map(|(_, _, shape_type)| shape_type));
+    for (id, slack) in hang {
+        let Some(&[a, b, c, d]) = curves.get(&id) else {
+            continue;
+        };
+        let rope = rope_bezier(a, d, slack);
+        // converged ropes stay put, so their own `BezierModified` doesn't
+        // re-hang them every frame
+        if rope.b.distance(b) < 1e-3 && rope.c.distance(c) < 1e-3 {
+            continue;
+        }
+        update_bezier_in_place(&style, &mut query, id, rope.points());
+        modified.write(BezierModified {
+            id,
+            points: rope.points(),
+        });
+    }
+}
+
+/// Dragging the body of a `BezierRope` curve changes its slack: down pays
+/// out rope, up takes it in. `drag_start` leaves rope bodies to this.
+pub fn bezier_rope_drag(
+    click: Trigger<Pointer<Drag>>,
+    shape_types: Query<&ShapeType>,
+    mut ropes: Query<(&mut BezierRope, &BezierId)>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    input: Res<BezierInputMap>,
+    locks: BezierLocks,
+) {
+    if click.button != input.drag_button {
+        return;
+    }
+    let Ok(ShapeType::Bezier(bezier_shape)) = shape_types.get(click.target) else {
+        return;
+    };
+    if !matches!(bezier_shape.shape_type, BezierShapeType::BezierLine)
+        || locks.curve_locked(bezier_shape.id)
+    {
+        return;
+    }
+    let Some((mut rope, _)) = ropes.iter_mut().find(|(_, id)| id.0 == bezier_shape.id) else {
+        return;
+    };
+    let Some(points) = collect_bezier_points(shape_types.iter())
+        .get(&bezier_shape.id)
+        .copied()
+    else {
+        return;
+    };
+    let Some(delta) = cameras
+        .get(click.hit.camera)
+        .ok()
+        .and_then(|(camera, transform)| {
+            pointer_delta_to_world(
+                camera,
+                transform,
+                click.pointer_location.position,
+                click.delta,
+            )
+        })
+    else {
+        return;
+    };
+    let [a, b, c, d] = points;
+    // the current drop of the curve's middle below its chord
+    let sag = (a.lerp(d, 0.5) - evaluate_bezier(a, b, c, d, 0.5)).y;
+    let rope_curve = sag_bezier(a, d, (sag - delta.y).max(0.0));
+    rope.slack = rope_curve.arc_length() - a.distance(d);
+}
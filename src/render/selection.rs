@@ -0,0 +1,280 @@
+use super::*;
+
+/// The anchor or control point last clicked, which the arrow keys nudge.
+#[derive(Resource, Clone, Debug)]
+pub struct SelectedBezierPoint {
+    pub id: usize,
+    pub part: BezierShapeType,
+}
+
+/// Every selected part: anchors and controls, or whole curves as their
+/// `BezierLine`. Dragging any selected part moves all of them.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct BezierSelection {
+    pub parts: Vec<(usize, BezierShapeType)>,
+}
+
+impl BezierSelection {
+    pub fn contains(&self, id: usize, part: &BezierShapeType) -> bool {
+        self.parts.iter().any(|(i, p)| *i == id && p == part)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+    pub fn clear(&mut self) {
+        self.parts.clear();
+    }
+    /// Makes `part` of curve `id` the only selected part.
+    pub fn select(&mut self, id: usize, part: BezierShapeType) {
+        self.parts.clear();
+        self.parts.push((id, part));
+    }
+    /// Adds `part` of curve `id`, or removes it if it is already selected.
+    pub fn toggle(&mut self, id: usize, part: BezierShapeType) {
+        if let Some(index) = self.parts.iter().position(|(i, p)| *i == id && *p == part) {
+            self.parts.remove(index);
+        } else {
+            self.parts.push((id, part));
+        }
+    }
+    /// Selects every anchor and control inside `rect`, added to the current
+    /// selection if `additive`, otherwise replacing it.
+    pub fn select_in_rect<'a>(
+        &mut self,
+        shape_types: impl IntoIterator<Item = &'a ShapeType>,
+        rect: Rect,
+        additive: bool,
+    ) {
+        if !additive {
+            self.parts.clear();
+        }
+        for shape_type in shape_types {
+            let ShapeType::Bezier(bezier_shape) = shape_type else {
+                continue;
+            };
+            let parts: Vec<(BezierShapeType, Vec2)> = match bezier_shape.handles {
+                Some(handles) => [
+                    BezierShapeType::Start,
+                    BezierShapeType::ControlStart,
+                    BezierShapeType::ControlEnd,
+                    BezierShapeType::End,
+                ]
+                .into_iter()
+                .zip(handles)
+                .collect(),
+                None => bezier_shape
+                    .point
+                    .map(|point| (bezier_shape.shape_type.clone(), point))
+                    .into_iter()
+                    .collect(),
+            };
+            for (part, point) in parts {
+                if rect.contains(point) && !self.contains(bezier_shape.id, &part) {
+                    self.parts.push((bezier_shape.id, part));
+                }
+            }
+        }
+    }
+    /// Like `select_in_rect`, but only looks at the points `index` has
+    /// filed near `rect`.
+    pub fn select_indexed(&mut self, index: &BezierPointIndex, rect: Rect, additive: bool) {
+        if !additive {
+            self.parts.clear();
+        }
+        for point in index.in_rect(rect) {
+            if !self.contains(point.id, &point.part) {
+                self.parts.push((point.id, point.part.clone()));
+            }
+        }
+    }
+    /// Ids of the curves with a selected part, in ascending order.
+    pub fn curves(&self) -> Vec<usize> {
+        let mut ids: Vec<usize> = self.parts.iter().map(|(id, _)| *id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+}
+
+/// A curve moved along with a group drag.
+#[derive(Clone, Debug, Reflect)]
+pub struct BezierGroupMember {
+    pub id: usize,
+    pub parts: Vec<BezierShapeType>,
+    pub quadratic: bool,
+    pub start_points: [Vec2; 4],
+    pub points: [Vec2; 4],
+}
+
+impl BezierGroupMember {
+    /// The curve's points with its selected parts moved by `delta`.
+    pub fn moved(&self, delta: Vec2) -> [Vec2; 4] {
+        let [a, b, c, d] = self.start_points;
+        let mut state = BezierDragState {
+            a: Some(a),
+            b: Some(b),
+            c: Some(c),
+            d: Some(d),
+            quadratic: self.quadratic,
+            ..default()
+        };
+        if self.parts.contains(&BezierShapeType::BezierLine) {
+            state.dragging = BezierShapeType::BezierLine;
+            state.add_delta(delta);
+        } else {
+            for part in &self.parts {
+                state.dragging = part.clone();
+                state.add_delta(delta);
+            }
+        }
+        state.points().unwrap_or(self.start_points)
+    }
+}
+
+/// The group a drag on `id`'s `part` moves: every curve with a selected
+/// part, if the dragged part is selected along with others.
+pub(crate) fn group_drag_members<'a>(
+    selection: &BezierSelection,
+    shape_types: impl IntoIterator<Item = &'a ShapeType> + Clone,
+    id: usize,
+    part: &BezierShapeType,
+) -> Vec<BezierGroupMember> {
+    if selection.parts.len() < 2 || !selection.contains(id, part) {
+        return Vec::new();
+    }
+    let curves = collect_bezier_points(shape_types.clone());
+    let quadratics: HashSet<usize> = shape_types
+        .into_iter()
+        .filter_map(|shape_type| match shape_type {
+            ShapeType::Bezier(bezier_shape)
+                if matches!(bezier_shape.shape_type, BezierShapeType::Control) =>
+            {
+                Some(bezier_shape.id)
+            }
+            _ => None,
+        })
+        .collect();
+    selection
+        .curves()
+        .into_iter()
+        .filter_map(|id| {
+            let points = *curves.get(&id)?;
+            Some(BezierGroupMember {
+                id,
+                parts: selection
+                    .parts
+                    .iter()
+                    .filter(|(i, _)| *i == id)
+                    .map(|(_, part)| part.clone())
+                    .collect(),
+                quadratic: quadratics.contains(&id),
+                start_points: points,
+                points,
+            })
+        })
+        .collect()
+}
+
+/// World units moved per arrow key press, and with Shift held.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct BezierNudgeSettings {
+    pub step: f32,
+    pub large_step: f32,
+}
+
+impl Default for BezierNudgeSettings {
+    fn default() -> Self {
+        Self {
+            step: 1.0,
+            large_step: 10.0,
+        }
+    }
+}
+
+/// Selects the clicked part, or with Shift held adds it to (or removes it
+/// from) the selection. Anchors and controls also become the point the
+/// arrow keys nudge; clicking the curve body selects the whole curve.
+pub fn select_bezier_point(
+    click: Trigger<Pointer<Click>>,
+    query: Query<&ShapeType>,
+    keys: Res<ButtonInput<KeyCode>>,
+    input: Res<BezierInputMap>,
+    mut selection: ResMut<BezierSelection>,
+    mut commands: Commands,
+) {
+    if click.button != input.drag_button {
+        return;
+    }
+    let Ok(ShapeType::Bezier(bezier_shape)) = query.get(click.target) else {
+        return;
+    };
+    let part = match click.hit.position {
+        Some(position) => bezier_shape.part_at(position.truncate()),
+        None => bezier_shape.shape_type.clone(),
+    };
+    // `point_of` misses the hit circles of `BezierStyle::pick_radius`
+    let is_point = matches!(
+        part,
+        BezierShapeType::Start
+            | BezierShapeType::ControlStart
+            | BezierShapeType::ControlEnd
+            | BezierShapeType::End
+            | BezierShapeType::Control
+    );
+    if !is_point && !matches!(part, BezierShapeType::BezierLine) {
+        return;
+    }
+    if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        selection.toggle(bezier_shape.id, part.clone());
+    } else {
+        selection.select(bezier_shape.id, part.clone());
+    }
+    if is_point {
+        commands.insert_resource(SelectedBezierPoint {
+            id: bezier_shape.id,
+            part,
+        });
+    }
+}
+
+/// Moves the selected point with the arrow keys, Shift for larger steps,
+/// as a `BezierCommand::MovePoint`.
+pub fn nudge_selected_bezier_point(
+    keys: Res<ButtonInput<KeyCode>>,
+    selected: Option<Res<SelectedBezierPoint>>,
+    settings: Res<BezierNudgeSettings>,
+    mut bezier_commands: ResMut<BezierCommands>,
+    locks: BezierLocks,
+) {
+    let Some(selected) = selected else {
+        return;
+    };
+    if locks.part_locked(selected.id, &selected.part) {
+        return;
+    }
+    let mut direction = Vec2::ZERO;
+    for (key, step) in [
+        (KeyCode::ArrowLeft, Vec2::NEG_X),
+        (KeyCode::ArrowRight, Vec2::X),
+        (KeyCode::ArrowUp, Vec2::Y),
+        (KeyCode::ArrowDown, Vec2::NEG_Y),
+    ] {
+        if keys.just_pressed(key) {
+            direction += step;
+        }
+    }
+    if direction == Vec2::ZERO {
+        return;
+    }
+    let step = if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        settings.large_step
+    } else {
+        settings.step
+    };
+
+    bezier_commands.push(BezierCommand::MovePoint {
+        id: selected.id,
+        part: selected.part.clone(),
+        delta: direction * step,
+    });
+}
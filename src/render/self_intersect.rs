@@ -0,0 +1,60 @@
+use super::*;
+
+/// Marks the spots where a curve or spline crosses itself with a cross,
+/// since fills and offsets of such shapes misbehave. Off by default; each
+/// frame's check costs an intersection test per pair of spline segments.
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct BezierSelfIntersectionWarnings {
+    pub enabled: bool,
+    pub color: Color,
+    /// Half the width of the cross, in world units.
+    pub size: f32,
+}
+
+impl Default for BezierSelfIntersectionWarnings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            color: Color::srgb(1.0, 0.4, 0.0),
+            size: 6.0,
+        }
+    }
+}
+
+/// Draws `BezierSelfIntersectionWarnings`.
+pub fn draw_bezier_self_intersections(
+    mut gizmos: Gizmos,
+    settings: Res<BezierSelfIntersectionWarnings>,
+    registry: Res<BezierRegistry>,
+    splines: Query<&BezierSpline>,
+) {
+    if !settings.enabled {
+        return;
+    }
+    let curves: BTreeMap<usize, [Vec2; 4]> = registry
+        .iter()
+        .map(|(id, entry)| (id, entry.points))
+        .collect();
+    let mut in_spline = HashSet::new();
+    let mut crossings = Vec::new();
+    for spline in splines.iter() {
+        in_spline.extend(spline.segments.iter().copied());
+        if let Some(cubics) = spline.cubics(&curves) {
+            crossings.extend(spline_self_intersections(&cubics, spline.closed));
+        }
+    }
+    for (id, &points) in &curves {
+        if !in_spline.contains(id) {
+            crossings.extend(CubicBezier::from(points).self_intersections());
+        }
+    }
+    let (diagonal, anti) = (
+        Vec2::splat(settings.size),
+        Vec2::new(settings.size, -settings.size),
+    );
+    for (_, _, point) in crossings {
+        gizmos.line_2d(point - diagonal, point + diagonal, settings.color);
+        gizmos.line_2d(point - anti, point + anti, settings.color);
+    }
+}
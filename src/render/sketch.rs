@@ -0,0 +1,139 @@
+use super::*;
+use bevy::window::PrimaryWindow;
+
+/// Freehand sketching. While `enabled`, dragging across empty space records
+/// a stroke, drawn as a `ShapeType::Sketch` shape, and releasing fits it
+/// with `fit_points` and spawns the result as an editable `BezierSpline`.
+#[derive(Resource, Clone, Debug)]
+pub struct BezierSketchMode {
+    pub enabled: bool,
+    /// Furthest the fitted curves may stray from the stroke, in world units.
+    pub max_error: f32,
+    /// Pointer movement smaller than this isn't recorded, so a slow hand
+    /// doesn't pile up points.
+    pub min_distance: f32,
+    points: Vec<Vec2>,
+}
+
+impl Default for BezierSketchMode {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_error: 4.0,
+            min_distance: 2.0,
+            points: Vec::new(),
+        }
+    }
+}
+
+impl BezierSketchMode {
+    pub fn is_sketching(&self) -> bool {
+        !self.points.is_empty()
+    }
+    /// Points of the stroke recorded so far.
+    pub fn points(&self) -> &[Vec2] {
+        &self.points
+    }
+}
+
+/// Spline points of the curves `fit_points` finds for `stroke`, laid out as
+/// `spawn_bezier_spline` expects.
+pub fn sketch_spline_points(stroke: &[Vec2], max_error: f32) -> Vec<Vec2> {
+    let curves = fit_points(stroke, max_error);
+    let mut points = Vec::with_capacity(curves.len() * 3 + 1);
+    if let Some(first) = curves.first() {
+        points.push(first.a);
+    }
+    for curve in &curves {
+        points.extend([curve.b, curve.c, curve.d]);
+    }
+    points
+}
+
+/// Records strokes for `BezierSketchMode` and turns them into curves on
+/// release. Presses over existing curve parts are left to dragging.
+#[allow(clippy::too_many_arguments)]
+pub fn bezier_sketch_tool(
+    mut commands: Commands,
+    mut mode: ResMut<BezierSketchMode>,
+    mut ids: ResMut<BezierIdAllocator>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    hovered: Query<(), With<BezierHovered>>,
+    style: Res<BezierStyle>,
+) {
+    if !mode.enabled {
+        return;
+    }
+    if mouse.just_released(MouseButton::Left) && mode.is_sketching() {
+        let points = sketch_spline_points(&mode.points, mode.max_error);
+        spawn_bezier_spline(&mut commands, &style, &mut ids, &points);
+        mode.points.clear();
+        return;
+    }
+
+    let cursor = windows.single().ok().and_then(Window::cursor_position);
+    let Some((camera, camera_transform)) = cameras.iter().find(|(camera, _)| camera.is_active)
+    else {
+        return;
+    };
+    let Some(position) =
+        cursor.and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+    else {
+        return;
+    };
+
+    if mouse.just_pressed(MouseButton::Left) && hovered.is_empty() {
+        mode.points.push(position);
+    } else if mode.is_sketching() && mouse.pressed(MouseButton::Left) {
+        let far_enough = mode
+            .points
+            .last()
+            .is_none_or(|last| last.distance(position) >= mode.min_distance);
+        if far_enough {
+            mode.points.push(position);
+        }
+    }
+}
+
+/// Draws the stroke being sketched as a `ShapeType::Sketch` shape.
+pub fn update_bezier_sketch_stroke(
+    mut commands: Commands,
+    mode: Res<BezierSketchMode>,
+    mut strokes: Query<(Entity, &mut Shape, &ShapeType)>,
+    style: Res<BezierStyle>,
+) {
+    if !mode.is_changed() && !style.is_changed() {
+        return;
+    }
+    let mut existing = strokes
+        .iter_mut()
+        .filter(|(_, _, shape_type)| matches!(shape_type, ShapeType::Sketch));
+    let Some((first, rest)) = mode.points.split_first() else {
+        for (entity, _, _) in existing {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+
+    let mut path = ShapePath::new().move_to(*first);
+    for point in rest {
+        path = path.line_to(*point);
+    }
+    let shape = ShapeBuilder::with(&path)
+        .stroke((style.sketch_color, style.sketch_stroke_width))
+        .build();
+
+    if let Some((_, mut stroke, _)) = existing.next() {
+        *stroke = shape;
+    } else {
+        let z = style.layering.base_z + style.layering.curve;
+        commands.spawn((
+            ShapeType::Sketch,
+            shape,
+            Transform::from_xyz(0.0, 0.0, z),
+            Pickable::IGNORE,
+        ));
+    }
+}
@@ -0,0 +1,141 @@
+use super::*;
+
+/// Snapping applied while dragging: points land on grid intersections,
+/// handles can be held to fixed angles around their anchor, and anchors
+/// land on nearby anchors of other curves.
+#[derive(Resource, Clone, Debug)]
+pub struct BezierSnapSettings {
+    pub grid_enabled: bool,
+    /// Grid cell size in world units.
+    pub grid_size: f32,
+    /// Handle angle step in radians, used while a `angle_keys` key is held.
+    /// Ctrl alone extends a handle (`BezierEditSettings::extend_handle_keys`),
+    /// so by default handles snap with Alt+Ctrl, rotating at a fixed length.
+    pub angle_increment: f32,
+    pub angle_keys: Vec<KeyCode>,
+    /// A dragged `Start` or `End` anchor within this many world units of
+    /// another curve's `Start` or `End` jumps onto it. `None` turns anchor
+    /// snapping off.
+    pub anchor_radius: Option<f32>,
+    /// Color of the circle drawn around the anchor about to be snapped to.
+    pub indicator_color: Color,
+    /// Weld anchors snapped together, see `weld_snapped_anchors`.
+    pub weld_on_snap: bool,
+}
+
+impl Default for BezierSnapSettings {
+    fn default() -> Self {
+        Self {
+            grid_enabled: false,
+            grid_size: 10.0,
+            angle_increment: std::f32::consts::FRAC_PI_4,
+            angle_keys: vec![KeyCode::ControlLeft, KeyCode::ControlRight],
+            anchor_radius: Some(8.0),
+            indicator_color: Color::srgb(1.0, 0.8, 0.0),
+            weld_on_snap: false,
+        }
+    }
+}
+
+impl BezierSnapSettings {
+    /// Adjusts `delta`, the world-space movement since the drag started,
+    /// so the dragged point of `drag` lands where snapping wants it. Dragging
+    /// the curve body snaps its `Start` anchor and moves the rest along.
+    pub fn snap_drag_delta(&self, drag: &BezierDragState, delta: Vec2, angle: bool) -> Vec2 {
+        let Some([a, b, c, d]) = drag.start_points else {
+            return delta;
+        };
+        let (start, anchor) = match drag.dragging {
+            BezierShapeType::Start | BezierShapeType::BezierLine => (a, None),
+            BezierShapeType::ControlStart => (b, Some(a)),
+            BezierShapeType::ControlEnd => (c, Some(d)),
+            BezierShapeType::End => (d, None),
+            BezierShapeType::Control => (reduce_to_quadratic(a, b, c, d), Some(a)),
+            BezierShapeType::Line | BezierShapeType::Handles | BezierShapeType::MoveGrip => {
+                return delta;
+            }
+        };
+        let mut target = start + delta;
+        if self.grid_enabled {
+            target = snap_to_grid(target, self.grid_size);
+        }
+        if let (true, Some(anchor)) = (angle, anchor) {
+            target = snap_to_angle(anchor, target, self.angle_increment);
+        }
+        target - start
+    }
+}
+
+/// Another curve's anchor a dragged anchor has snapped to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnchorSnap {
+    pub id: usize,
+    /// `Start` or `End`.
+    pub part: BezierShapeType,
+    pub position: Vec2,
+}
+
+/// The drag of `part` of curve `id` ended with it snapped onto `to`.
+/// Listen for it to record the join, or set
+/// `BezierSnapSettings::weld_on_snap` to weld the two anchors.
+#[derive(Event, Clone, Debug)]
+pub struct BezierAnchorSnapped {
+    pub id: usize,
+    pub part: BezierShapeType,
+    pub to: AnchorSnap,
+}
+
+/// The `Start` or `End` anchor in `index` closest to `target` within
+/// `radius`, skipping the curves in `exclude`.
+pub fn nearest_anchor(
+    index: &BezierPointIndex,
+    exclude: &HashSet<usize>,
+    target: Vec2,
+    radius: f32,
+) -> Option<AnchorSnap> {
+    index
+        .nearest(target, radius, |point| {
+            matches!(point.part, BezierShapeType::Start | BezierShapeType::End)
+                && !exclude.contains(&point.id)
+        })
+        .map(|point| AnchorSnap {
+            id: point.id,
+            part: point.part.clone(),
+            position: point.position,
+        })
+}
+
+/// Circles the anchor each dragged anchor is snapped to.
+pub fn draw_bezier_anchor_snaps(
+    mut gizmos: Gizmos,
+    drags: Res<BezierDrag>,
+    settings: Res<BezierSnapSettings>,
+) {
+    let Some(radius) = settings.anchor_radius else {
+        return;
+    };
+    for snap in drags
+        .pointers
+        .values()
+        .filter_map(|drag| drag.anchor_snap.as_ref())
+    {
+        gizmos.circle_2d(
+            Isometry2d::from_translation(snap.position),
+            radius,
+            settings.indicator_color,
+        );
+    }
+}
+
+/// Flips grid snapping on `BezierInputMap::snap_toggle_key`.
+pub fn toggle_bezier_grid_snap(
+    keys: Res<ButtonInput<KeyCode>>,
+    input: Res<BezierInputMap>,
+    mut settings: ResMut<BezierSnapSettings>,
+) {
+    if let Some(key) = input.snap_toggle_key {
+        if keys.just_pressed(key) {
+            settings.grid_enabled = !settings.grid_enabled;
+        }
+    }
+}
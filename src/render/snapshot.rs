@@ -0,0 +1,99 @@
+use super::*;
+use bevy::asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+/// Rasterizes `curves` on the CPU into a `resolution` sized image, e.g. for
+/// thumbnails of saved drawings without a window or GPU. The curves are
+/// scaled to fit with a margin of one stroke width and drawn with
+/// `BezierStyle::bezier_line_color` on a transparent background; curves
+/// that end where they start are filled with `fill_color` too.
+pub fn render_to_image(curves: &[CubicBezier], style: &BezierStyle, resolution: UVec2) -> Image {
+    let size = Extent3d {
+        width: resolution.x.max(1),
+        height: resolution.y.max(1),
+        depth_or_array_layers: 1,
+    };
+    let Some(mut pixmap) = tiny_skia::Pixmap::new(size.width, size.height) else {
+        return Image::default();
+    };
+
+    let bounds = curves.iter().fold(Rect::EMPTY, |bounds, curve| {
+        bounds.union(bezier_tight_bounds(curve.a, curve.b, curve.c, curve.d))
+    });
+    if !bounds.is_empty() {
+        let margin = style.bezier_stroke_width;
+        let bounds = bounds.inflate(margin);
+        let scale = (size.width as f32 / bounds.width().max(f32::EPSILON))
+            .min(size.height as f32 / bounds.height().max(f32::EPSILON));
+        let center = Vec2::new(size.width as f32, size.height as f32) * 0.5;
+        // world y points up, image rows go down
+        let to_image = |p: Vec2| {
+            let p = (p - bounds.center()) * scale;
+            (center.x + p.x, center.y - p.y)
+        };
+        let paint = |color: Color| {
+            let color = color.to_srgba();
+            let mut paint = tiny_skia::Paint::default();
+            paint.set_color_rgba8(
+                (color.red * 255.0) as u8,
+                (color.green * 255.0) as u8,
+                (color.blue * 255.0) as u8,
+                (color.alpha * 255.0) as u8,
+            );
+            paint.anti_alias = true;
+            paint
+        };
+        let stroke = tiny_skia::Stroke {
+            width: style.bezier_stroke_width * scale,
+            ..default()
+        };
+        for curve in curves {
+            let mut builder = tiny_skia::PathBuilder::new();
+            let (ax, ay) = to_image(curve.a);
+            let (bx, by) = to_image(curve.b);
+            let (cx, cy) = to_image(curve.c);
+            let (dx, dy) = to_image(curve.d);
+            builder.move_to(ax, ay);
+            builder.cubic_to(bx, by, cx, cy, dx, dy);
+            let closed = curve.a == curve.d;
+            if closed {
+                builder.close();
+            }
+            let Some(path) = builder.finish() else {
+                continue;
+            };
+            if closed {
+                pixmap.fill_path(
+                    &path,
+                    &paint(style.fill_color),
+                    tiny_skia::FillRule::Winding,
+                    tiny_skia::Transform::identity(),
+                    None,
+                );
+            }
+            pixmap.stroke_path(
+                &path,
+                &paint(style.bezier_line_color),
+                &stroke,
+                tiny_skia::Transform::identity(),
+                None,
+            );
+        }
+    }
+
+    let data = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+    Image::new(
+        size,
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
@@ -0,0 +1,83 @@
+use super::*;
+
+/// Coordinate space of a curve's points. `World` curves live in 2D world
+/// units with y up. `Ui` curves use UI logical pixels from the top-left of
+/// the window with y down, the space `Node`s are laid out in, so connectors
+/// can be drawn between UI nodes; their root is kept mapped onto the active
+/// camera by `sync_ui_bezier_roots`. Put on the root and every part.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub enum BezierSpace {
+    #[default]
+    World,
+    Ui,
+}
+
+/// Spawns a new open curve in UI space with a fresh id, see `BezierSpace`.
+pub fn spawn_ui_bezier(
+    commands: &mut Commands,
+    style: &BezierStyle,
+    ids: &mut BezierIdAllocator,
+    a: Vec2,
+    b: Vec2,
+    c: Vec2,
+    d: Vec2,
+) -> BezierId {
+    let id = ids.next_id();
+    let children = spawn_bezier_shapes(commands, style, bezier_open(style, id, a, b, c, d));
+    for child in &children {
+        commands.entity(*child).insert(BezierSpace::Ui);
+    }
+    commands
+        .spawn((BezierId(id), BezierSpace::Ui))
+        .add_children(&children);
+    BezierId(id)
+}
+
+/// Places the roots of UI space curves so their points, in logical pixels,
+/// land under the same pixels of the active camera's viewport.
+pub fn sync_ui_bezier_roots(
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut roots: Query<(&BezierSpace, &mut Transform), With<BezierId>>,
+) {
+    let Some((camera, camera_transform)) = cameras.iter().find(|(camera, _)| camera.is_active)
+    else {
+        return;
+    };
+    let to_world = |p: Vec2| camera.viewport_to_world_2d(camera_transform, p).ok();
+    let (Some(origin), Some(x), Some(y)) =
+        (to_world(Vec2::ZERO), to_world(Vec2::X), to_world(Vec2::Y))
+    else {
+        return;
+    };
+    let (x, y) = (x - origin, y - origin);
+    for (space, mut transform) in roots.iter_mut() {
+        if *space != BezierSpace::Ui {
+            continue;
+        }
+        let wanted = Transform {
+            translation: origin.extend(transform.translation.z),
+            rotation: Quat::from_rotation_z(x.to_angle()),
+            // y down in UI, up in the world
+            scale: Vec3::new(x.length(), -y.length(), 1.0),
+        };
+        if *transform != wanted {
+            *transform = wanted;
+        }
+    }
+}
+
+/// Position of `target` in `space`: its world translation, or for a UI
+/// node in UI space, its center in logical pixels.
+pub fn position_in_space(
+    space: BezierSpace,
+    target: &GlobalTransform,
+    node: Option<&ComputedNode>,
+) -> Vec2 {
+    let position = target.translation().truncate();
+    match (space, node) {
+        // UI layout places nodes in physical pixels
+        (BezierSpace::Ui, Some(node)) => position * node.inverse_scale_factor(),
+        _ => position,
+    }
+}
@@ -0,0 +1,580 @@
+use super::*;
+
+/// A chain of connected cubic segments. Every segment is an ordinary curve
+/// with its own id; the `End` of segment `i` is the same point as the
+/// `Start` of segment `i + 1`, and the drag systems keep them together.
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct BezierSpline {
+    pub segments: Vec<usize>,
+    /// How the two handles meeting at joint `i` (between segments `i` and
+    /// `i + 1`) move together. Missing entries are `Free`.
+    pub handle_modes: Vec<HandleMode>,
+    /// The last segment ends on the first segment's `Start` anchor and the
+    /// enclosed region is filled.
+    pub closed: bool,
+}
+
+/// Coupling of the two control handles on either side of a spline anchor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HandleMode {
+    /// Each handle moves on its own, leaving a corner.
+    #[default]
+    Free,
+    /// The opposite handle mirrors direction and length (C1 continuity).
+    Mirrored,
+    /// The opposite handle keeps its length but stays collinear (G1).
+    Aligned,
+    /// Mirrored, and the next segment's far handle follows so the curvature
+    /// matches across the joint too (C2).
+    Curvature,
+}
+
+impl HandleMode {
+    /// Where the handle opposite `moved` across `anchor` should go, given
+    /// where it is now.
+    pub fn opposite(self, moved: Vec2, anchor: Vec2, current: Vec2) -> Vec2 {
+        match self {
+            HandleMode::Free => current,
+            HandleMode::Mirrored | HandleMode::Curvature => 2.0 * anchor - moved,
+            HandleMode::Aligned => match (anchor - moved).try_normalize() {
+                Some(direction) => anchor + direction * current.distance(anchor),
+                None => current,
+            },
+        }
+    }
+}
+
+impl BezierSpline {
+    pub fn handle_mode(&self, joint: usize) -> HandleMode {
+        self.handle_modes.get(joint).copied().unwrap_or_default()
+    }
+    pub fn set_handle_mode(&mut self, joint: usize, mode: HandleMode) {
+        if joint >= self.handle_modes.len() {
+            self.handle_modes.resize(joint + 1, HandleMode::Free);
+        }
+        self.handle_modes[joint] = mode;
+    }
+    /// Index of the segment before `index`, wrapping around closed splines.
+    pub fn previous_index(&self, index: usize) -> Option<usize> {
+        match index.checked_sub(1) {
+            Some(previous) => Some(previous),
+            None if self.closed && self.segments.len() > 1 => Some(self.segments.len() - 1),
+            None => None,
+        }
+    }
+    /// Index of the segment after `index`, wrapping around closed splines.
+    pub fn next_index(&self, index: usize) -> Option<usize> {
+        if index + 1 < self.segments.len() {
+            Some(index + 1)
+        } else if self.closed && self.segments.len() > 1 {
+            Some(0)
+        } else {
+            None
+        }
+    }
+    /// Ids of the segments joined to the start and to the end of `id`.
+    pub fn neighbors(&self, id: usize) -> Option<(Option<usize>, Option<usize>)> {
+        let index = self.segments.iter().position(|&segment| segment == id)?;
+        let previous = self.previous_index(index).map(|i| self.segments[i]);
+        let next = self.next_index(index).map(|i| self.segments[i]);
+        Some((previous, next))
+    }
+    /// The spline's segments in order, or `None` if one is missing.
+    pub fn cubics(&self, curves: &BTreeMap<usize, [Vec2; 4]>) -> Option<Vec<CubicBezier>> {
+        self.segments
+            .iter()
+            .map(|id| curves.get(id).map(|&points| CubicBezier::from(points)))
+            .collect()
+    }
+    /// The spline as one Bevy `CubicCurve`, e.g. to animate along it with
+    /// Bevy's curve APIs. `None` if a segment is missing.
+    pub fn to_bevy_curve(&self, curves: &BTreeMap<usize, [Vec2; 4]>) -> Option<CubicCurve<Vec2>> {
+        bevy_curve(&self.cubics(curves)?)
+    }
+}
+
+/// Spawns a closed spline: like `spawn_bezier_spline` but the final anchor
+/// is left off (`3 * n` points) because the last segment returns to the
+/// first point.
+pub fn spawn_closed_bezier_spline(
+    commands: &mut Commands,
+    style: &BezierStyle,
+    ids: &mut BezierIdAllocator,
+    points: &[Vec2],
+) -> Option<Entity> {
+    if points.len() < 3 || points.len() % 3 != 0 {
+        return None;
+    }
+    let mut looped = points.to_vec();
+    looped.push(points[0]);
+    let segments = spawn_spline_segments(commands, style, ids, &looped);
+    let cubics: Vec<CubicBezier> = looped
+        .windows(4)
+        .step_by(3)
+        .map(|segment| CubicBezier::new(segment[0], segment[1], segment[2], segment[3]))
+        .collect();
+    let spline = BezierSpline {
+        segments,
+        closed: true,
+        ..default()
+    };
+    Some(spawn_spline_entity(commands, style, spline, &cubics))
+}
+
+/// Spawns the entity of `spline`, whose segments `cubics` already exist.
+/// A closed spline also gets its fill, drawn under the first segment.
+pub fn spawn_spline_entity(
+    commands: &mut Commands,
+    style: &BezierStyle,
+    spline: BezierSpline,
+    cubics: &[CubicBezier],
+) -> Entity {
+    let Some(&first) = spline.segments.first().filter(|_| spline.closed) else {
+        return commands.spawn(spline).id();
+    };
+    let fill_z = style.layering.layer_z(first) + style.layering.fill;
+    commands
+        .spawn((
+            spline,
+            closed_spline_fill(style, cubics),
+            Transform::from_xyz(0.0, 0.0, fill_z),
+            Pickable::IGNORE,
+        ))
+        .id()
+}
+
+/// Fill shape for the region enclosed by a closed spline.
+pub fn closed_spline_fill(style: &BezierStyle, segments: &[CubicBezier]) -> Shape {
+    let mut path = ShapePath::new();
+    if let Some(first) = segments.first() {
+        path = path.move_to(first.a);
+    }
+    for segment in segments {
+        path = path.cubic_bezier_to(segment.b, segment.c, segment.d);
+    }
+    ShapeBuilder::with(&path.close())
+        .fill(Fill {
+            options: FillOptions::default().with_fill_rule(style.fill_rule),
+            color: style.fill_color,
+        })
+        .build()
+}
+
+/// Rebuilds the fill of closed splines after any of their segments moved.
+pub fn update_closed_spline_fills(
+    mut splines: Query<(&BezierSpline, &mut Shape), Without<ShapeType>>,
+    changed: Query<(), Changed<ShapeType>>,
+    shapes: Query<&ShapeType>,
+    style: Res<BezierStyle>,
+) {
+    if changed.is_empty() && !style.is_changed() {
+        return;
+    }
+    let curves = collect_bezier_points(shapes.iter());
+    for (spline, mut shape) in splines.iter_mut() {
+        if !spline.closed {
+            continue;
+        }
+        if let Some(segments) = spline.cubics(&curves) {
+            *shape = closed_spline_fill(&style, &segments);
+        }
+    }
+}
+
+/// Spawns a spline through `points`, laid out as
+/// `[anchor, control, control, anchor, control, control, anchor, ...]`
+/// (`3 * n + 1` points for `n` segments). Returns the spline entity, or
+/// `None` if the points don't describe at least one whole segment.
+pub fn spawn_bezier_spline(
+    commands: &mut Commands,
+    style: &BezierStyle,
+    ids: &mut BezierIdAllocator,
+    points: &[Vec2],
+) -> Option<Entity> {
+    if points.len() < 4 || (points.len() - 1) % 3 != 0 {
+        return None;
+    }
+    let segments = spawn_spline_segments(commands, style, ids, points);
+    let spline = BezierSpline {
+        segments,
+        ..default()
+    };
+    Some(spawn_spline_entity(commands, style, spline, &[]))
+}
+
+/// Spawns an editable spline passing through `points`, e.g. a GPS track
+/// or level waypoints, see `spline_through_points` for `smoothing`.
+/// Returns the spline entity, or `None` for fewer than two distinct points.
+pub fn spawn_spline_through_points(
+    commands: &mut Commands,
+    style: &BezierStyle,
+    ids: &mut BezierIdAllocator,
+    points: &[Vec2],
+    smoothing: f32,
+) -> Option<Entity> {
+    let curves = spline_through_points(points, smoothing);
+    let first = curves.first()?;
+    let mut spline_points = vec![first.a];
+    for curve in &curves {
+        spline_points.extend([curve.b, curve.c, curve.d]);
+    }
+    spawn_bezier_spline(commands, style, ids, &spline_points)
+}
+
+/// Spawns an editable spline with the segments of a Bevy `CubicCurve`,
+/// e.g. a Hermite or B-spline built with Bevy math. The segments are
+/// assumed to join up, as those of Bevy's spline generators do. Returns the
+/// spline entity, or `None` for a curve without segments.
+pub fn spawn_bevy_curve(
+    commands: &mut Commands,
+    style: &BezierStyle,
+    ids: &mut BezierIdAllocator,
+    curve: &CubicCurve<Vec2>,
+) -> Option<Entity> {
+    let curves = from_bevy_curve(curve);
+    let first = curves.first()?;
+    let mut spline_points = vec![first.a];
+    for curve in &curves {
+        spline_points.extend([curve.b, curve.c, curve.d]);
+    }
+    spawn_bezier_spline(commands, style, ids, &spline_points)
+}
+
+fn spawn_spline_segments(
+    commands: &mut Commands,
+    style: &BezierStyle,
+    ids: &mut BezierIdAllocator,
+    points: &[Vec2],
+) -> Vec<usize> {
+    points
+        .windows(4)
+        .step_by(3)
+        .map(|segment| {
+            let id = ids.next_id();
+            let shapes = bezier_open(style, id, segment[0], segment[1], segment[2], segment[3]);
+            spawn_bezier_with_id(commands, style, id, shapes);
+            id
+        })
+        .collect()
+}
+
+/// Time allowed between the two clicks of a double-click, in seconds.
+const DOUBLE_CLICK_SECONDS: f64 = 0.3;
+
+/// Remembers the last click on a curve body, and on an anchor, to
+/// recognise double-clicks.
+#[derive(Resource, Default)]
+pub struct BezierClickTracker {
+    last: Option<(usize, f64)>,
+    last_anchor: Option<(usize, BezierShapeType, f64)>,
+}
+
+/// Double-clicking an anchor shared by two spline segments toggles the
+/// joint between a corner (`HandleMode::Free`) and smooth
+/// (`HandleMode::Aligned`), lining its handles up when it becomes smooth.
+/// Drags then keep the handles as the joint's mode says.
+#[allow(clippy::too_many_arguments)]
+pub fn bezier_double_click_anchor(
+    click: Trigger<Pointer<Click>>,
+    query: Query<&ShapeType>,
+    splines: Query<&BezierSpline>,
+    mut bezier_commands: ResMut<BezierCommands>,
+    mut tracker: ResMut<BezierClickTracker>,
+    time: Res<Time>,
+    input: Res<BezierInputMap>,
+    drag: Res<BezierDrag>,
+    locks: BezierLocks,
+) {
+    if click.button != input.drag_button || drag.click_ends_drag(&click.pointer_id) {
+        return;
+    }
+    let Ok(ShapeType::Bezier(bezier_shape)) = query.get(click.target) else {
+        return;
+    };
+    let part = match click.hit.position {
+        Some(position) => bezier_shape.part_at(position.truncate()),
+        None => bezier_shape.shape_type.clone(),
+    };
+    if !matches!(part, BezierShapeType::Start | BezierShapeType::End) {
+        return;
+    }
+    let id = bezier_shape.id;
+    let now = time.elapsed_secs_f64();
+    let double = matches!(&tracker.last_anchor, Some((last_id, last_part, at))
+        if *last_id == id && *last_part == part && now - at <= DOUBLE_CLICK_SECONDS);
+    tracker.last_anchor = if double {
+        None
+    } else {
+        Some((id, part.clone(), now))
+    };
+    if !double || locks.part_locked(id, &part) {
+        return;
+    }
+    let Some((spline, joint)) = splines.iter().find_map(|spline| {
+        let index = spline.segments.iter().position(|&segment| segment == id)?;
+        let joint = match part {
+            BezierShapeType::End => spline.next_index(index).map(|_| index),
+            _ => spline.previous_index(index),
+        }?;
+        Some((spline, joint))
+    }) else {
+        return;
+    };
+    let mode = match spline.handle_mode(joint) {
+        HandleMode::Free => HandleMode::Aligned,
+        _ => HandleMode::Free,
+    };
+    bezier_commands.push(BezierCommand::SetJointMode { id, joint, mode });
+}
+
+/// Double-clicking a curve, or clicking it with an
+/// `BezierInputMap::add_anchor_keys` key held, inserts an anchor at
+/// the clicked point by splitting the curve in two. The halves become (or
+/// stay) segments of a spline so they remain joined, and together trace
+/// the original curve exactly.
+#[allow(clippy::too_many_arguments)]
+pub fn bezier_double_click_split(
+    click: Trigger<Pointer<Click>>,
+    query: Query<&ShapeType>,
+    mut bezier_commands: ResMut<BezierCommands>,
+    mut tracker: ResMut<BezierClickTracker>,
+    time: Res<Time>,
+    keys: Res<ButtonInput<KeyCode>>,
+    input: Res<BezierInputMap>,
+    drag: Res<BezierDrag>,
+) {
+    if click.button != input.drag_button || drag.click_ends_drag(&click.pointer_id) {
+        return;
+    }
+    let Ok(ShapeType::Bezier(bezier_shape)) = query.get(click.target) else {
+        return;
+    };
+    if !matches!(bezier_shape.shape_type, BezierShapeType::BezierLine) || bezier_shape.closed {
+        return;
+    }
+    let id = bezier_shape.id;
+    let now = time.elapsed_secs_f64();
+    let insert = keys.any_pressed(input.add_anchor_keys.iter().copied());
+    let double = matches!(tracker.last, Some((last_id, at)) if last_id == id && now - at <= DOUBLE_CLICK_SECONDS);
+    tracker.last = if double || insert {
+        None
+    } else {
+        Some((id, now))
+    };
+    let (true, Some(position)) = (double || insert, click.hit.position) else {
+        return;
+    };
+    bezier_commands.push(BezierCommand::InsertAnchor {
+        id,
+        position: position.truncate(),
+    });
+}
+
+/// Splits curve `id` at the point on it closest to `position`, turning that
+/// point into a new anchor. Returns the id of the second half, or `None`
+/// when the closest point is one of the curve's ends.
+pub fn insert_bezier_anchor(
+    commands: &mut Commands,
+    query: &mut Query<(Entity, &mut Shape, &mut ShapeType)>,
+    splines: &mut Query<&mut BezierSpline>,
+    ids: &mut BezierIdAllocator,
+    style: &BezierStyle,
+    id: usize,
+    position: Vec2,
+) -> Option<usize> {
+    let points = collect_bezier_points(query.iter().map(|(_, _, s)| s)).remove(&id)?;
+    let curve = CubicBezier::from(points);
+    let (t, _, _) = curve.closest_point(position);
+    if t <= 0.0 || t >= 1.0 {
+        return None;
+    }
+    let (first, second) = curve.split(t);
+    let second_id = ids.next_id();
+    update_bezier_in_place(style, query, id, first.points());
+    let CubicBezier { a, b, c, d } = second;
+    spawn_bezier_with_id(
+        commands,
+        style,
+        second_id,
+        bezier_open(style, second_id, a, b, c, d),
+    );
+
+    match splines
+        .iter_mut()
+        .find(|spline| spline.segments.contains(&id))
+    {
+        Some(mut spline) => {
+            let index = spline
+                .segments
+                .iter()
+                .position(|&segment| segment == id)
+                .unwrap();
+            spline.segments.insert(index + 1, second_id);
+            // the new joint starts out smooth, as the split curve was
+            if spline.handle_modes.len() < index {
+                spline.handle_modes.resize(index, HandleMode::Free);
+            }
+            spline.handle_modes.insert(index, HandleMode::Aligned);
+        }
+        None => {
+            commands.spawn(BezierSpline {
+                segments: vec![id, second_id],
+                handle_modes: vec![HandleMode::Aligned],
+                ..default()
+            });
+        }
+    }
+    Some(second_id)
+}
+
+/// Clicking an anchor shared by two spline segments with an
+/// `BezierInputMap::delete_anchor_keys` key held removes it, see
+/// `remove_anchor`.
+pub fn bezier_click_remove_anchor(
+    click: Trigger<Pointer<Click>>,
+    mut bezier_commands: ResMut<BezierCommands>,
+    query: Query<&ShapeType>,
+    splines: Query<&BezierSpline>,
+    keys: Res<ButtonInput<KeyCode>>,
+    input: Res<BezierInputMap>,
+    drag: Res<BezierDrag>,
+) {
+    if click.button != input.drag_button
+        || drag.click_ends_drag(&click.pointer_id)
+        || !keys.any_pressed(input.delete_anchor_keys.iter().copied())
+    {
+        return;
+    }
+    let Ok(ShapeType::Bezier(bezier_shape)) = query.get(click.target) else {
+        return;
+    };
+    let id = bezier_shape.id;
+    let Some(index) = splines.iter().find_map(|spline| {
+        let position = spline.segments.iter().position(|&segment| segment == id)?;
+        match bezier_shape.shape_type {
+            BezierShapeType::Start => Some(position),
+            BezierShapeType::End => Some(position + 1),
+            _ => None,
+        }
+    }) else {
+        return;
+    };
+    bezier_commands.push(BezierCommand::RemoveAnchor { id, index });
+}
+
+/// Removes anchor `index` of the spline containing curve `id`, counting
+/// from 0 at the spline's start, by merging the two segments meeting there
+/// into one with `merge_beziers`. Only anchors shared by two segments can
+/// be removed; the merged curve is the first segment, updated in place.
+pub fn remove_anchor(commands: &mut Commands, id: usize, index: usize) {
+    commands.queue(move |world: &mut World| {
+        let mut splines = world.query::<&BezierSpline>();
+        let Some(segments) = splines
+            .iter(world)
+            .find(|spline| spline.segments.contains(&id))
+            .map(|spline| spline.segments.clone())
+        else {
+            return;
+        };
+        if index == 0 || index >= segments.len() {
+            return;
+        }
+        let (first_id, second_id) = (segments[index - 1], segments[index]);
+        let mut query = world.query::<&ShapeType>();
+        let curves = collect_bezier_points(query.iter(world));
+        let (Some(&first), Some(&second)) = (curves.get(&first_id), curves.get(&second_id)) else {
+            return;
+        };
+        let merged = merge_beziers(&CubicBezier::from(first), &CubicBezier::from(second));
+
+        // the first segment takes the merged points in place, keeping its
+        // root, label, lock and user data, and the second's far weld
+        unweld_anchor(world, second_id, &BezierShapeType::Start);
+        hand_over_weld(world, second_id, first_id, BezierShapeType::End);
+        move_bezier(world, first_id, merged.points());
+        despawn_bezier(&mut world.commands(), second_id);
+        world.flush();
+
+        let mut splines = world.query::<&mut BezierSpline>();
+        if let Some(mut spline) = splines
+            .iter_mut(world)
+            .find(|spline| spline.segments.contains(&id))
+        {
+            spline.segments.remove(index);
+            if index - 1 < spline.handle_modes.len() {
+                spline.handle_modes.remove(index - 1);
+            }
+        }
+    });
+}
+
+/// Keeps the segments joined to the one being dragged attached: shared
+/// anchors follow, and the handle across a joint follows its `HandleMode`.
+/// Dragging `Handles` stands for setting every point at once, so both joints
+/// keep their mode. Returns the neighbours' edits.
+pub(crate) fn follow_spline_neighbors(
+    style: &BezierStyle,
+    query: &mut Query<(Entity, &mut Shape, &mut ShapeType)>,
+    splines: &Query<&BezierSpline>,
+    drag: &BezierDragState,
+    modified: &mut EventWriter<BezierModified>,
+) -> Vec<BezierEdit> {
+    let Some((spline, index)) = splines.iter().find_map(|spline| {
+        let index = spline
+            .segments
+            .iter()
+            .position(|&id| id == drag.bezier_id)?;
+        Some((spline, index))
+    }) else {
+        return Vec::new();
+    };
+    let Some([a, b, c, d]) = drag.points() else {
+        return Vec::new();
+    };
+    let curves = collect_bezier_points(query.iter().map(|(_, _, s)| s));
+
+    let mut updates = Vec::new();
+    if let Some(previous_index) = spline.previous_index(index) {
+        let previous = spline.segments[previous_index];
+        if let Some(mut points) = curves.get(&previous).copied() {
+            points[3] = a;
+            if matches!(
+                drag.dragging,
+                BezierShapeType::ControlStart | BezierShapeType::Handles
+            ) {
+                points[2] = spline.handle_mode(previous_index).opposite(b, a, points[2]);
+            }
+            updates.push((previous, points, curves[&previous]));
+        }
+    }
+    if let Some(next) = spline.next_index(index).map(|i| spline.segments[i]) {
+        if let Some(mut points) = curves.get(&next).copied() {
+            points[0] = d;
+            if matches!(
+                drag.dragging,
+                BezierShapeType::ControlEnd | BezierShapeType::Handles
+            ) {
+                points[1] = spline.handle_mode(index).opposite(c, d, points[1]);
+            }
+            updates.push((next, points, curves[&next]));
+        }
+    }
+
+    let mut edits = Vec::new();
+    for (neighbor, points, before) in updates {
+        if points == before {
+            continue;
+        }
+        update_bezier_in_place(style, query, neighbor, points);
+        modified.write(BezierModified {
+            id: neighbor,
+            points,
+        });
+        edits.push(BezierEdit {
+            id: neighbor,
+            before,
+            after: points,
+        });
+    }
+    edits
+}
@@ -0,0 +1,75 @@
+use super::*;
+use std::borrow::Cow;
+
+/// Per-curve changes to `BezierStyle`, e.g. to highlight the active curve or
+/// color curves by category. Put it on a curve's `BezierId` root, or insert
+/// it into `BezierStyle::overrides` directly. Unset fields keep the global
+/// style.
+#[derive(Component, Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct BezierStyleOverride {
+    pub intersection_color: Option<Color>,
+    pub intersection_radius: Option<f32>,
+    pub sketch_color: Option<Color>,
+    pub bezier_stroke_width: Option<f32>,
+    pub sketch_stroke_width: Option<f32>,
+    pub bezier_line_color: Option<Color>,
+    pub fill_color: Option<Color>,
+}
+
+impl BezierStyleOverride {
+    /// `style` with the set fields replaced.
+    pub fn apply(&self, style: &BezierStyle) -> BezierStyle {
+        let mut style = style.clone();
+        if let Some(color) = self.intersection_color {
+            style.intersection_color = color;
+        }
+        if let Some(radius) = self.intersection_radius {
+            style.intersection_radius = radius;
+        }
+        if let Some(color) = self.sketch_color {
+            style.sketch_color = color;
+        }
+        if let Some(width) = self.bezier_stroke_width {
+            style.bezier_stroke_width = width;
+        }
+        if let Some(width) = self.sketch_stroke_width {
+            style.sketch_stroke_width = width;
+        }
+        if let Some(color) = self.bezier_line_color {
+            style.bezier_line_color = color;
+        }
+        if let Some(color) = self.fill_color {
+            style.fill_color = color;
+        }
+        style
+    }
+}
+
+impl BezierStyle {
+    /// The style curve `id` is drawn with, after its override if it has one.
+    pub fn for_curve(&self, id: usize) -> Cow<'_, BezierStyle> {
+        match self.overrides.get(&id) {
+            Some(style_override) => Cow::Owned(style_override.apply(self)),
+            None => Cow::Borrowed(self),
+        }
+    }
+}
+
+/// Copies `BezierStyleOverride` components from curve roots into
+/// `BezierStyle::overrides`; the style change redraws the curves.
+pub fn sync_bezier_style_overrides(
+    changed: Query<(&BezierId, &BezierStyleOverride), Changed<BezierStyleOverride>>,
+    mut removed: RemovedComponents<BezierStyleOverride>,
+    roots: Query<&BezierId>,
+    mut style: ResMut<BezierStyle>,
+) {
+    for (id, style_override) in changed.iter() {
+        style.overrides.insert(id.0, style_override.clone());
+    }
+    for entity in removed.read() {
+        if let Ok(id) = roots.get(entity) {
+            style.overrides.remove(&id.0);
+        }
+    }
+}
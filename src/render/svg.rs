@@ -0,0 +1,240 @@
+use super::*;
+use std::fmt::Write;
+
+/// `d` attribute for a single cubic: `M a C b c d`.
+pub fn to_svg_path(curve: &CubicBezier) -> String {
+    spline_to_svg_path(std::slice::from_ref(curve), false)
+}
+
+/// `d` attribute for connected segments, each starting where the previous
+/// one ended, optionally closed with `Z`.
+pub fn spline_to_svg_path(segments: &[CubicBezier], closed: bool) -> String {
+    let Some(first) = segments.first() else {
+        return String::new();
+    };
+    let mut path = format!("M {} {}", first.a.x, first.a.y);
+    for segment in segments {
+        let _ = write!(
+            path,
+            " C {} {} {} {} {} {}",
+            segment.b.x, segment.b.y, segment.c.x, segment.c.y, segment.d.x, segment.d.y
+        );
+    }
+    if closed {
+        path.push_str(" Z");
+    }
+    path
+}
+
+/// One `M ...` run of an SVG path, converted to cubics.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SvgSubpath {
+    pub segments: Vec<CubicBezier>,
+    pub closed: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SvgPathError {
+    UnsupportedCommand(char),
+    ExpectedNumber(usize),
+    MissingMoveTo,
+}
+
+impl std::fmt::Display for SvgPathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SvgPathError::UnsupportedCommand(command) => {
+                write!(f, "unsupported path command '{}'", command)
+            }
+            SvgPathError::ExpectedNumber(offset) => write!(f, "expected a number at {}", offset),
+            SvgPathError::MissingMoveTo => write!(f, "path data must start with a move-to"),
+        }
+    }
+}
+
+impl std::error::Error for SvgPathError {}
+
+enum Token {
+    Command(char),
+    Number(f32),
+}
+
+fn tokenize(data: &str) -> Result<Vec<(usize, Token)>, SvgPathError> {
+    let mut tokens = Vec::new();
+    let bytes = data.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        let ch = bytes[i] as char;
+        if ch.is_ascii_whitespace() || ch == ',' {
+            i += 1;
+        } else if ch.is_ascii_alphabetic() && ch != 'e' && ch != 'E' {
+            tokens.push((i, Token::Command(ch)));
+            i += 1;
+        } else {
+            let start = i;
+            let mut seen_dot = false;
+            let mut seen_exponent = false;
+            if matches!(ch, '-' | '+') {
+                i += 1;
+            }
+            while i < bytes.len() {
+                match bytes[i] as char {
+                    '0'..='9' => i += 1,
+                    '.' if !seen_dot && !seen_exponent => {
+                        seen_dot = true;
+                        i += 1;
+                    }
+                    'e' | 'E' if !seen_exponent => {
+                        seen_exponent = true;
+                        i += 1;
+                        if i < bytes.len() && matches!(bytes[i] as char, '-' | '+') {
+                            i += 1;
+                        }
+                    }
+                    _ => break,
+                }
+            }
+            let number = data[start..i]
+                .parse::<f32>()
+                .map_err(|_| SvgPathError::ExpectedNumber(start))?;
+            tokens.push((start, Token::Number(number)));
+        }
+    }
+    Ok(tokens)
+}
+
+/// Parses SVG path data (`M`, `L`, `C`, `S`, `Q`, `A` and `Z`, absolute or
+/// relative) into cubic subpaths. Lines become straight cubics with their
+/// controls at a third and two thirds of the way along; quadratics are
+/// elevated and elliptical arcs split into cubics, see `svg_arc_to_cubics`.
+pub fn parse_svg_path(data: &str) -> Result<Vec<SvgSubpath>, SvgPathError> {
+    let tokens = tokenize(data)?;
+    let mut subpaths: Vec<SvgSubpath> = Vec::new();
+    let mut current = Vec2::ZERO;
+    let mut start = Vec2::ZERO;
+    let mut last_control: Option<Vec2> = None;
+    let mut command = None;
+    let mut i = 0;
+
+    let number = |i: &mut usize| -> Result<f32, SvgPathError> {
+        match tokens.get(*i) {
+            Some((_, Token::Number(value))) => {
+                *i += 1;
+                Ok(*value)
+            }
+            Some((offset, _)) => Err(SvgPathError::ExpectedNumber(*offset)),
+            None => Err(SvgPathError::ExpectedNumber(data.len())),
+        }
+    };
+
+    while i < tokens.len() {
+        if let (_, Token::Command(ch)) = &tokens[i] {
+            command = Some(*ch);
+            i += 1;
+        }
+        let Some(ch) = command else {
+            return Err(SvgPathError::MissingMoveTo);
+        };
+        let relative = ch.is_ascii_lowercase();
+        let origin = if relative { current } else { Vec2::ZERO };
+        let point = |i: &mut usize| -> Result<Vec2, SvgPathError> {
+            Ok(origin + Vec2::new(number(i)?, number(i)?))
+        };
+        match ch.to_ascii_uppercase() {
+            'M' => {
+                current = point(&mut i)?;
+                start = current;
+                subpaths.push(SvgSubpath::default());
+                last_control = None;
+                // further coordinate pairs after a move-to are line-tos
+                command = Some(if relative { 'l' } else { 'L' });
+            }
+            'L' => {
+                let end = point(&mut i)?;
+                let subpath = subpaths.last_mut().ok_or(SvgPathError::MissingMoveTo)?;
+                subpath.segments.push(line_cubic(current, end));
+                current = end;
+                last_control = None;
+            }
+            'C' => {
+                let (b, c, d) = (point(&mut i)?, point(&mut i)?, point(&mut i)?);
+                let subpath = subpaths.last_mut().ok_or(SvgPathError::MissingMoveTo)?;
+                subpath.segments.push(CubicBezier::new(current, b, c, d));
+                current = d;
+                last_control = Some(c);
+            }
+            'Q' => {
+                let (q, d) = (point(&mut i)?, point(&mut i)?);
+                let subpath = subpaths.last_mut().ok_or(SvgPathError::MissingMoveTo)?;
+                subpath
+                    .segments
+                    .push(QuadraticBezier::new(current, q, d).elevate());
+                current = d;
+                last_control = None;
+            }
+            'S' => {
+                let b = last_control.map_or(current, |control| 2.0 * current - control);
+                let (c, d) = (point(&mut i)?, point(&mut i)?);
+                let subpath = subpaths.last_mut().ok_or(SvgPathError::MissingMoveTo)?;
+                subpath.segments.push(CubicBezier::new(current, b, c, d));
+                current = d;
+                last_control = Some(c);
+            }
+            'A' => {
+                let radii = Vec2::new(number(&mut i)?, number(&mut i)?);
+                let rotation = number(&mut i)?.to_radians();
+                let (large_arc, sweep) = (number(&mut i)? != 0.0, number(&mut i)? != 0.0);
+                let end = point(&mut i)?;
+                let subpath = subpaths.last_mut().ok_or(SvgPathError::MissingMoveTo)?;
+                subpath.segments.extend(svg_arc_to_cubics(
+                    current, radii, rotation, large_arc, sweep, end,
+                ));
+                current = end;
+                last_control = None;
+            }
+            'Z' => {
+                let subpath = subpaths.last_mut().ok_or(SvgPathError::MissingMoveTo)?;
+                if current != start {
+                    subpath.segments.push(line_cubic(current, start));
+                }
+                subpath.closed = true;
+                current = start;
+                last_control = None;
+                command = None;
+            }
+            other => return Err(SvgPathError::UnsupportedCommand(other)),
+        }
+    }
+    subpaths.retain(|subpath| !subpath.segments.is_empty());
+    Ok(subpaths)
+}
+
+fn line_cubic(start: Vec2, end: Vec2) -> CubicBezier {
+    CubicBezier::new(
+        start,
+        start.lerp(end, 1.0 / 3.0),
+        start.lerp(end, 2.0 / 3.0),
+        end,
+    )
+}
+
+/// Parses SVG path data and spawns every subpath as a spline, returning
+/// the spline entities.
+pub fn spawn_svg_path(
+    commands: &mut Commands,
+    style: &BezierStyle,
+    ids: &mut BezierIdAllocator,
+    data: &str,
+) -> Result<Vec<Entity>, SvgPathError> {
+    let subpaths = parse_svg_path(data)?;
+    Ok(subpaths
+        .iter()
+        .filter_map(|subpath| {
+            let mut points = vec![subpath.segments[0].a];
+            for segment in &subpath.segments {
+                points.extend([segment.b, segment.c, segment.d]);
+            }
+            spawn_bezier_spline(commands, style, ids, &points)
+        })
+        .collect())
+}
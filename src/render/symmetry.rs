@@ -0,0 +1,99 @@
+use super::*;
+
+/// An axis-aligned line to mirror curves across.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub enum BezierMirrorAxis {
+    /// The vertical line `x = value`, swapping left and right.
+    Vertical(f32),
+    /// The horizontal line `y = value`, swapping top and bottom.
+    Horizontal(f32),
+}
+
+impl BezierMirrorAxis {
+    /// `point` reflected across the axis.
+    pub fn reflect(self, point: Vec2) -> Vec2 {
+        match self {
+            BezierMirrorAxis::Vertical(x) => Vec2::new(2.0 * x - point.x, point.y),
+            BezierMirrorAxis::Horizontal(y) => Vec2::new(point.x, 2.0 * y - point.y),
+        }
+    }
+    /// `points` of a curve reflected across the axis.
+    pub fn mirror(self, points: [Vec2; 4]) -> [Vec2; 4] {
+        points.map(|point| self.reflect(point))
+    }
+    /// A point on the axis and its direction, as `mirror_bezier` takes them.
+    pub fn point_and_direction(self) -> (Vec2, Vec2) {
+        match self {
+            BezierMirrorAxis::Vertical(x) => (Vec2::new(x, 0.0), Vec2::Y),
+            BezierMirrorAxis::Horizontal(y) => (Vec2::new(0.0, y), Vec2::X),
+        }
+    }
+}
+
+/// Put on a curve's `BezierId` root to keep curve `twin` its mirror image
+/// across `axis`, updated live as the curve is dragged or edited. The twin
+/// follows the original only: edits made to the twin itself are undone on
+/// the next frame.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct BezierSymmetry {
+    pub twin: usize,
+    pub axis: BezierMirrorAxis,
+}
+
+/// Spawns a mirror image of curve `id` across `axis` as its live twin,
+/// returning the twin's id, or `None` when the curve isn't found.
+pub fn spawn_symmetric_bezier(
+    commands: &mut Commands,
+    style: &BezierStyle,
+    ids: &mut BezierIdAllocator,
+    query: &Query<(Entity, &ShapeType)>,
+    id: usize,
+    axis: BezierMirrorAxis,
+) -> Option<usize> {
+    let (axis_point, axis_dir) = axis.point_and_direction();
+    let twin = spawn_mirrored_bezier(commands, style, ids, query, id, axis_point, axis_dir)?;
+    let symmetry = BezierSymmetry { twin, axis };
+    commands.queue(move |world: &mut World| {
+        let mut roots = world.query::<(Entity, &BezierId)>();
+        if let Some(root) = roots
+            .iter(world)
+            .find_map(|(entity, root_id)| (root_id.0 == id).then_some(entity))
+        {
+            world.entity_mut(root).insert(symmetry);
+        }
+    });
+    Some(twin)
+}
+
+/// Moves the twins of `BezierSymmetry` curves onto the mirror images of
+/// their originals.
+pub fn mirror_symmetric_beziers(
+    symmetries: Query<(&BezierId, &BezierSymmetry)>,
+    mut query: Query<(Entity, &mut Shape, &mut ShapeType)>,
+    mut document: Option<ResMut<BezierDocument>>,
+    mut modified: EventWriter<BezierModified>,
+    style: Res<BezierStyle>,
+) {
+    if symmetries.is_empty() {
+        return;
+    }
+    let curves = collect_bezier_points(query.iter().map(|(_, _, shape_type)| shape_type));
+    for (id, symmetry) in symmetries.iter() {
+        let (Some(&points), Some(&twin)) = (curves.get(&id.0), curves.get(&symmetry.twin)) else {
+            continue;
+        };
+        let mirrored = symmetry.axis.mirror(points);
+        if twin == mirrored {
+            continue;
+        }
+        update_bezier_in_place(&style, &mut query, symmetry.twin, mirrored);
+        modified.write(BezierModified {
+            id: symmetry.twin,
+            points: mirrored,
+        });
+        if let Some(document) = document.as_mut() {
+            document.write_back(symmetry.twin, mirrored);
+        }
+    }
+}
@@ -0,0 +1,305 @@
+//! A headless `App` running `BezierPlugin`, and pointer drags synthesized
+//! the way `bevy_picking` would send them, for integration tests of the
+//! editing logic. Everything here is deterministic: no window, no GPU and
+//! no real input, only `App::update` between the steps a test asks for.
+use super::*;
+use bevy::asset::RenderAssetUsages;
+use bevy::log::LogPlugin;
+use bevy::picking::backend::HitData;
+use bevy::picking::pointer::Location;
+use bevy::render::RenderPlugin;
+use bevy::render::camera::RenderTarget;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::render::settings::{RenderCreation, WgpuSettings};
+use bevy::window::ExitCondition;
+use bevy::winit::WinitPlugin;
+use std::time::Duration;
+
+/// Size in pixels of the image the test camera renders to. With the
+/// default projection one pixel is one world unit, the world origin sits
+/// in the middle and screen y points down, world y up.
+pub const TEST_VIEWPORT: UVec2 = UVec2::new(800, 600);
+
+/// How close `assert_points_near` wants points to be by default.
+pub const TEST_TOLERANCE: f32 = 1e-3;
+
+/// An `App` with `BezierPlugin` and a 2D camera, stepped by hand.
+pub struct BezierTestApp {
+    pub app: App,
+    pub camera: Entity,
+    pointer_id: PointerId,
+    /// Screen position of the drag in progress, and how far it has gone.
+    drag: Option<(Entity, Vec2, Vec2)>,
+}
+
+impl Default for BezierTestApp {
+    fn default() -> Self {
+        Self::new(BezierPlugin::default())
+    }
+}
+
+impl BezierTestApp {
+    /// A headless app running `plugin`, with the camera set up and one
+    /// update already run.
+    pub fn new(plugin: BezierPlugin) -> Self {
+        let mut app = App::new();
+        app.add_plugins(
+            DefaultPlugins
+                .set(RenderPlugin {
+                    render_creation: RenderCreation::Automatic(WgpuSettings {
+                        backends: None,
+                        ..default()
+                    }),
+                    ..default()
+                })
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    ..default()
+                })
+                .disable::<WinitPlugin>()
+                .disable::<LogPlugin>(),
+        )
+        .add_plugins((ShapePlugin, plugin));
+        let size = Extent3d {
+            width: TEST_VIEWPORT.x,
+            height: TEST_VIEWPORT.y,
+            depth_or_array_layers: 1,
+        };
+        let image = app
+            .world_mut()
+            .resource_mut::<Assets<Image>>()
+            .add(Image::new_fill(
+                size,
+                TextureDimension::D2,
+                &[0; 4],
+                TextureFormat::Rgba8UnormSrgb,
+                RenderAssetUsages::default(),
+            ));
+        let camera = app
+            .world_mut()
+            .spawn((
+                Camera2d,
+                Camera {
+                    target: RenderTarget::Image(image.into()),
+                    ..default()
+                },
+            ))
+            .id();
+        app.update();
+        Self {
+            app,
+            camera,
+            pointer_id: PointerId::Mouse,
+            drag: None,
+        }
+    }
+    pub fn world(&self) -> &World {
+        self.app.world()
+    }
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+    pub fn update(&mut self) {
+        self.app.update();
+    }
+    /// Spawns an open curve through `points` and returns its id.
+    pub fn spawn_curve(&mut self, points: [Vec2; 4]) -> usize {
+        let world = self.app.world_mut();
+        let style = world.resource::<BezierStyle>().clone();
+        let id = world.resource_mut::<BezierIdAllocator>().next_id();
+        let [a, b, c, d] = points;
+        spawn_bezier_with_id(
+            &mut world.commands(),
+            &style,
+            id,
+            bezier_open(&style, id, a, b, c, d),
+        );
+        world.flush();
+        self.app.update();
+        id
+    }
+    /// Spawns a spline through `points`, laid out as for
+    /// `spawn_bezier_spline`, and returns its segment ids.
+    pub fn spawn_spline(&mut self, points: &[Vec2]) -> Vec<usize> {
+        let world = self.app.world_mut();
+        let style = world.resource::<BezierStyle>().clone();
+        let spline = world.resource_scope(|world, mut ids: Mut<BezierIdAllocator>| {
+            let spline = spawn_bezier_spline(&mut world.commands(), &style, &mut ids, points);
+            world.flush();
+            spline
+        });
+        let segments = spline
+            .and_then(|spline| world.get::<BezierSpline>(spline))
+            .map(|spline| spline.segments.clone())
+            .expect("points should describe whole segments");
+        self.app.update();
+        segments
+    }
+    /// The entity drawing `part` of curve `id`.
+    pub fn part(&mut self, id: usize, part: BezierShapeType) -> Entity {
+        let mut query = self.app.world_mut().query::<(Entity, &ShapeType)>();
+        query
+            .iter(self.app.world())
+            .find_map(|(entity, shape_type)| match shape_type {
+                ShapeType::Bezier(bezier_shape)
+                    if bezier_shape.id == id && bezier_shape.shape_type == part =>
+                {
+                    Some(entity)
+                }
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("curve {id} has no {part} part"))
+    }
+    /// Every entity drawing part of curve `id`.
+    pub fn parts(&mut self, id: usize) -> Vec<Entity> {
+        let mut query = self.app.world_mut().query::<(Entity, &ShapeType)>();
+        query
+            .iter(self.app.world())
+            .filter_map(|(entity, shape_type)| match shape_type {
+                ShapeType::Bezier(bezier_shape) if bezier_shape.id == id => Some(entity),
+                _ => None,
+            })
+            .collect()
+    }
+    /// Current points of curve `id`, read back from its shapes.
+    pub fn points(&mut self, id: usize) -> [Vec2; 4] {
+        let mut query = self.app.world_mut().query::<&ShapeType>();
+        collect_bezier_points(query.iter(self.app.world()))
+            .remove(&id)
+            .unwrap_or_else(|| panic!("no curve {id}"))
+    }
+    /// Where the world point `position` shows up on the test camera.
+    pub fn world_to_screen(&mut self, position: Vec2) -> Vec2 {
+        let mut cameras = self.app.world_mut().query::<(&Camera, &GlobalTransform)>();
+        let (camera, transform) = cameras
+            .get(self.app.world(), self.camera)
+            .expect("the test camera is never despawned");
+        camera
+            .world_to_viewport(transform, position.extend(0.0))
+            .expect("the test camera has a viewport")
+    }
+    fn location(&self, position: Vec2) -> Location {
+        let camera = self
+            .app
+            .world()
+            .get::<Camera>(self.camera)
+            .expect("the test camera is never despawned");
+        Location {
+            target: camera
+                .target
+                .normalize(None)
+                .expect("an image target needs no window"),
+            position,
+        }
+    }
+    fn trigger<E: std::fmt::Debug + Clone + Reflect>(
+        &mut self,
+        target: Entity,
+        position: Vec2,
+        event: E,
+    ) {
+        let pointer = Pointer {
+            target,
+            pointer_id: self.pointer_id,
+            pointer_location: self.location(position),
+            event,
+        };
+        let world = self.app.world_mut();
+        world.trigger_targets(pointer, target);
+        world.flush();
+    }
+    /// Where `target`, a part of curve `id`, is pointed at: the point it
+    /// draws or, for the curve body, halfway along the curve. Parts drawing
+    /// a point can be found even if others of the curve are missing.
+    fn grab_point(&mut self, id: usize, target: Entity) -> Vec2 {
+        match self.app.world().get::<ShapeType>(target) {
+            Some(ShapeType::Bezier(BezierShape {
+                point: Some(point), ..
+            })) => *point,
+            _ => {
+                let [a, b, c, d] = self.points(id);
+                CubicBezier::new(a, b, c, d).point_at(0.5)
+            }
+        }
+    }
+    /// Presses the drag button on `part` of curve `id`, see `grab_point`.
+    pub fn drag_start(&mut self, id: usize, part: BezierShapeType) {
+        let target = self.part(id, part);
+        let world_position = self.grab_point(id, target);
+        let screen = self.world_to_screen(world_position);
+        let button = self.app.world().resource::<BezierInputMap>().drag_button;
+        let hit = HitData::new(self.camera, 0.0, Some(world_position.extend(0.0)), None);
+        self.trigger(target, screen, DragStart { button, hit });
+        self.drag = Some((target, screen, Vec2::ZERO));
+        self.app.update();
+    }
+    /// Moves the pointer of the drag in progress by `delta` screen pixels,
+    /// y pointing down as the pointer reports it.
+    pub fn drag_by(&mut self, delta: Vec2) {
+        let (target, screen, distance) = self.drag.expect("drag_by needs drag_start first");
+        let (screen, distance) = (screen + delta, distance + delta);
+        let button = self.app.world().resource::<BezierInputMap>().drag_button;
+        self.trigger(
+            target,
+            screen,
+            Drag {
+                button,
+                distance,
+                delta,
+            },
+        );
+        self.drag = Some((target, screen, distance));
+        self.app.update();
+    }
+    /// Releases the drag in progress.
+    pub fn drag_end(&mut self) {
+        let (target, screen, distance) = self.drag.take().expect("drag_end needs drag_start first");
+        let button = self.app.world().resource::<BezierInputMap>().drag_button;
+        self.trigger(target, screen, DragEnd { button, distance });
+        self.app.update();
+    }
+    /// Clicks the drag button on `part` of curve `id`. During a drag this
+    /// is the click Bevy sends on release, before `drag_end`.
+    pub fn click(&mut self, id: usize, part: BezierShapeType) {
+        let target = self.part(id, part);
+        let world_position = self.grab_point(id, target);
+        let screen = self.world_to_screen(world_position);
+        let button = self.app.world().resource::<BezierInputMap>().drag_button;
+        let hit = HitData::new(self.camera, 0.0, Some(world_position.extend(0.0)), None);
+        self.trigger(
+            target,
+            screen,
+            Click {
+                button,
+                hit,
+                duration: Duration::ZERO,
+            },
+        );
+        self.app.update();
+    }
+    /// Drags `part` of curve `id` by `delta` screen pixels in one move.
+    pub fn drag(&mut self, id: usize, part: BezierShapeType, delta: Vec2) {
+        self.drag_start(id, part);
+        self.drag_by(delta);
+        self.drag_end();
+    }
+    /// Panics unless curve `id` is at `expected`, within `TEST_TOLERANCE`.
+    pub fn assert_points(&mut self, id: usize, expected: [Vec2; 4]) {
+        assert_points_near(self.points(id), expected, TEST_TOLERANCE);
+    }
+}
+
+/// Panics, listing both sets of points, unless each of `actual` is within
+/// `tolerance` of the same point of `expected`.
+#[track_caller]
+pub fn assert_points_near(actual: [Vec2; 4], expected: [Vec2; 4], tolerance: f32) {
+    let near = actual
+        .iter()
+        .zip(expected)
+        .all(|(actual, expected)| actual.distance(expected) <= tolerance);
+    assert!(
+        near,
+        "points differ by more than {tolerance}:\n  actual:   {actual:?}\n  expected: {expected:?}"
+    );
+}
@@ -0,0 +1,114 @@
+use super::*;
+use bevy::picking::backend::{HitData, PointerHits};
+
+/// Whether `pointer_id` picks with `BezierStyle::touch_hit_radius`: touches,
+/// which is how Bevy reports styluses too, and custom pointers such as a
+/// pen driven by a tablet plugin. The mouse keeps the drawn strokes.
+pub fn is_touch_or_pen(pointer_id: &PointerId) -> bool {
+    !pointer_id.is_mouse()
+}
+
+/// Whether pointer `pointer_id` is one of several fingers on the screen, in
+/// which case the gesture (e.g. a two-finger pan) is left to the app rather
+/// than dragging curves.
+pub fn is_multi_touch(pointer_id: &PointerId, touches: &Touches) -> bool {
+    pointer_id.is_touch() && touches.iter().count() > 1
+}
+
+/// World units one logical pixel spans under the active camera.
+pub fn world_units_per_pixel(cameras: &Query<(&Camera, &GlobalTransform)>) -> Option<f32> {
+    let (camera, camera_transform) = cameras.iter().find(|(camera, _)| camera.is_active)?;
+    let origin = camera
+        .viewport_to_world_2d(camera_transform, Vec2::ZERO)
+        .ok()?;
+    let step = camera
+        .viewport_to_world_2d(camera_transform, Vec2::X)
+        .ok()?;
+    Some(origin.distance(step))
+}
+
+/// `BezierStyle::touch_hit_radius` in world units for `pointer_id` at
+/// `position` on `camera`, or zero for pointers that aren't
+/// `is_touch_or_pen`. Logical pixels make the radius the same physical size
+/// at any DPI.
+pub fn pointer_hit_radius(
+    style: &BezierStyle,
+    pointer_id: &PointerId,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    position: Vec2,
+) -> f32 {
+    if !is_touch_or_pen(pointer_id) {
+        return 0.0;
+    }
+    let (Ok(at), Ok(step)) = (
+        camera.viewport_to_world_2d(camera_transform, position),
+        camera.viewport_to_world_2d(camera_transform, position + Vec2::X),
+    ) else {
+        return 0.0;
+    };
+    style.touch_hit_radius * at.distance(step)
+}
+
+/// Picking backend hitting the curves and points within
+/// `pointer_hit_radius` of each touch or pen pointer, so fingers grab
+/// thin strokes without widening them for the mouse. Hits are ordered by
+/// distance, nearest first; parts `bezier_pick_prefilter` made
+/// non-pickable and curves in `BezierSpace::Ui` are left to the mesh
+/// backend.
+pub fn bezier_touch_picking(
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform)>,
+    shapes: Query<(Entity, &ShapeType, &Pickable, Option<&BezierSpace>)>,
+    style: Res<BezierStyle>,
+    mut hits: EventWriter<PointerHits>,
+) {
+    let Some((camera_entity, camera, camera_transform)) =
+        cameras.iter().find(|(_, camera, _)| camera.is_active)
+    else {
+        return;
+    };
+    let mut curves = None;
+    for (pointer_id, location) in pointers.iter() {
+        if !is_touch_or_pen(pointer_id) {
+            continue;
+        }
+        let Some(screen) = location.location().map(|location| location.position) else {
+            continue;
+        };
+        let Ok(position) = camera.viewport_to_world_2d(camera_transform, screen) else {
+            continue;
+        };
+        let radius = pointer_hit_radius(&style, pointer_id, camera, camera_transform, screen);
+        let curves = curves.get_or_insert_with(|| {
+            collect_bezier_points(shapes.iter().map(|(_, shape_type, _, _)| shape_type))
+        });
+        let picks: Vec<(Entity, HitData)> = shapes
+            .iter()
+            .filter_map(|(entity, shape_type, pickable, space)| {
+                let ShapeType::Bezier(bezier_shape) = shape_type else {
+                    return None;
+                };
+                if !pickable.is_hoverable || matches!(space, Some(BezierSpace::Ui)) {
+                    return None;
+                }
+                let (hit, distance) = match (&bezier_shape.shape_type, bezier_shape.point) {
+                    (BezierShapeType::BezierLine, _) => {
+                        let curve = CubicBezier::from(*curves.get(&bezier_shape.id)?);
+                        let (_, point, distance) = curve.closest_point(position);
+                        (point, distance)
+                    }
+                    (_, Some(point)) => (point, point.distance(position)),
+                    _ => return None,
+                };
+                (distance <= radius).then(|| {
+                    let hit = HitData::new(camera_entity, distance, Some(hit.extend(0.0)), None);
+                    (entity, hit)
+                })
+            })
+            .collect();
+        if !picks.is_empty() {
+            hits.write(PointerHits::new(*pointer_id, picks, camera.order as f32));
+        }
+    }
+}
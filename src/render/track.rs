@@ -0,0 +1,100 @@
+use super::*;
+
+/// Put on a curve's `BezierId` root to animate its points through
+/// `keyframes`, each a time in seconds and the points at that time, sorted
+/// by time. Points are blended linearly between neighbouring keyframes and
+/// held before the first and after the last. Set `time` to scrub; the curve
+/// is redrawn whenever the track changes, playing or not.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct BezierTrack {
+    pub keyframes: Vec<(f32, [Vec2; 4])>,
+    /// Current position on the timeline, in seconds.
+    pub time: f32,
+    pub playing: bool,
+    /// Playback rate; negative plays backwards.
+    pub speed: f32,
+    /// Wrap around to the start after the last keyframe.
+    pub looping: bool,
+}
+
+impl BezierTrack {
+    pub fn new(keyframes: Vec<(f32, [Vec2; 4])>) -> Self {
+        let mut track = Self {
+            keyframes,
+            time: 0.0,
+            playing: true,
+            speed: 1.0,
+            looping: false,
+        };
+        track.sort();
+        track
+    }
+    pub fn looping(mut self) -> Self {
+        self.looping = true;
+        self
+    }
+    /// Adds a keyframe, replacing one already at `time`.
+    pub fn insert(&mut self, time: f32, points: [Vec2; 4]) {
+        self.keyframes.retain(|(at, _)| *at != time);
+        self.keyframes.push((time, points));
+        self.sort();
+    }
+    fn sort(&mut self) {
+        self.keyframes.sort_by(|(x, _), (y, _)| x.total_cmp(y));
+    }
+    /// Time of the last keyframe.
+    pub fn duration(&self) -> f32 {
+        self.keyframes.last().map_or(0.0, |(time, _)| *time)
+    }
+    /// Jumps to `time`, clamped to the track.
+    pub fn scrub(&mut self, time: f32) {
+        self.time = time.clamp(0.0, self.duration());
+    }
+    /// The points at `time`.
+    pub fn points_at(&self, time: f32) -> Option<[Vec2; 4]> {
+        let next = self.keyframes.iter().position(|(at, _)| *at > time);
+        match next {
+            None => self.keyframes.last().map(|(_, points)| *points),
+            Some(0) => self.keyframes.first().map(|(_, points)| *points),
+            Some(next) => {
+                let (from_time, from) = self.keyframes[next - 1];
+                let (to_time, to) = self.keyframes[next];
+                let t = (time - from_time) / (to_time - from_time);
+                Some(lerp_curves(&from.into(), &to.into(), t).points())
+            }
+        }
+    }
+}
+
+/// Advances playing `BezierTrack`s and redraws the curves of tracks that
+/// moved or were scrubbed.
+pub fn play_bezier_tracks(
+    time: Res<Time>,
+    mut tracks: Query<(&mut BezierTrack, &BezierId)>,
+    mut query: Query<(Entity, &mut Shape, &mut ShapeType)>,
+    mut modified: EventWriter<BezierModified>,
+    style: Res<BezierStyle>,
+) {
+    for (mut track, id) in tracks.iter_mut() {
+        if track.playing && track.speed != 0.0 {
+            let duration = track.duration();
+            let mut now = track.time + time.delta_secs() * track.speed;
+            if track.looping && duration > 0.0 {
+                now = now.rem_euclid(duration);
+            } else if !(0.0..=duration).contains(&now) {
+                now = now.clamp(0.0, duration);
+                track.playing = false;
+            }
+            track.time = now;
+        }
+        if !track.is_changed() {
+            continue;
+        }
+        let Some(points) = track.points_at(track.time) else {
+            continue;
+        };
+        update_bezier_in_place(&style, &mut query, id.0, points);
+        modified.write(BezierModified { id: id.0, points });
+    }
+}
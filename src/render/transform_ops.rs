@@ -0,0 +1,194 @@
+use super::*;
+use bevy::window::PrimaryWindow;
+
+/// Moves every point of curve `id` by `delta`.
+pub fn translate_bezier(commands: &mut Commands, id: usize, delta: Vec2) {
+    commands.send_event(TransformBezier {
+        id,
+        transform: Transform::from_translation(delta.extend(0.0)),
+    });
+}
+
+/// Rotates curve `id` by `angle` radians, counter-clockwise, around
+/// `origin`.
+pub fn rotate_bezier(commands: &mut Commands, id: usize, origin: Vec2, angle: f32) {
+    commands.send_event(TransformBezier {
+        id,
+        transform: around(
+            origin,
+            Transform::from_rotation(Quat::from_rotation_z(angle)),
+        ),
+    });
+}
+
+/// Scales curve `id` by `factor` towards or away from `origin`.
+pub fn scale_bezier(commands: &mut Commands, id: usize, origin: Vec2, factor: f32) {
+    commands.send_event(TransformBezier {
+        id,
+        transform: around(origin, Transform::from_scale(Vec3::splat(factor))),
+    });
+}
+
+/// `transform` applied about `origin` instead of the world origin.
+fn around(origin: Vec2, transform: Transform) -> Transform {
+    let origin = origin.extend(0.0);
+    Transform::from_translation(origin) * transform * Transform::from_translation(-origin)
+}
+
+/// Handles drawn around the bounding box of the selected curves: dragging
+/// a corner scales them about the box's center, dragging the handle above
+/// the box rotates them around it.
+#[derive(Resource, Clone, Debug)]
+pub struct BezierTransformGizmo {
+    pub enabled: bool,
+    /// Radius of the handles, in world units, for drawing and grabbing.
+    pub handle_radius: f32,
+    /// How far above the box the rotate handle sits.
+    pub rotate_offset: f32,
+    pub color: Color,
+    drag: Option<TransformGizmoDrag>,
+}
+
+impl Default for BezierTransformGizmo {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            handle_radius: 6.0,
+            rotate_offset: 24.0,
+            color: Color::srgb(0.3, 0.6, 1.0),
+            drag: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct TransformGizmoDrag {
+    rotate: bool,
+    center: Vec2,
+    last: Vec2,
+    ids: Vec<usize>,
+}
+
+impl BezierTransformGizmo {
+    /// Whether a handle is being dragged.
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+}
+
+/// Bounding box of the curves `ids` in `curves`.
+fn selection_bounds(curves: &BTreeMap<usize, [Vec2; 4]>, ids: &[usize]) -> Option<Rect> {
+    ids.iter()
+        .filter_map(|id| curves.get(id))
+        .map(|&points| CubicBezier::from(points).bounding_box())
+        .reduce(|bounds, other| bounds.union(other))
+}
+
+/// Draws `BezierTransformGizmo` around the selected curves.
+pub fn draw_bezier_transform_gizmo(
+    mut gizmos: Gizmos,
+    settings: Res<BezierTransformGizmo>,
+    selection: Res<BezierSelection>,
+    query: Query<&ShapeType>,
+) {
+    if !settings.enabled || selection.is_empty() {
+        return;
+    }
+    let curves = collect_bezier_points(query.iter());
+    let Some(bounds) = selection_bounds(&curves, &selection.curves()) else {
+        return;
+    };
+    gizmos.rect_2d(
+        Isometry2d::from_translation(bounds.center()),
+        bounds.size(),
+        settings.color,
+    );
+    for corner in rect_corners(bounds) {
+        gizmos.circle_2d(
+            Isometry2d::from_translation(corner),
+            settings.handle_radius,
+            settings.color,
+        );
+    }
+    let top = Vec2::new(bounds.center().x, bounds.max.y);
+    let rotate = top + Vec2::Y * settings.rotate_offset;
+    gizmos.line_2d(top, rotate, settings.color);
+    gizmos.circle_2d(
+        Isometry2d::from_translation(rotate),
+        settings.handle_radius,
+        settings.color,
+    );
+}
+
+fn rect_corners(rect: Rect) -> [Vec2; 4] {
+    [
+        rect.min,
+        Vec2::new(rect.max.x, rect.min.y),
+        rect.max,
+        Vec2::new(rect.min.x, rect.max.y),
+    ]
+}
+
+/// Scales and rotates the selected curves while a `BezierTransformGizmo`
+/// handle is dragged with the left mouse button.
+#[allow(clippy::too_many_arguments)]
+pub fn bezier_transform_gizmo_tool(
+    mut commands: Commands,
+    mut settings: ResMut<BezierTransformGizmo>,
+    selection: Res<BezierSelection>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    query: Query<&ShapeType>,
+) {
+    if !settings.enabled || mouse.just_released(MouseButton::Left) {
+        settings.drag = None;
+        return;
+    }
+    let cursor = windows.single().ok().and_then(Window::cursor_position);
+    let Some((camera, camera_transform)) = cameras.iter().find(|(camera, _)| camera.is_active)
+    else {
+        return;
+    };
+    let Some(position) =
+        cursor.and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+    else {
+        return;
+    };
+
+    if mouse.just_pressed(MouseButton::Left) {
+        let ids = selection.curves();
+        let curves = collect_bezier_points(query.iter());
+        let Some(bounds) = selection_bounds(&curves, &ids) else {
+            return;
+        };
+        let rotate_handle =
+            Vec2::new(bounds.center().x, bounds.max.y) + Vec2::Y * settings.rotate_offset;
+        let near = |handle: Vec2| handle.distance(position) <= settings.handle_radius;
+        let rotate = near(rotate_handle);
+        if rotate || rect_corners(bounds).into_iter().any(near) {
+            settings.drag = Some(TransformGizmoDrag {
+                rotate,
+                center: bounds.center(),
+                last: position,
+                ids,
+            });
+        }
+        return;
+    }
+    let Some(drag) = settings.drag.as_mut() else {
+        return;
+    };
+    if position == drag.last {
+        return;
+    }
+    let (from, to) = (drag.last - drag.center, position - drag.center);
+    for &id in &drag.ids {
+        if drag.rotate {
+            rotate_bezier(&mut commands, id, drag.center, from.angle_to(to));
+        } else if from.length() > f32::EPSILON && to.length() > f32::EPSILON {
+            scale_bezier(&mut commands, id, drag.center, to.length() / from.length());
+        }
+    }
+    drag.last = position;
+}
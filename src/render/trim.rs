@@ -0,0 +1,105 @@
+use super::*;
+
+/// CAD-style trimming: while enabled, clicking a curve removes the piece
+/// of it between the two intersections (where intersection markers sit)
+/// on either side of the click. A piece running to the curve's end is cut
+/// off there; a piece between two intersections leaves the curve split in
+/// two, the second half under a new id.
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct BezierTrimMode {
+    pub enabled: bool,
+}
+
+/// Parameters between which the piece of a curve around `t` lies, bounded
+/// by the parameters in `cuts` or the curve's ends. `None` when there are
+/// no cuts to trim against.
+pub fn trim_interval(cuts: &[f32], t: f32) -> Option<(f32, f32)> {
+    let start = cuts
+        .iter()
+        .copied()
+        .filter(|&cut| cut < t)
+        .fold(0.0, f32::max);
+    let end = cuts
+        .iter()
+        .copied()
+        .filter(|&cut| cut > t)
+        .fold(1.0, f32::min);
+    (start > 0.0 || end < 1.0).then_some((start, end))
+}
+
+/// Clicking a curve body in `BezierTrimMode` trims it at the intersections
+/// around the click, see `trim_bezier_at`.
+pub fn bezier_trim_click(
+    click: Trigger<Pointer<Click>>,
+    query: Query<&ShapeType>,
+    splines: Query<&BezierSpline>,
+    mut bezier_commands: ResMut<BezierCommands>,
+    mode: Res<BezierTrimMode>,
+    input: Res<BezierInputMap>,
+    locks: BezierLocks,
+) {
+    if !mode.enabled || click.button != input.drag_button {
+        return;
+    }
+    let Ok(ShapeType::Bezier(bezier_shape)) = query.get(click.target) else {
+        return;
+    };
+    if !matches!(bezier_shape.shape_type, BezierShapeType::BezierLine) || bezier_shape.closed {
+        return;
+    }
+    let id = bezier_shape.id;
+    // trimming a segment would pull it off the anchors it shares
+    if locks.curve_locked(id) || splines.iter().any(|spline| spline.segments.contains(&id)) {
+        return;
+    }
+    let Some(position) = click.hit.position else {
+        return;
+    };
+    bezier_commands.push(BezierCommand::Trim {
+        id,
+        position: position.truncate(),
+    });
+}
+
+/// Removes the piece of curve `id` around the point closest to `position`,
+/// bounded by the curve's intersections with every other curve. Returns
+/// the id of the curve spawned for the part after the piece, if the piece
+/// was in the middle; nothing changes when the curve crosses no other.
+pub fn trim_bezier_at(
+    commands: &mut Commands,
+    query: &mut Query<(Entity, &mut Shape, &mut ShapeType)>,
+    ids: &mut BezierIdAllocator,
+    style: &BezierStyle,
+    id: usize,
+    position: Vec2,
+) -> Option<usize> {
+    let mut curves = collect_bezier_points(query.iter().map(|(_, _, s)| s));
+    let curve = CubicBezier::from(curves.remove(&id)?);
+    let cuts: Vec<f32> = curves
+        .values()
+        .flat_map(|&other| intersections(&curve, &CubicBezier::from(other)))
+        .map(|(t, _, _)| t)
+        .filter(|&t| t > 0.0 && t < 1.0)
+        .collect();
+    let (t, _, _) = curve.closest_point(position);
+    let (start, end) = trim_interval(&cuts, t)?;
+
+    if start <= 0.0 {
+        update_bezier_in_place(style, query, id, curve.trim(end, 1.0).points());
+        None
+    } else if end >= 1.0 {
+        update_bezier_in_place(style, query, id, curve.trim(0.0, start).points());
+        None
+    } else {
+        update_bezier_in_place(style, query, id, curve.trim(0.0, start).points());
+        let second_id = ids.next_id();
+        let CubicBezier { a, b, c, d } = curve.trim(end, 1.0);
+        spawn_bezier_with_id(
+            commands,
+            style,
+            second_id,
+            bezier_open(style, second_id, a, b, c, d),
+        );
+        Some(second_id)
+    }
+}
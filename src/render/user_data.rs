@@ -0,0 +1,84 @@
+use super::*;
+
+/// Free-form tags of the curve on whose `BezierId` root it sits, e.g. a
+/// road's type or a wire's net name, for the app to give curves meaning.
+/// Written and read with the curve by `export_beziers` and
+/// `import_beziers`, and copied by `duplicate_bezier` and
+/// `spawn_mirrored_bezier`.
+#[derive(Component, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct BezierUserData(pub HashMap<String, String>);
+
+impl BezierUserData {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+    /// Sets `key` to `value`, returning the value it replaced.
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) -> Option<String> {
+        self.0.insert(key.into(), value.into())
+    }
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.0.remove(key)
+    }
+}
+
+/// Sets `key` to `value` in the `BezierUserData` of curve `id`, adding the
+/// component to its root if it has none.
+pub fn set_bezier_user_data(
+    commands: &mut Commands,
+    id: usize,
+    key: impl Into<String>,
+    value: impl Into<String>,
+) {
+    let (key, value) = (key.into(), value.into());
+    commands.queue(move |world: &mut World| {
+        let mut roots = world.query::<(Entity, &BezierId)>();
+        let Some(root) = roots
+            .iter(world)
+            .find_map(|(entity, root_id)| (root_id.0 == id).then_some(entity))
+        else {
+            return;
+        };
+        let mut root = world.entity_mut(root);
+        match root.get_mut::<BezierUserData>() {
+            Some(mut data) => {
+                data.insert(key, value);
+            }
+            None => {
+                root.insert(BezierUserData(HashMap::from([(key, value)])));
+            }
+        }
+    });
+}
+
+/// Replaces the `BezierUserData` of curve `id` with `data`.
+pub fn replace_bezier_user_data(commands: &mut Commands, id: usize, data: BezierUserData) {
+    commands.queue(move |world: &mut World| {
+        let mut roots = world.query::<(Entity, &BezierId)>();
+        if let Some(root) = roots
+            .iter(world)
+            .find_map(|(entity, root_id)| (root_id.0 == id).then_some(entity))
+        {
+            world.entity_mut(root).insert(data);
+        }
+    });
+}
+
+/// Copies the `BezierUserData` of curve `from`, if any, onto curve `to`, a
+/// copy of it spawned by the same `commands`.
+pub(crate) fn copy_bezier_user_data(commands: &mut Commands, from: usize, to: usize) {
+    commands.queue(move |world: &mut World| {
+        let mut roots = world.query::<(Entity, &BezierId, Option<&BezierUserData>)>();
+        let data = roots
+            .iter(world)
+            .find_map(|(_, root_id, data)| (root_id.0 == from).then_some(data))
+            .flatten()
+            .cloned();
+        let root = roots
+            .iter(world)
+            .find_map(|(entity, root_id, _)| (root_id.0 == to).then_some(entity));
+        if let (Some(data), Some(root)) = (data, root) {
+            world.entity_mut(root).insert(data);
+        }
+    });
+}
@@ -0,0 +1,177 @@
+use super::*;
+
+/// Put on a curve's `Start` or `End` entity to keep another curve's anchor
+/// on it: when the anchor moves, the `part` anchor of curve `id` moves
+/// along, with its control point so the other curve keeps its shape there.
+/// `weld_bezier_anchors` puts one on both anchors so either can be dragged.
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct WeldedTo {
+    pub id: usize,
+    pub part: BezierShapeType,
+}
+
+/// Welds the `part` anchor of curve `id` to the `other_part` anchor of
+/// curve `other_id`, both `Start` or `End`. The second anchor is moved
+/// onto the first on the next drag.
+pub fn weld_bezier_anchors(
+    commands: &mut Commands,
+    id: usize,
+    part: BezierShapeType,
+    other_id: usize,
+    other_part: BezierShapeType,
+) {
+    commands.queue(move |world: &mut World| {
+        let (Some(anchor), Some(other)) = (
+            find_bezier_part(world, id, &part),
+            find_bezier_part(world, other_id, &other_part),
+        ) else {
+            return;
+        };
+        world.entity_mut(anchor).insert(WeldedTo {
+            id: other_id,
+            part: other_part,
+        });
+        world.entity_mut(other).insert(WeldedTo { id, part });
+    });
+}
+
+/// Undoes `weld_bezier_anchors` for the `part` anchor of curve `id` and
+/// whichever anchor it was welded to.
+pub fn unweld_bezier_anchor(commands: &mut Commands, id: usize, part: BezierShapeType) {
+    commands.queue(move |world: &mut World| unweld_anchor(world, id, &part));
+}
+
+pub(crate) fn unweld_anchor(world: &mut World, id: usize, part: &BezierShapeType) {
+    let Some(anchor) = find_bezier_part(world, id, part) else {
+        return;
+    };
+    let Some(welded) = world.entity_mut(anchor).take::<WeldedTo>() else {
+        return;
+    };
+    if let Some(other) = find_bezier_part(world, welded.id, &welded.part) {
+        world.entity_mut(other).remove::<WeldedTo>();
+    }
+}
+
+/// Hands the weld of the `part` anchor of curve `from` to the same anchor
+/// of curve `to`, which takes its place, as when `remove_anchor` merges two
+/// segments. Whatever `to`'s anchor was welded to is unwelded.
+pub(crate) fn hand_over_weld(world: &mut World, from: usize, to: usize, part: BezierShapeType) {
+    unweld_anchor(world, to, &part);
+    let Some(anchor) = find_bezier_part(world, from, &part) else {
+        return;
+    };
+    let Some(welded) = world.entity_mut(anchor).take::<WeldedTo>() else {
+        return;
+    };
+    if let Some(other) = find_bezier_part(world, welded.id, &welded.part) {
+        world.entity_mut(other).insert(WeldedTo {
+            id: to,
+            part: part.clone(),
+        });
+    }
+    if let Some(anchor) = find_bezier_part(world, to, &part) {
+        world.entity_mut(anchor).insert(welded);
+    }
+}
+
+fn find_bezier_part(world: &mut World, id: usize, part: &BezierShapeType) -> Option<Entity> {
+    let mut query = world.query::<(Entity, &ShapeType)>();
+    query
+        .iter(world)
+        .find_map(|(entity, shape_type)| match shape_type {
+            // the marker, not a `pick_radius` hit circle of the same part
+            ShapeType::Bezier(bezier_shape)
+                if bezier_shape.id == id
+                    && bezier_shape.shape_type == *part
+                    && bezier_shape.point.is_some() =>
+            {
+                Some(entity)
+            }
+            _ => None,
+        })
+}
+
+/// Moves the anchors welded to curve `id`'s anchors onto them, one weld
+/// deep, after curve `id` was edited. Returns the welded curves' edits.
+pub(crate) fn follow_welded_anchors(
+    style: &BezierStyle,
+    query: &mut Query<(Entity, &mut Shape, &mut ShapeType)>,
+    welds: &Query<(Entity, &WeldedTo)>,
+    id: usize,
+    document: &mut Option<ResMut<BezierDocument>>,
+    modified: &mut EventWriter<BezierModified>,
+) -> Vec<BezierEdit> {
+    if welds.is_empty() {
+        return Vec::new();
+    }
+    let before = collect_bezier_points(query.iter().map(|(_, _, shape_type)| shape_type));
+    let mut curves = before.clone();
+    let Some(points) = curves.get(&id).copied() else {
+        return Vec::new();
+    };
+    let mut changed = BTreeMap::new();
+    for (entity, welded) in welds.iter() {
+        let Ok((_, _, ShapeType::Bezier(bezier_shape))) = query.get(entity) else {
+            continue;
+        };
+        let target = match bezier_shape.shape_type {
+            BezierShapeType::Start if bezier_shape.id == id => points[0],
+            BezierShapeType::End if bezier_shape.id == id => points[3],
+            _ => continue,
+        };
+        let Some(other) = curves.get_mut(&welded.id) else {
+            continue;
+        };
+        let (anchor, control) = match welded.part {
+            BezierShapeType::Start => (0, 1),
+            BezierShapeType::End => (3, 2),
+            _ => continue,
+        };
+        let delta = target - other[anchor];
+        if delta == Vec2::ZERO {
+            continue;
+        }
+        other[anchor] += delta;
+        other[control] += delta;
+        changed.insert(welded.id, *other);
+    }
+    let mut edits = Vec::new();
+    for (other_id, points) in changed {
+        update_bezier_in_place(style, query, other_id, points);
+        modified.write(BezierModified {
+            id: other_id,
+            points,
+        });
+        if let Some(document) = document.as_mut() {
+            document.write_back(other_id, points);
+        }
+        edits.push(BezierEdit {
+            id: other_id,
+            before: before[&other_id],
+            after: points,
+        });
+    }
+    edits
+}
+
+/// Welds anchors snapped together by a drag when
+/// `BezierSnapSettings::weld_on_snap` is set.
+pub fn weld_snapped_anchors(
+    mut commands: Commands,
+    mut snapped: EventReader<BezierAnchorSnapped>,
+    settings: Res<BezierSnapSettings>,
+) {
+    for event in snapped.read() {
+        if settings.weld_on_snap {
+            weld_bezier_anchors(
+                &mut commands,
+                event.id,
+                event.part.clone(),
+                event.to.id,
+                event.to.part.clone(),
+            );
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use super::*;
+
+impl BezierLayering {
+    /// Layer of curve `id`, see `layers`.
+    pub fn layer(&self, id: usize) -> i32 {
+        self.layers.get(&id).copied().unwrap_or(0)
+    }
+    /// `ids` from back to front: by layer, then older (lower) ids first.
+    pub fn stacking_order(&self, ids: impl IntoIterator<Item = usize>) -> Vec<usize> {
+        let mut order: Vec<usize> = ids.into_iter().collect();
+        order.sort_by_key(|&id| (self.layer(id), id));
+        order.dedup();
+        order
+    }
+    /// Moves curve `id` one place up the stacking order of `ids`, above the
+    /// curve right over it. Returns whether any layer changed.
+    pub fn raise(&mut self, ids: impl IntoIterator<Item = usize>, id: usize) -> bool {
+        self.move_in_order(ids, id, |index, len| (index + 1).min(len - 1))
+    }
+    /// Moves curve `id` one place down the stacking order of `ids`.
+    pub fn lower(&mut self, ids: impl IntoIterator<Item = usize>, id: usize) -> bool {
+        self.move_in_order(ids, id, |index, _| index.saturating_sub(1))
+    }
+    /// Puts curve `id` above every other curve of `ids`.
+    pub fn bring_to_front(&mut self, ids: impl IntoIterator<Item = usize>, id: usize) -> bool {
+        self.move_in_order(ids, id, |_, len| len - 1)
+    }
+    /// Puts curve `id` below every other curve of `ids`.
+    pub fn send_to_back(&mut self, ids: impl IntoIterator<Item = usize>, id: usize) -> bool {
+        self.move_in_order(ids, id, |_, _| 0)
+    }
+    /// Moves `id` to place `to(index, len)` of the stacking order and gives
+    /// every curve its own layer, so curves sharing one no longer tie.
+    /// Returns whether any layer changed.
+    fn move_in_order(
+        &mut self,
+        ids: impl IntoIterator<Item = usize>,
+        id: usize,
+        to: impl FnOnce(usize, usize) -> usize,
+    ) -> bool {
+        let mut order = self.stacking_order(ids);
+        let Some(index) = order.iter().position(|&other| other == id) else {
+            return false;
+        };
+        let target = to(index, order.len());
+        order.remove(index);
+        order.insert(target, id);
+        let mut changed = false;
+        for (layer, other) in order.into_iter().enumerate() {
+            changed |= self.layers.insert(other, layer as i32) != Some(layer as i32);
+        }
+        changed
+    }
+}
+
+fn restack_bezier(
+    commands: &mut Commands,
+    id: usize,
+    restack: impl FnOnce(&mut BezierLayering, Vec<usize>, usize) -> bool + Send + 'static,
+) {
+    commands.queue(move |world: &mut World| {
+        let mut roots = world.query::<&BezierId>();
+        let ids: Vec<usize> = roots.iter(world).map(|root_id| root_id.0).collect();
+        let mut layering = world.resource::<BezierStyle>().layering.clone();
+        // only touch the style when the order changes, as that restyles
+        // every curve
+        if restack(&mut layering, ids, id) {
+            world.resource_mut::<BezierStyle>().layering = layering;
+        }
+    });
+}
+
+/// Moves curve `id` above the curve right over it, see
+/// `BezierLayering::raise`.
+pub fn raise_bezier(commands: &mut Commands, id: usize) {
+    restack_bezier(commands, id, |layering, ids, id| layering.raise(ids, id));
+}
+
+/// Moves curve `id` below the curve right under it.
+pub fn lower_bezier(commands: &mut Commands, id: usize) {
+    restack_bezier(commands, id, |layering, ids, id| layering.lower(ids, id));
+}
+
+/// Puts curve `id` above every other curve.
+pub fn bring_to_front(commands: &mut Commands, id: usize) {
+    restack_bezier(commands, id, |layering, ids, id| {
+        layering.bring_to_front(ids, id)
+    });
+}
+
+/// Puts curve `id` below every other curve.
+pub fn send_to_back(commands: &mut Commands, id: usize) {
+    restack_bezier(commands, id, |layering, ids, id| {
+        layering.send_to_back(ids, id)
+    });
+}
+
+/// Brings curves to the front as a drag starts on them, so one lying under
+/// others stays on top while edited, see `BezierLayering::raise_dragged`.
+pub fn bring_dragged_beziers_to_front(
+    mut commands: Commands,
+    mut started: EventReader<BezierDragStarted>,
+    style: Res<BezierStyle>,
+) {
+    for event in started.read() {
+        if style.layering.raise_dragged {
+            bring_to_front(&mut commands, event.id);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use super::*;
+
+/// Keeps handles and strokes the same size on screen at any zoom: while
+/// `enabled`, `BezierStyle::intersection_radius`, `bezier_stroke_width`
+/// and `sketch_stroke_width` are set from these sizes, in logical pixels,
+/// under the active camera's projection, and the curves are restyled.
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct BezierScreenSizes {
+    pub enabled: bool,
+    pub handle_radius: f32,
+    pub stroke_width: f32,
+    pub sketch_stroke_width: f32,
+}
+
+impl Default for BezierScreenSizes {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            handle_radius: 6.0,
+            stroke_width: 4.0,
+            sketch_stroke_width: 1.0,
+        }
+    }
+}
+
+/// Applies `BezierScreenSizes` to `BezierStyle` when the zoom changed
+/// enough to notice.
+pub fn apply_bezier_screen_sizes(
+    sizes: Res<BezierScreenSizes>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut style: ResMut<BezierStyle>,
+) {
+    if !sizes.enabled {
+        return;
+    }
+    let Some(scale) = world_units_per_pixel(&cameras) else {
+        return;
+    };
+    let radius = sizes.handle_radius * scale;
+    // only restyle for a real change, not for float noise while zooming
+    let current = style.intersection_radius;
+    if (current - radius).abs() <= current * 0.05 && !sizes.is_changed() {
+        return;
+    }
+    style.intersection_radius = radius;
+    style.bezier_stroke_width = sizes.stroke_width * scale;
+    style.sketch_stroke_width = sizes.sketch_stroke_width * scale;
+}
@@ -0,0 +1,380 @@
+use bevy::math::Vec2;
+use bevy::prelude::{ButtonInput, Entity, KeyCode};
+use bezier::test_utils::{BezierTestApp, TEST_TOLERANCE, assert_points_near};
+use bezier::{
+    BezierCommand, BezierCommands, BezierDrag, BezierEdit, BezierFunction, BezierHistory, BezierId,
+    BezierShapeType, BezierSpline, BezierStep, HandleMode, ShapeType, lock_bezier,
+    restore_bezier_step,
+};
+
+const CURVE: [Vec2; 4] = [
+    Vec2::new(-200.0, 0.0),
+    Vec2::new(-100.0, 100.0),
+    Vec2::new(100.0, 100.0),
+    Vec2::new(200.0, 0.0),
+];
+
+fn moved(points: [Vec2; 4], delta: Vec2) -> [Vec2; 4] {
+    points.map(|point| point + delta)
+}
+
+#[test]
+fn dragging_an_anchor_moves_only_that_anchor() {
+    let mut app = BezierTestApp::default();
+    let id = app.spawn_curve(CURVE);
+    app.drag(id, BezierShapeType::End, Vec2::new(10.0, 20.0));
+    let [a, b, c, d] = CURVE;
+    // screen y points down, world y up
+    app.assert_points(id, [a, b, c, d + Vec2::new(10.0, -20.0)]);
+}
+
+#[test]
+fn dragging_the_body_moves_the_whole_curve() {
+    let mut app = BezierTestApp::default();
+    let id = app.spawn_curve(CURVE);
+    app.drag(id, BezierShapeType::BezierLine, Vec2::new(30.0, -15.0));
+    app.assert_points(id, moved(CURVE, Vec2::new(30.0, 15.0)));
+}
+
+#[test]
+fn a_drag_follows_the_pointer_across_moves() {
+    let mut app = BezierTestApp::default();
+    let id = app.spawn_curve(CURVE);
+    app.drag_start(id, BezierShapeType::BezierLine);
+    for _ in 0..4 {
+        app.drag_by(Vec2::new(5.0, 5.0));
+    }
+    app.drag_end();
+    app.assert_points(id, moved(CURVE, Vec2::new(20.0, -20.0)));
+}
+
+#[test]
+fn a_constrained_drag_keeps_its_first_axis() {
+    let mut app = BezierTestApp::default();
+    let id = app.spawn_curve(CURVE);
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::ShiftLeft);
+    app.drag_start(id, BezierShapeType::BezierLine);
+    app.drag_by(Vec2::new(10.0, 2.0));
+    app.drag_by(Vec2::new(0.0, 30.0));
+    app.drag_end();
+    app.assert_points(id, moved(CURVE, Vec2::new(10.0, 0.0)));
+}
+
+#[test]
+fn drags_update_shapes_in_place() {
+    let mut app = BezierTestApp::default();
+    let id = app.spawn_curve(CURVE);
+    let mut before = app.parts(id);
+    app.drag(id, BezierShapeType::ControlStart, Vec2::new(-40.0, 0.0));
+    let mut after = app.parts(id);
+    before.sort();
+    after.sort();
+    assert_eq!(before, after);
+}
+
+#[test]
+fn spline_neighbours_follow_a_shared_anchor() {
+    let mut app = BezierTestApp::default();
+    let segments = app.spawn_spline(&[
+        Vec2::new(-300.0, 0.0),
+        Vec2::new(-250.0, 50.0),
+        Vec2::new(-150.0, 50.0),
+        Vec2::new(-100.0, 0.0),
+        Vec2::new(-50.0, -50.0),
+        Vec2::new(50.0, -50.0),
+        Vec2::new(100.0, 0.0),
+    ]);
+    app.drag(segments[0], BezierShapeType::End, Vec2::new(0.0, -40.0));
+    let joint = Vec2::new(-100.0, 40.0);
+    assert!(app.points(segments[0])[3].distance(joint) < 1e-3);
+    assert!(app.points(segments[1])[0].distance(joint) < 1e-3);
+}
+
+#[test]
+fn a_drag_is_one_undo_step() {
+    let mut app = BezierTestApp::default();
+    let id = app.spawn_curve(CURVE);
+    app.drag_start(id, BezierShapeType::Start);
+    app.drag_by(Vec2::new(10.0, 0.0));
+    app.drag_by(Vec2::new(10.0, 0.0));
+    app.drag_end();
+    let mut history = app.world_mut().resource_mut::<BezierHistory>();
+    let [a, b, c, d] = CURVE;
+    let edit = BezierEdit {
+        id,
+        before: [a + Vec2::new(20.0, 0.0), b, c, d],
+        after: CURVE,
+    };
+    assert_eq!(history.undo(), Some(BezierStep::Edits(vec![edit])));
+    assert!(!history.can_undo());
+}
+
+fn undo(app: &mut BezierTestApp) {
+    let step = app
+        .world_mut()
+        .resource_mut::<BezierHistory>()
+        .undo()
+        .expect("there is a step to undo");
+    let world = app.world_mut();
+    restore_bezier_step(&mut world.commands(), step);
+    world.flush();
+    app.update();
+}
+
+#[test]
+fn undoing_a_delete_brings_the_curve_back() {
+    let mut app = BezierTestApp::default();
+    let id = app.spawn_curve(CURVE);
+    app.world_mut()
+        .resource_mut::<BezierCommands>()
+        .push(BezierCommand::DeleteCurve { id });
+    app.update();
+    assert!(app.parts(id).is_empty());
+    undo(&mut app);
+    app.assert_points(id, CURVE);
+}
+
+#[test]
+fn spline_neighbours_are_undone_with_the_move() {
+    let mut app = BezierTestApp::default();
+    let points = [
+        Vec2::new(-300.0, 0.0),
+        Vec2::new(-250.0, 50.0),
+        Vec2::new(-150.0, 50.0),
+        Vec2::new(-100.0, 0.0),
+        Vec2::new(-50.0, -50.0),
+        Vec2::new(50.0, -50.0),
+        Vec2::new(100.0, 0.0),
+    ];
+    let segments = app.spawn_spline(&points);
+    app.world_mut()
+        .resource_mut::<BezierCommands>()
+        .push(BezierCommand::MovePoint {
+            id: segments[0],
+            part: BezierShapeType::End,
+            delta: Vec2::new(0.0, 40.0),
+        });
+    app.update();
+    undo(&mut app);
+    app.assert_points(segments[0], [points[0], points[1], points[2], points[3]]);
+    app.assert_points(segments[1], [points[3], points[4], points[5], points[6]]);
+}
+
+#[test]
+fn spline_neighbours_follow_set_points() {
+    let mut app = BezierTestApp::default();
+    let points = [
+        Vec2::new(-300.0, 0.0),
+        Vec2::new(-250.0, 50.0),
+        Vec2::new(-150.0, 50.0),
+        Vec2::new(-100.0, 0.0),
+        Vec2::new(-50.0, -50.0),
+        Vec2::new(50.0, -50.0),
+        Vec2::new(100.0, 0.0),
+    ];
+    let segments = app.spawn_spline(&points);
+    let joint = Vec2::new(-100.0, 40.0);
+    app.world_mut()
+        .resource_mut::<BezierCommands>()
+        .push(BezierCommand::SetPoints {
+            id: segments[0],
+            points: [points[0], points[1], points[2], joint],
+        });
+    app.update();
+    assert!(app.points(segments[1])[0].distance(joint) < 1e-3);
+    undo(&mut app);
+    app.assert_points(segments[1], [points[3], points[4], points[5], points[6]]);
+}
+
+#[test]
+fn locked_curves_stay_put() {
+    let mut app = BezierTestApp::default();
+    let id = app.spawn_curve(CURVE);
+    let world = app.world_mut();
+    lock_bezier(&mut world.commands(), id);
+    world.flush();
+    app.update();
+    app.drag(id, BezierShapeType::BezierLine, Vec2::new(50.0, 50.0));
+    app.assert_points(id, CURVE);
+}
+
+#[test]
+fn continuity_moves_are_undone_with_the_edit() {
+    let mut app = BezierTestApp::default();
+    let segments = app.spawn_spline(&[
+        Vec2::new(-300.0, 0.0),
+        Vec2::new(-250.0, 50.0),
+        Vec2::new(-150.0, 50.0),
+        Vec2::new(-100.0, 0.0),
+        Vec2::new(-50.0, -50.0),
+        Vec2::new(50.0, -50.0),
+        Vec2::new(100.0, 0.0),
+    ]);
+    let mut commands = app.world_mut().resource_mut::<BezierCommands>();
+    commands.push(BezierCommand::SetJointMode {
+        id: segments[0],
+        joint: 0,
+        mode: HandleMode::Curvature,
+    });
+    app.update();
+    app.update();
+    let before = [app.points(segments[0]), app.points(segments[1])];
+    app.world_mut()
+        .resource_mut::<BezierCommands>()
+        .push(BezierCommand::MovePoint {
+            id: segments[0],
+            part: BezierShapeType::ControlEnd,
+            delta: Vec2::new(-30.0, 20.0),
+        });
+    app.update();
+    app.update();
+    // the far handle of the next segment moved to keep the curvature
+    assert_ne!(app.points(segments[1])[2], before[1][2]);
+    undo(&mut app);
+    app.assert_points(segments[0], before[0]);
+    app.assert_points(segments[1], before[1]);
+}
+
+/// The parts of curve `id` and the point each draws, in entity order.
+fn part_points(app: &mut BezierTestApp, id: usize) -> Vec<(Entity, Option<Vec2>)> {
+    let mut parts = app.parts(id);
+    parts.sort();
+    parts
+        .into_iter()
+        .map(|entity| match app.world().get::<ShapeType>(entity) {
+            Some(ShapeType::Bezier(bezier_shape)) => (entity, bezier_shape.point),
+            _ => (entity, None),
+        })
+        .collect()
+}
+
+#[test]
+fn a_curve_missing_a_handle_is_left_alone() {
+    let mut app = BezierTestApp::default();
+    let id = app.spawn_curve(CURVE);
+    let handle = app.part(id, BezierShapeType::ControlStart);
+    app.world_mut().despawn(handle);
+    app.update();
+    let before = part_points(&mut app, id);
+    app.drag_start(id, BezierShapeType::Start);
+    app.drag_by(Vec2::new(30.0, 30.0));
+    app.drag_end();
+    assert!(app.world().resource::<BezierDrag>().pointers.is_empty());
+    assert_eq!(part_points(&mut app, id), before);
+}
+
+#[test]
+fn function_curves_are_constrained_during_the_drag() {
+    let mut app = BezierTestApp::default();
+    let id = app.spawn_curve(CURVE);
+    let mut roots = app.world_mut().query::<(Entity, &BezierId)>();
+    let root = roots
+        .iter(app.world())
+        .find_map(|(entity, root_id)| (root_id.0 == id).then_some(entity))
+        .expect("spawned curves have a root");
+    app.world_mut().entity_mut(root).insert(BezierFunction);
+    app.drag(id, BezierShapeType::ControlStart, Vec2::new(-300.0, 0.0));
+    let [a, b, c, d] = CURVE;
+    let constrained = [a, Vec2::new(a.x, b.y), c, d];
+    app.assert_points(id, constrained);
+    let step = app.world_mut().resource_mut::<BezierHistory>().undo();
+    let Some(BezierStep::Edits(edits)) = step else {
+        panic!("the drag should be one step of point edits, got {step:?}");
+    };
+    assert_points_near(edits[0].before, constrained, TEST_TOLERANCE);
+}
+
+fn spline_count(app: &mut BezierTestApp) -> usize {
+    let mut splines = app.world_mut().query::<&BezierSpline>();
+    splines.iter(app.world()).count()
+}
+
+#[test]
+fn the_click_ending_a_drag_inserts_no_anchor() {
+    let mut app = BezierTestApp::default();
+    let id = app.spawn_curve(CURVE);
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::ControlLeft);
+    app.drag_start(id, BezierShapeType::BezierLine);
+    app.drag_by(Vec2::new(30.0, 0.0));
+    app.click(id, BezierShapeType::BezierLine);
+    app.drag_end();
+    assert_eq!(spline_count(&mut app), 0);
+    app.click(id, BezierShapeType::BezierLine);
+    app.update();
+    assert_eq!(spline_count(&mut app), 1);
+}
+
+fn root(app: &mut BezierTestApp, id: usize) -> Option<Entity> {
+    let mut roots = app.world_mut().query::<(Entity, &BezierId)>();
+    roots
+        .iter(app.world())
+        .find_map(|(entity, root_id)| (root_id.0 == id).then_some(entity))
+}
+
+#[test]
+fn removing_an_anchor_keeps_the_first_segment() {
+    let mut app = BezierTestApp::default();
+    let points = [
+        Vec2::new(-300.0, 0.0),
+        Vec2::new(-250.0, 50.0),
+        Vec2::new(-150.0, 50.0),
+        Vec2::new(-100.0, 0.0),
+        Vec2::new(-50.0, -50.0),
+        Vec2::new(50.0, -50.0),
+        Vec2::new(100.0, 0.0),
+    ];
+    let segments = app.spawn_spline(&points);
+    let first = root(&mut app, segments[0]);
+    let mut parts = app.parts(segments[0]);
+    parts.sort();
+    app.world_mut()
+        .resource_mut::<BezierCommands>()
+        .push(BezierCommand::RemoveAnchor {
+            id: segments[0],
+            index: 1,
+        });
+    app.update();
+    assert_eq!(root(&mut app, segments[0]), first);
+    let mut after = app.parts(segments[0]);
+    after.sort();
+    assert_eq!(after, parts);
+    assert!(app.parts(segments[1]).is_empty());
+    let [a, _, _, d] = app.points(segments[0]);
+    assert!(a.distance(points[0]) < TEST_TOLERANCE);
+    assert!(d.distance(points[6]) < TEST_TOLERANCE);
+}
+
+#[test]
+fn undoing_a_joint_mode_change_restores_the_mode() {
+    let mut app = BezierTestApp::default();
+    let segments = app.spawn_spline(&[
+        Vec2::new(-300.0, 0.0),
+        Vec2::new(-250.0, 50.0),
+        Vec2::new(-150.0, 50.0),
+        Vec2::new(-100.0, 0.0),
+        Vec2::new(-50.0, -50.0),
+        Vec2::new(50.0, -50.0),
+        Vec2::new(100.0, 0.0),
+    ]);
+    let before = [app.points(segments[0]), app.points(segments[1])];
+    app.world_mut()
+        .resource_mut::<BezierCommands>()
+        .push(BezierCommand::SetJointMode {
+            id: segments[0],
+            joint: 0,
+            mode: HandleMode::Aligned,
+        });
+    app.update();
+    undo(&mut app);
+    let mut splines = app.world_mut().query::<&BezierSpline>();
+    let spline = splines
+        .single(app.world())
+        .expect("the spline is still there");
+    assert_eq!(spline.handle_mode(0), HandleMode::Free);
+    app.assert_points(segments[0], before[0]);
+    app.assert_points(segments[1], before[1]);
+}